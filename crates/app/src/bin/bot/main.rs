@@ -1,4 +1,4 @@
-use std::env;
+use std::sync::Arc;
 
 use teloxide::update_listeners::webhooks;
 use teloxide::{
@@ -6,10 +6,12 @@ use teloxide::{
     utils::command::BotCommands, Bot,
 };
 
-use app::bot::{build_storage, schema_and_storage};
+use app::bot::{build_storage, schema_and_storage, TokenStorage};
 use app::formatting::format_logs;
 use internal::commands::Command;
+use internal::config::AppConfig;
 use internal::errors::NetworkError;
+use internal::pagination::ResultCache;
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -23,21 +25,31 @@ async fn main() -> eyre::Result<()> {
         Command::descriptions()
     );
 
-    let token = env!("TELEGRAM_BOT_TOKEN");
+    let app_config = Arc::new(AppConfig::load()?);
+
+    let token = &app_config.telegram_bot_token;
     let bot = Bot::new(token);
 
-    let port = env!("PORT").parse::<u16>()?;
-    let host = env!("HOST");
+    let (port, host) = (app_config.port, &app_config.host);
     let addr = ([0, 0, 0, 0], port).into();
     let url = reqwest::Url::parse(&format!("{host}/bot{token}"))?;
 
+    let (token_store, snapshot_store) = TokenStorage::from_config(&app_config).build()?;
+    let result_cache = Arc::new(ResultCache::default());
+
     // bot.delete_webhook().await?;
     bot.set_my_commands(Command::bot_commands()).await?;
 
     // [!!] Must be after `bot.delete_webhook()` [!!]
-    let update_listener =
-        app::net::webhook_with_custom_server(bot.clone(), webhooks::Options::new(addr, url))
-            .await?;
+    let update_listener = app::net::webhook_with_custom_server(
+        bot.clone(),
+        webhooks::Options::new(addr, url),
+        token_store.clone(),
+        snapshot_store.clone(),
+        app_config.clone(),
+        result_cache.clone(),
+    )
+    .await?;
     let err_handler =
         LoggingErrorHandler::with_custom_text(NetworkError::UpdateListenerError.to_string());
 
@@ -47,7 +59,13 @@ async fn main() -> eyre::Result<()> {
     log::info!("[ LOG ] 🚀 <| Bot is running |> ");
 
     Dispatcher::builder(bot, schema)
-        .dependencies(dptree::deps![storage])
+        .dependencies(dptree::deps![
+            storage,
+            token_store,
+            snapshot_store,
+            app_config,
+            result_cache
+        ])
         .build()
         .dispatch_with_listener(update_listener, err_handler)
         .await;
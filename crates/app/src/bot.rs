@@ -10,9 +10,15 @@ use teloxide::dptree;
 use teloxide::prelude::{DependencyMap, Handler, Update};
 
 use internal::commands::Command;
+use internal::config::AppConfig;
+use internal::db::{
+    FileTokenStore, InMemTokenStore, RedisTokenStore, SnapshotStore, TokenStore,
+    WrappedSnapshotStore, WrappedTokenStore,
+};
 use internal::dialogue::DialogueData;
 use internal::handlers::{
-    handle_callback, handle_commands, handle_text, handle_unknown_command, is_other_command,
+    handle_callback, handle_commands, handle_inline_query, handle_text, handle_unknown_command,
+    is_other_command,
 };
 
 use error_traits::MergeOkErr;
@@ -46,10 +52,15 @@ where
         .branch(unknown_commands)
         .branch(text);
     let callback_handler = Update::filter_callback_query().endpoint(handle_callback);
-    let main_handler = dptree::entry()
+    let dialogue_handler = dptree::entry()
         .enter_dialogue::<Update, S, DialogueData>()
         .branch(message_handler)
         .branch(callback_handler);
+    // Inline queries don't come from a chat, so there is no dialogue for them.
+    let inline_query_handler = Update::filter_inline_query().endpoint(handle_inline_query);
+    let main_handler = dptree::entry()
+        .branch(inline_query_handler)
+        .branch(dialogue_handler);
     (main_handler, storage)
 }
 
@@ -95,3 +106,54 @@ async fn build_stor_internal(
             redis_storage
         })
 }
+
+/// Where users' `YouTube` access tokens (and snapshots of their playlists) are kept.
+pub enum TokenStorage<'a> {
+    Redis(&'a str),
+    File(&'a str),
+    InMem,
+}
+
+impl<'a> TokenStorage<'a> {
+    /// `Redis` if its url is set, then file if its path is set, otherwise memory.
+    pub fn from_config(config: &'a AppConfig) -> Self {
+        match (
+            &config.redis_youtube_access_token_storage,
+            &config.file_youtube_access_token_storage,
+        ) {
+            (Some(url), _) => TokenStorage::Redis(url),
+            (None, Some(path)) => TokenStorage::File(path),
+            (None, None) => TokenStorage::InMem,
+        }
+    }
+
+    /// Same backend serves as both stores.
+    pub fn build(&self) -> eyre::Result<(WrappedTokenStore, WrappedSnapshotStore)> {
+        let stores = match *self {
+            TokenStorage::Redis(url) => shared(RedisTokenStore::open(url)?),
+            TokenStorage::File(path) => shared(FileTokenStore::new(path)),
+            TokenStorage::InMem => shared(InMemTokenStore::default()),
+        };
+        log::info!(
+            "[ LOG ] 🔑 <| Using `{}` to store access tokens and playlist snapshots. |> ",
+            self.name()
+        );
+        Ok(stores)
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            TokenStorage::Redis(..) => "RedisTokenStore",
+            TokenStorage::File(..) => "FileTokenStore",
+            TokenStorage::InMem => "InMemTokenStore",
+        }
+    }
+}
+
+fn shared<S>(store: S) -> (WrappedTokenStore, WrappedSnapshotStore)
+where
+    S: TokenStore + SnapshotStore + 'static,
+{
+    let store = Arc::new(store);
+    (store.clone(), store)
+}
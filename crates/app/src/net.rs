@@ -3,11 +3,15 @@ use std::future::Future;
 use std::net::SocketAddr;
 
 use axum::routing::any;
-use teloxide::requests::Requester;
+use axum::Extension;
 use teloxide::stop::StopToken;
 use teloxide::update_listeners::{webhooks, UpdateListener};
+use teloxide::{Bot, RequestError};
 
 use internal::auth_server::handle_auth_code;
+use internal::config::WrappedConfig;
+use internal::db::{WrappedSnapshotStore, WrappedTokenStore};
+use internal::pagination::WrappedResultCache;
 
 #[allow(clippy::expect_used)]
 fn spawn_background_server(
@@ -30,22 +34,29 @@ fn spawn_background_server(
     tokio::spawn(serve);
 }
 
-pub async fn webhook_with_custom_server<R>(
-    bot: R,
+pub async fn webhook_with_custom_server(
+    bot: Bot,
     options: webhooks::Options,
-) -> Result<impl UpdateListener<Err = Infallible>, R::Err>
-where
-    R: Requester + Send + 'static,
-    R::DeleteWebhook: Send,
-{
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
+) -> Result<impl UpdateListener<Err = Infallible>, RequestError> {
     let webhooks::Options { address, .. } = options;
     let url = options.url.clone();
 
-    let (mut update_listener, stop_flag, app) = webhooks::axum_to_router(bot, options).await?;
+    let (mut update_listener, stop_flag, app) =
+        webhooks::axum_to_router(bot.clone(), options).await?;
     let stop_token = update_listener.stop_token();
 
     let app = app.route(url.path(), any(serve_all));
-    let app = app.route("/google_callback_auth_code", any(handle_auth_code));
+    let app = app
+        .route("/google_callback_auth_code", any(handle_auth_code))
+        .layer(Extension(bot))
+        .layer(Extension(token_store))
+        .layer(Extension(snapshot_store))
+        .layer(Extension(app_config))
+        .layer(Extension(result_cache));
 
     spawn_background_server(address, app, stop_flag, stop_token);
 
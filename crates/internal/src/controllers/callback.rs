@@ -1,7 +1,10 @@
 use teloxide::dispatching::dialogue::GetChatId;
 use teloxide::Bot;
 
+use crate::model::config::types::WrappedConfig;
+use crate::model::db::types::{WrappedSnapshotStore, WrappedTokenStore};
 use crate::model::dialogue::types::TheDialogue;
+use crate::model::pagination::types::WrappedResultCache;
 use crate::StdRes;
 use teloxide::types::CallbackQuery;
 
@@ -11,6 +14,10 @@ pub async fn handle_callback(
     bot: Bot,
     callback: CallbackQuery,
     dialogue: TheDialogue,
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
 ) -> StdRes<(), ()> {
     log::info!(" [:: LOG ::]     @[fn]:[controllers::handle_callback] :: [Started]");
 
@@ -21,11 +28,24 @@ pub async fn handle_callback(
 
     let chat_id = callback.chat_id().ok_or_else(log_err)?;
 
+    let user_id = callback.from.id;
     let sendable = crate::model::handlers::callback::common::handle_callback(
-        callback.clone(),
+        callback,
         dialogue.clone(),
+        &result_cache,
+    )
+    .await;
+    update_view(
+        &bot,
+        chat_id,
+        sendable,
+        dialogue,
+        Some(user_id),
+        token_store.as_ref(),
+        snapshot_store.as_ref(),
+        &app_config,
+        &result_cache,
     )
     .await;
-    update_view(&bot, chat_id, sendable, dialogue, callback.into()).await;
     Ok(())
 }
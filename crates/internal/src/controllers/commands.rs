@@ -4,7 +4,10 @@ use teloxide::utils::command::BotCommands;
 use teloxide::Bot;
 
 use crate::model::commands::types::Command;
+use crate::model::config::types::WrappedConfig;
+use crate::model::db::types::{WrappedSnapshotStore, WrappedTokenStore};
 use crate::model::dialogue::types::TheDialogue;
+use crate::model::pagination::types::WrappedResultCache;
 use crate::view::funcs::update_view;
 use crate::view::types::Sendable;
 use crate::StdRes;
@@ -25,6 +28,10 @@ pub async fn handle_unknown_command(
     bot: Bot,
     msg: Message,
     dialogue: TheDialogue,
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
 ) -> StdRes<(), ()> {
     log::info!(" [:: LOG ::]     @[fn]:[controllers::handle_unknown_command] :: [Started]");
 
@@ -33,24 +40,50 @@ pub async fn handle_unknown_command(
         msg.chat.id,
         Sendable::SendError("Unknown command ❌"),
         dialogue,
-        None,
+        msg.from().map(|u| u.id),
+        token_store.as_ref(),
+        snapshot_store.as_ref(),
+        &app_config,
+        &result_cache,
     )
     .await;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_commands(
     bot: Bot,
     msg: Message,
     dialogue: TheDialogue,
     cmd: Command,
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
 ) -> StdRes<(), ()> {
     log::info!(" [:: LOG ::]     @[fn]:[controllers::handle_commands] :: [Started]");
 
     let chat_id = msg.chat.id;
-    let sendable =
-        crate::model::handlers::commands::handle_commands(msg, dialogue.clone(), cmd).await;
+    let user_id = msg.from().map(|u| u.id);
+    let sendable = crate::model::handlers::commands::handle_commands(
+        msg,
+        dialogue.clone(),
+        cmd,
+        token_store.as_ref(),
+    )
+    .await;
 
-    update_view(&bot, chat_id, sendable, dialogue, None).await;
+    update_view(
+        &bot,
+        chat_id,
+        sendable,
+        dialogue,
+        user_id,
+        token_store.as_ref(),
+        snapshot_store.as_ref(),
+        &app_config,
+        &result_cache,
+    )
+    .await;
     Ok(())
 }
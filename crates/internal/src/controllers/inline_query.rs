@@ -0,0 +1,33 @@
+use teloxide::types::InlineQuery;
+use teloxide::Bot;
+
+use crate::model::config::types::WrappedConfig;
+use crate::model::db::types::WrappedTokenStore;
+use crate::model::pagination::types::WrappedResultCache;
+use crate::view::funcs::answer_inline_query;
+use crate::StdRes;
+
+pub async fn handle_inline_query(
+    bot: Bot,
+    query: InlineQuery,
+    token_store: WrappedTokenStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
+) -> StdRes<(), ()> {
+    log::info!(" [:: LOG ::]     @[fn]:[controllers::handle_inline_query] :: [Started]");
+
+    let log_prefix = " [:: LOG ::]     @[fn]:[controllers::handle_inline_query]";
+    let res = crate::model::handlers::inline_query::handle_inline_query(
+        &query.query,
+        query.from.id,
+        token_store.as_ref(),
+        &app_config,
+        &result_cache,
+    )
+    .await;
+    match res {
+        Ok(opt_results) => answer_inline_query(&bot, query.id, opt_results).await,
+        Err(e) => log::error!("{log_prefix}{e:?}"),
+    }
+    Ok(())
+}
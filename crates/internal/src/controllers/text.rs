@@ -1,14 +1,37 @@
 use teloxide::prelude::Message;
 use teloxide::Bot;
 
+use crate::model::config::types::WrappedConfig;
+use crate::model::db::types::{WrappedSnapshotStore, WrappedTokenStore};
 use crate::model::dialogue::types::TheDialogue;
+use crate::model::pagination::types::WrappedResultCache;
 use crate::view::funcs::update_view;
 use crate::StdRes;
 
-pub async fn handle_text(bot: Bot, msg: Message, dialogue: TheDialogue) -> StdRes<(), ()> {
+pub async fn handle_text(
+    bot: Bot,
+    msg: Message,
+    dialogue: TheDialogue,
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
+) -> StdRes<(), ()> {
     log::info!(" [:: LOG ::]     @[fn]:[controllers::handle_text] :: [Started]");
     let chat_id = msg.chat.id;
+    let user_id = msg.from().map(|u| u.id);
     let sendable = crate::model::handlers::text::handle_text(msg, dialogue.clone()).await;
-    update_view(&bot, chat_id, sendable, dialogue, None).await;
+    update_view(
+        &bot,
+        chat_id,
+        sendable,
+        dialogue,
+        user_id,
+        token_store.as_ref(),
+        snapshot_store.as_ref(),
+        &app_config,
+        &result_cache,
+    )
+    .await;
     Ok(())
 }
@@ -10,6 +10,10 @@ pub mod auth_server {
     pub use crate::model::auth_server::{handle_auth_code, serve_all};
 }
 
+pub mod config {
+    pub use crate::model::config::types::{AppConfig, WrappedConfig, CONFIG_PATH_VAR};
+}
+
 pub mod commands {
     pub use crate::model::commands::types::Command;
 }
@@ -19,6 +23,7 @@ pub mod handlers {
     pub use crate::controllers::commands::{
         handle_commands, handle_unknown_command, is_other_command,
     };
+    pub use crate::controllers::inline_query::handle_inline_query;
     pub use crate::controllers::text::handle_text;
 }
 
@@ -26,6 +31,19 @@ pub mod errors {
     pub use crate::model::errors::*;
 }
 
+pub mod db {
+    pub use crate::model::db::traits::{SnapshotStore, TokenStore};
+    pub use crate::model::db::types::{
+        FileTokenStore, InMemTokenStore, LoginState, RedisTokenStore, WrappedSnapshotStore,
+        WrappedTokenStore,
+    };
+    pub use crate::model::youtube::types::YouTubeAccessToken;
+}
+
+pub mod pagination {
+    pub use crate::model::pagination::types::{ResultCache, WrappedResultCache};
+}
+
 pub mod dialogue {
     pub use crate::model::dialogue::types::DialogueData;
 }
@@ -38,5 +56,6 @@ pub(crate) mod view {
 pub(crate) mod controllers {
     pub(crate) mod callback;
     pub(crate) mod commands;
+    pub(crate) mod inline_query;
     pub(crate) mod text;
 }
@@ -1,39 +1,105 @@
-use axum::{body::Body, body::BoxBody, http::Request};
-use error_traits::PassErrWith;
-use google_youtube3::oauth2::read_application_secret;
-use maptypings::{ErrIf, MapType, WrapInRes};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse};
+use axum::{body::Body, http::Request, Extension};
+use error_traits::LogErr;
+use google_youtube3::oauth2::{read_application_secret, ApplicationSecret};
+use maptypings::{ForgetValue, MapType, WrapInRes};
 use reqwest::RequestBuilder;
+use teloxide::requests::Requester;
+use teloxide::types::UserId;
+use teloxide::Bot;
 
 use thiserror::Error;
 
-use crate::model::db::{combine_old_new_tokens, set_access_token};
+use crate::model::config::types::{AppConfig, WrappedConfig};
+use crate::model::db::funcs::combine_old_new_tokens;
+use crate::model::db::traits::TokenStore;
+use crate::model::db::types::{LoginState, WrappedSnapshotStore, WrappedTokenStore};
 use crate::model::net::funcs::{build_post_request, find_by_key};
-use crate::model::net::types::{
-    GET_ACCESS_TOKEN_URL, KV_SEP, PAIR_SEP, QUERY_SEPARATOR, STATE_CODE,
-};
+use crate::model::net::types::{KV_SEP, PAIR_SEP};
+use crate::model::pagination::types::WrappedResultCache;
 use crate::model::utils::PassWith;
 use crate::model::youtube::types::YouTubeAccessToken;
-use crate::Res;
+use crate::view::funcs::execute_command;
+use crate::{Res, StdRes};
 
 mod keys {
     pub(super) const STATE: &str = "state";
-    pub(super) const STATE_CODE: &str = "state_code";
-    pub(super) const FOR_USER: &str = "for_user";
     pub(super) const CODE: &str = "code";
+    pub(super) const ERROR: &str = "error";
 }
 
-struct User<T>(T);
+/// Small html pages shown in browser when authorization ends.
+mod pages {
+    fn page(title: &str, body: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\
+            <html><head><meta charset=\"utf-8\">\
+            <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+            <title>{title}</title></head>\
+            <body style=\"font-family: sans-serif; text-align: center; margin-top: 10%\">\
+            <h1>{title}</h1>{body}</body></html>"
+        )
+    }
+
+    pub(super) fn success(bot_link: &str) -> String {
+        let body = format!(
+            "<p>You are logged in. You can close this page now.</p>\
+            <p><a href=\"{bot_link}\">Return to the bot 🤖</a></p>"
+        );
+        page("Logged in ✅", &body)
+    }
+
+    pub(super) fn denied() -> String {
+        let body =
+            "<p>Access to your YouTube account wasn't granted, so the bot can't search it.</p>\
+            <p>If it was a mistake, go back to the bot and request a new login link.</p>";
+        page("Access denied ❌", body)
+    }
+
+    pub(super) fn expired() -> String {
+        let body = "<p>This login link has expired or was already used.</p>\
+            <p>Go back to the bot and request a new one.</p>";
+        page("Login link expired ⌛", body)
+    }
+
+    pub(super) fn failed() -> String {
+        let body = "<p>Something went wrong while logging you in.</p>\
+            <p>Please, go back to the bot and try again later.</p>";
+        page("Login failed ⚠", body)
+    }
+}
+
+/// Nonce that was sent to Google as `state`.
+struct State<T>(T);
 
 struct AuthCode<T>(T);
 
-#[derive(Debug, Error)]
-#[error("internal error")]
-struct InternalError;
+/// `code`, or the reason why there is none.
+type AuthCodeRes<'a> = StdRes<AuthCode<&'a str>, AuthError>;
 
+/// Ways authorization can fail, each one has its own page.
 #[derive(Debug, Error)]
-enum StateCodeError {
-    #[error("state codes don't match")]
-    Mismatch,
+enum AuthError {
+    #[error("user denied access ({0})")]
+    Denied(String),
+    #[error("login state is missing, expired or was already used")]
+    InvalidState,
+    #[error("failed to exchange auth code for a token: {0}")]
+    TokenExchange(eyre::Report),
+    #[error("internal error: {0}")]
+    Internal(#[from] eyre::Report),
+}
+
+impl AuthError {
+    fn page(&self) -> (StatusCode, String) {
+        match self {
+            AuthError::Denied(..) => (StatusCode::OK, pages::denied()),
+            AuthError::InvalidState => (StatusCode::BAD_REQUEST, pages::expired()),
+            AuthError::TokenExchange(..) => (StatusCode::BAD_GATEWAY, pages::failed()),
+            AuthError::Internal(..) => (StatusCode::INTERNAL_SERVER_ERROR, pages::failed()),
+        }
+    }
 }
 
 pub async fn serve_all(req: Request<Body>) -> &'static str {
@@ -51,51 +117,145 @@ pub async fn serve_all(req: Request<Body>) -> &'static str {
 }
 
 pub async fn handle_auth_code(
+    Extension(bot): Extension<Bot>,
+    Extension(token_store): Extension<WrappedTokenStore>,
+    Extension(snapshot_store): Extension<WrappedSnapshotStore>,
+    Extension(app_config): Extension<WrappedConfig>,
+    Extension(result_cache): Extension<WrappedResultCache>,
     req: Request<Body>,
-) -> axum::response::Result<axum::response::Response> {
-    handle_auth_code_internal(req)
-        .await
-        .pass_err_with(|e| log::error!("error: {e}"))
-        .map_err(|_| InternalError.to_string())?
-        .in_ok()
+) -> axum::response::Response {
+    let op = "crates/internal/src/model/auth_server.rs:handle_auth_code";
+
+    let login_state = match handle_auth_code_internal(req, token_store.as_ref(), &app_config).await
+    {
+        Ok(login_state) => login_state,
+        Err(e) => {
+            log::error!("[LOG]  op: '{op}'  ( error: {e} )");
+            let (status, page) = e.page();
+            return (status, Html(page)).into_response();
+        }
+    };
+
+    // Page is shown right away, while the command may take a while.
+    let (bot_clone, app_config_clone) = (bot.clone(), app_config.clone());
+    tokio::spawn(async move {
+        let log_prefix = " [:: LOG ::]    | @:[fn::handle_auth_code] error: ";
+        let res = notify_and_resume(
+            bot_clone,
+            login_state,
+            token_store,
+            snapshot_store,
+            app_config_clone,
+            result_cache,
+        );
+        res.await.log_err(log_prefix).forget_val();
+    });
+
+    let bot_link = bot_link(&bot, &app_config).await;
+    (StatusCode::OK, Html(pages::success(&bot_link))).into_response()
 }
 
-async fn handle_auth_code_internal(req: Request<Body>) -> Res<axum::response::Response> {
-    let op = "crates/internal/src/model/auth_server.rs:handle_auth_code";
+/// Exchanges `auth code` for a token and saves it.
+/// Returns login state of the user who logged in.
+async fn handle_auth_code_internal(
+    req: Request<Body>,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<LoginState, AuthError> {
+    let op = "crates/internal/src/model/auth_server.rs:handle_auth_code_internal";
 
     log::info!("[LOG]  op: '{op}'  ( started [ OK ] )");
     log::info!("req is: {req:?}");
 
     let decoded_query = get_query(&req);
-    let (for_user, auth_code) = get_params_from_query(&decoded_query)?;
+    let (state, auth_code) =
+        get_params_from_query(&decoded_query).map_err(|_| AuthError::InvalidState)?;
 
-    let db_url = env!("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE");
+    // Consumed before anything else, so that the same link can't be used twice.
+    let login_state = token_store
+        .take_login_state(state.0)
+        .await?
+        .ok_or(AuthError::InvalidState)?;
+    let (for_user, code_verifier) = (&login_state.user_id, &login_state.code_verifier);
+    let auth_code = auth_code?;
 
-    let new_token = get_youtube_token(auth_code).await?;
-    let updated_token = combine_old_new_tokens(for_user.0, new_token, db_url);
-    let serialized_token = serde_json::to_string(&updated_token)?;
+    let (secret_path, token_url) = (
+        &app_config.path_to_google_oauth_secret,
+        &app_config.google_token_url,
+    );
+    let new_token = get_youtube_token(auth_code, code_verifier, secret_path, token_url)
+        .await
+        .map_err(AuthError::TokenExchange)?;
+    let updated_token = combine_old_new_tokens(for_user, new_token, token_store).await;
 
-    set_access_token(for_user.0, &serialized_token, db_url)?;
+    token_store.set_token(for_user, &updated_token).await?;
 
-    redirect_user(env!("BOT_REDIRECT_URL"))?
+    login_state
         .pass_with(|| log::info!("[LOG]  op: '{op}'  ( finished [ OK ] )"))
         .in_ok()
 }
 
-/// Build request access token using `auth code`.
-async fn request_access_token<S: AsRef<str>>(
+/// Confirms login in the chat where login was requested,
+/// then executes the command that was interrupted by login (if any) in the same chat.
+async fn notify_and_resume(
+    bot: Bot,
+    login_state: LoginState,
+    token_store: WrappedTokenStore,
+    snapshot_store: WrappedSnapshotStore,
+    app_config: WrappedConfig,
+    result_cache: WrappedResultCache,
+) -> eyre::Result<()> {
+    let user_id = UserId(login_state.user_id.parse::<u64>()?);
+    let chat_id = login_state.chat_id;
+
+    let Some(command) = login_state.pending_command else {
+        let text = "You are logged in ✅ \nNow you can use /search, /list or \
+            /search_videos_in_my_playlists commands 🚀";
+        bot.send_message(chat_id, text).await?;
+        return ().in_ok();
+    };
+
+    bot.send_message(chat_id, "You are logged in ✅ \nContinuing your request...")
+        .await?;
+    execute_command(
+        &bot,
+        chat_id,
+        user_id,
+        command,
+        token_store.as_ref(),
+        snapshot_store.as_ref(),
+        &app_config,
+        &result_cache,
+    )
+    .await;
+    ().in_ok()
+}
+
+/// Deep link that opens chat with the bot in `Telegram` app.
+/// Falls back to `bot_redirect_url` if bot's username is unknown.
+async fn bot_link(bot: &Bot, app_config: &AppConfig) -> String {
+    match bot.get_me().await.map(|me| me.user.username) {
+        Ok(Some(username)) => format!("tg://resolve?domain={username}"),
+        _ => app_config.bot_redirect_url.clone(),
+    }
+}
+
+/// Build request access token using `auth code` and `PKCE` code verifier.
+fn build_access_token_req<S: AsRef<str>>(
+    token_url: &str,
+    secret: &ApplicationSecret,
     auth_code: AuthCode<S>,
+    code_verifier: &str,
 ) -> eyre::Result<RequestBuilder> {
-    let secret_path = env!("PATH_TO_GOOGLE_OAUTH_SECRET");
-    let secret = read_application_secret(secret_path).await?;
     let params = [
         ("client_id", secret.client_id.as_str()),
         ("client_secret", secret.client_secret.as_str()),
         ("code", auth_code.0.as_ref()),
+        ("code_verifier", code_verifier),
         ("grant_type", "authorization_code"),
         ("redirect_uri", secret.redirect_uris[0].as_str()),
     ];
-    build_post_request(GET_ACCESS_TOKEN_URL, params)
+    build_post_request(token_url, params)
 }
 
 fn get_query(req: &Request<Body>) -> String {
@@ -106,28 +266,177 @@ fn get_query(req: &Request<Body>) -> String {
     decoded_query
 }
 
-fn redirect_user(redirect_to: &str) -> Res<axum::response::Response<BoxBody>> {
-    axum::response::Response::builder()
-        .header(axum::http::header::LOCATION, redirect_to)
-        .status(axum::http::status::StatusCode::PERMANENT_REDIRECT)
-        .body(BoxBody::default())
-        .map_err(<_>::into)
+/// Google sends back `state` and either `code` or `error` (e.g. when user denies access).
+fn get_params_from_query(decoded_query: &str) -> Res<(State<&str>, AuthCodeRes<'_>)> {
+    let state = find_by_key(decoded_query, PAIR_SEP, keys::STATE)?.map_type(State);
+    let auth_code = match find_by_key(decoded_query, PAIR_SEP, keys::ERROR) {
+        Ok(error) => Err(AuthError::Denied(error.to_owned())),
+        Err(_) => find_by_key(decoded_query, PAIR_SEP, keys::CODE)
+            .map(AuthCode)
+            .map_err(AuthError::Internal),
+    };
+
+    (state, auth_code).in_ok()
+}
+
+async fn get_youtube_token<S: AsRef<str>>(
+    auth_code: AuthCode<S>,
+    code_verifier: &str,
+    secret_path: &str,
+    token_url: &str,
+) -> Res<YouTubeAccessToken> {
+    let secret = read_application_secret(secret_path).await?;
+    let req_builder = build_access_token_req(token_url, &secret, auth_code, code_verifier)?;
+    let resp = req_builder.send().await?;
+    resp.json::<YouTubeAccessToken>().await?.in_ok()
 }
 
-fn get_params_from_query(decoded_query: &str) -> Res<(User<&str>, AuthCode<&str>)> {
-    let state = find_by_key(decoded_query, PAIR_SEP, keys::STATE)?;
-    let auth_code = find_by_key(decoded_query, PAIR_SEP, keys::CODE)?.map_type(AuthCode);
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::db::types::InMemTokenStore;
+    use crate::model::dialogue::funcs::default_auth_url;
+    use crate::model::dialogue::types::CommandConfig;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_AUTH_CODE};
+    use crate::model::net::types::GET_ACCESS_TOKEN_URL;
+    use teloxide::types::ChatId;
 
-    find_by_key(state, QUERY_SEPARATOR, keys::STATE_CODE)?
-        .err_if(|s| !s.contains(STATE_CODE), StateCodeError::Mismatch)?;
+    #[test]
+    fn access_token_req_build_test() {
+        let secret = ApplicationSecret {
+            client_id: "client_id_7654".to_owned(),
+            client_secret: "client_secret_8765".to_owned(),
+            redirect_uris: vec!["https://example.com/google_callback_auth_code".to_owned()],
+            ..Default::default()
+        };
 
-    let for_user = find_by_key(state, QUERY_SEPARATOR, keys::FOR_USER)?.map_type(User);
+        let (auth_code, code_verifier) = (AuthCode("auth_code_9876"), "code_verifier_5432");
+        let req = build_access_token_req(GET_ACCESS_TOKEN_URL, &secret, auth_code, code_verifier)
+            .unwrap()
+            .build()
+            .unwrap();
 
-    (for_user, auth_code).in_ok()
-}
+        assert_eq!(
+            req.headers()
+                .get(reqwest::header::HOST)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "oauth2.googleapis.com"
+        );
+        assert_eq!(req.url().as_str(), GET_ACCESS_TOKEN_URL);
 
-async fn get_youtube_token<S: AsRef<str>>(auth_code: AuthCode<S>) -> Res<YouTubeAccessToken> {
-    let req_builder = request_access_token(auth_code).await?;
-    let resp = req_builder.send().await?;
-    resp.json::<YouTubeAccessToken>().await?.in_ok()
+        let expected_body = "client_id=client_id_7654\
+            &client_secret=client_secret_8765\
+            &code=auth_code_9876\
+            &code_verifier=code_verifier_5432\
+            &grant_type=authorization_code\
+            &redirect_uri=https%3A%2F%2Fexample.com%2Fgoogle_callback_auth_code";
+
+        assert_eq!(
+            req.body().unwrap().as_bytes().unwrap(),
+            expected_body.as_bytes()
+        );
+    }
+
+    #[test]
+    fn get_params_from_query_test() {
+        let decoded_query = "state=nonce_123&code=auth_code_456&scope=some_scope&";
+        let (state, auth_code) = get_params_from_query(decoded_query).unwrap();
+
+        assert_eq!(state.0, "nonce_123");
+        assert_eq!(auth_code.unwrap().0, "auth_code_456");
+
+        let decoded_query = "error=access_denied&state=nonce_123&";
+        let (state, auth_code) = get_params_from_query(decoded_query).unwrap();
+
+        assert_eq!(state.0, "nonce_123");
+        assert!(matches!(auth_code, Err(AuthError::Denied(e)) if e == "access_denied"));
+
+        assert!(get_params_from_query("code=auth_code_456&").is_err());
+    }
+
+    #[tokio::test]
+    async fn login_resumes_in_original_chat_test() {
+        let mock_api = MockYouTubeApi::start();
+        let secret_path = std::env::temp_dir().join(format!(
+            "youtube_search_bot_oauth_secret_{}.json",
+            std::process::id()
+        ));
+        let secret = serde_json::json!({ "web": {
+            "client_id": "client_id_7654",
+            "client_secret": "client_secret_8765",
+            "auth_uri": "https://accounts.google.com/o/oauth2/auth",
+            "token_uri": GET_ACCESS_TOKEN_URL,
+            "redirect_uris": ["https://example.com/google_callback_auth_code"]
+        }});
+        std::fs::write(&secret_path, secret.to_string()).unwrap();
+        let app_config = AppConfig {
+            telegram_bot_token: "bot_token".to_owned(),
+            port: 8443,
+            host: "https://example.com".to_owned(),
+            redis_youtube_access_token_storage: None,
+            file_youtube_access_token_storage: None,
+            path_to_google_oauth_secret: secret_path.to_string_lossy().into_owned(),
+            bot_redirect_url: "https://t.me/some_bot".to_owned(),
+            playlist_search_concurrency: 2,
+            youtube_api_url: "http://127.0.0.1:1/youtube/v3".to_owned(),
+            google_token_url: mock_api.token_url.clone(),
+        };
+        let token_store = InMemTokenStore::default();
+        let (user_id, group_chat) = ("42", ChatId(-1001234567890));
+
+        let pending_command = Some(CommandConfig::PlaylistChanges);
+        let auth_url = default_auth_url(
+            user_id,
+            group_chat,
+            pending_command,
+            &app_config,
+            &token_store,
+        )
+        .await
+        .unwrap();
+        let (_, state) = auth_url.query_pairs().find(|(k, _)| k == "state").unwrap();
+        let req = Request::builder()
+            .uri(format!(
+                "/google_callback_auth_code?state={state}&code={MOCK_AUTH_CODE}"
+            ))
+            .body(Body::empty())
+            .unwrap();
+        let login_state = handle_auth_code_internal(req, &token_store, &app_config).await;
+        std::fs::remove_file(secret_path).unwrap();
+
+        // Command is resumed in the chat where it was started, not in private chat with user.
+        let login_state = login_state.unwrap();
+        assert_eq!(login_state.user_id, user_id);
+        assert_eq!(login_state.chat_id, group_chat);
+        assert!(matches!(
+            login_state.pending_command,
+            Some(CommandConfig::PlaylistChanges)
+        ));
+        assert!(token_store.get_token(user_id).await.is_ok());
+    }
+
+    #[test]
+    fn auth_error_page_test() {
+        let (status, page) = AuthError::InvalidState.page();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(page.contains("expired"));
+
+        let (status, page) = AuthError::Denied("access_denied".to_owned()).page();
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("denied"));
+
+        let (status, page) = AuthError::TokenExchange(eyre::eyre!("bad code")).page();
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert!(page.contains("Login failed"));
+    }
+
+    #[test]
+    fn success_page_test() {
+        let page = pages::success("tg://resolve?domain=some_bot");
+        assert!(page.contains("<a href=\"tg://resolve?domain=some_bot\">"));
+    }
 }
@@ -1,7 +1,7 @@
 use error_traits::{LogErr, MapErrBy};
 use maptypings::WrapInRes;
 
-use crate::model::db::{delete_access_token, get_access_token};
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::funcs::get_dialogue_data;
 use crate::model::dialogue::types::{
     ListCommandSettings, MessageTriplet, SearchCommandSettings,
@@ -25,11 +25,14 @@ fn build_log_out_req(token: YouTubeAccessToken) -> eyre::Result<reqwest::Request
 }
 
 /// Revoke `refresh token` and delete token from db.
-pub(crate) async fn log_out(user_id: &str, db_url: &str) -> eyre::Result<MessageTriplet> {
+pub(crate) async fn log_out(
+    user_id: &str,
+    token_store: &dyn TokenStore,
+) -> eyre::Result<MessageTriplet> {
     let log_prefix = "@[fn]:[model::commands::log_out] ";
     log::info!("{log_prefix}:: [Started]");
 
-    match get_access_token(user_id, db_url) {
+    match token_store.get_token(user_id).await {
         Ok(token) => {
             let resp = build_log_out_req(token)?.send().await?;
             let revoked_token_successfully = resp.status().is_success();
@@ -39,7 +42,7 @@ pub(crate) async fn log_out(user_id: &str, db_url: &str) -> eyre::Result<Message
             log::debug!("{log_prefix} ( resp is: '{:#?}' )", resp);
             log::debug!("{log_prefix} ( body is: '{:#?}' )", resp.text().await);
 
-            delete_access_token(user_id, db_url)?;
+            token_store.delete_token(user_id).await?;
 
             ("Logged out successfully ✅".to_owned(), None, None).in_ok()
         }
@@ -55,10 +58,11 @@ fn print_search_config(search_settings: &SearchCommandSettings) -> String {
     let SearchCommandSettings {
         result_limit,
         text_to_search,
+        match_mode,
         ..
     } = search_settings;
     let t = format!(
-        "{}{}{}{}",
+        "{}{}{}{}{}",
         maybe_print(format!("\n🎯 {}  =  ", "Target".to_bold()), target, ""),
         maybe_print(
             format!("\n💳 {}  =  ", "Search in".to_bold()),
@@ -74,6 +78,11 @@ fn print_search_config(search_settings: &SearchCommandSettings) -> String {
             format!("\n💬 {}  =  ", "Text to search".to_bold()),
             text_to_search,
             ""
+        ),
+        maybe_print(
+            format!("\n🔤 {}  =  ", "Matching".to_bold()),
+            match_mode,
+            ""
         )
     );
     if t.is_empty() {
@@ -115,9 +124,10 @@ fn print_search_videos_in_pls_config(
         search_in,
         text_to_search,
         result_limit,
+        match_mode,
     } = list_settings;
     let t = format!(
-        "{}{}{}",
+        "{}{}{}{}",
         maybe_print(
             format!("\n💳 {}  =  ", "Search in".to_bold()),
             search_in,
@@ -132,6 +142,11 @@ fn print_search_videos_in_pls_config(
             format!("\n💬 {}  =  ", "Text to search".to_bold()),
             text_to_search,
             ""
+        ),
+        maybe_print(
+            format!("\n🔤 {}  =  ", "Matching".to_bold()),
+            match_mode,
+            ""
         )
     );
     if t.is_empty() {
@@ -173,12 +188,15 @@ pub(crate) async fn info(dialogue: &TheDialogue) -> StdRes<MessageTriplet, Messa
 #[cfg(test)]
 mod tests {
     use crate::model::commands::funcs::{
-        build_log_out_req, print_list_config, print_search_config,
+        build_log_out_req, log_out, print_list_config, print_search_config,
     };
+    use crate::model::db::types::InMemTokenStore;
     use crate::model::dialogue::types::{ListCommandSettings, SearchCommandSettings};
     use crate::model::keyboards::types::Requestable;
     use crate::model::net::funcs::build_post_request;
-    use crate::model::net::types::{SubscriptionRequester, REVOKE_ACCESS_TOKEN_URL};
+    use crate::model::net::types::{
+        SubscriptionRequester, UploadsRequester, REVOKE_ACCESS_TOKEN_URL,
+    };
     use crate::model::youtube::types::YouTubeAccessToken;
     use google_youtube3::hyper;
     use std::str::{from_utf8, FromStr};
@@ -199,6 +217,15 @@ mod tests {
             print_list_config(&c),
             "Your list parameters are\n🎯 <b>Target</b>  =  Subscription"
         );
+
+        let c = SearchCommandSettings {
+            target: Requestable::Uploads(UploadsRequester).into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            print_search_config(&c),
+            "Your search parameters are\n🎯 <b>Target</b>  =  My upload"
+        );
     }
 
     #[test]
@@ -246,6 +273,12 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn log_out_without_token_test() {
+        let token_store = InMemTokenStore::default();
+        assert!(log_out("user_id", &token_store).await.is_err());
+    }
+
     trait ShortUnwrap<T> {
         fn unwr(self) -> T;
     }
@@ -19,4 +19,18 @@ pub enum Command {
     LogOut,
     #[command(description = "Search Videos In My Playlists")]
     SearchVideosInMyPlaylists,
+    #[command(description = "Export Subscriptions Or Playlists")]
+    Export,
+    #[command(description = "Save Current State Of My Playlists")]
+    SnapshotPlaylists,
+    #[command(description = "Show Videos That Disappeared From My Playlists")]
+    PlaylistChanges,
+    #[command(description = "Show Runtime And Other Stats Of My Playlists")]
+    PlaylistStats,
+    #[command(description = "Find Duplicate Videos, Optionally Only In Playlist With Given Title")]
+    FindDuplicates(String),
+    #[command(description = "Find Subscribed Channels That Stopped Uploading")]
+    InactiveSubscriptions,
+    #[command(description = "Show Stats Of My Subscriptions")]
+    Stats,
 }
@@ -0,0 +1,215 @@
+use std::path::Path;
+
+use maptypings::WrapInRes;
+
+use crate::model::config::types::{keys, AppConfig, DEFAULT_PLAYLIST_SEARCH_CONCURRENCY};
+use crate::model::errors::{ConfigError, InvalidConfigValueError, MissingEnvVarError};
+use crate::model::net::types::{GET_ACCESS_TOKEN_URL, YOUTUBE_API_URL};
+use crate::StdRes;
+
+/// Reads `toml` config file.
+pub(crate) fn read_config_file(path: impl AsRef<Path>) -> eyre::Result<toml::Table> {
+    std::fs::read_to_string(path)?
+        .parse::<toml::Table>()?
+        .in_ok()
+}
+
+fn toml_value_to_string(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Builds config, taking values from `env` first and from `file` second.
+/// Empty values count as missing.
+pub(crate) fn build_config(
+    env: impl Fn(&str) -> Option<String>,
+    file: Option<&toml::Table>,
+) -> StdRes<AppConfig, ConfigError> {
+    let lookup = |key: &'static str| {
+        env(key)
+            .or_else(|| file?.get(&key.to_lowercase()).map(toml_value_to_string))
+            .filter(|v| !v.trim().is_empty())
+    };
+
+    let mut missing = vec![];
+    let mut required = |var: &'static str| {
+        lookup(var).unwrap_or_else(|| {
+            missing.push(MissingEnvVarError { var });
+            String::new()
+        })
+    };
+    let telegram_bot_token = required(keys::TELEGRAM_BOT_TOKEN);
+    let port = required(keys::PORT);
+    let host = required(keys::HOST);
+    let path_to_google_oauth_secret = required(keys::PATH_TO_GOOGLE_OAUTH_SECRET);
+    let bot_redirect_url = required(keys::BOT_REDIRECT_URL);
+
+    let mut invalid = vec![];
+    let port = match port.parse::<u16>() {
+        Ok(port) => port,
+        Err(e) => {
+            if !port.is_empty() {
+                let (key, reason) = (keys::PORT, e.to_string());
+                invalid.push(InvalidConfigValueError { key, reason });
+            }
+            0
+        }
+    };
+    let playlist_search_concurrency = match lookup(keys::PLAYLIST_SEARCH_CONCURRENCY) {
+        None => DEFAULT_PLAYLIST_SEARCH_CONCURRENCY,
+        Some(value) => match value.trim().parse::<usize>() {
+            Ok(n) if n >= 1 => n,
+            _ => {
+                let key = keys::PLAYLIST_SEARCH_CONCURRENCY;
+                let reason = "must be a number greater than 0".to_owned();
+                invalid.push(InvalidConfigValueError { key, reason });
+                DEFAULT_PLAYLIST_SEARCH_CONCURRENCY
+            }
+        },
+    };
+    let youtube_api_url = lookup(keys::YOUTUBE_API_URL).unwrap_or(YOUTUBE_API_URL.to_owned());
+    let google_token_url =
+        lookup(keys::GOOGLE_TOKEN_URL).unwrap_or(GET_ACCESS_TOKEN_URL.to_owned());
+    for (key, url) in [
+        (keys::HOST, &host),
+        (keys::BOT_REDIRECT_URL, &bot_redirect_url),
+        (keys::YOUTUBE_API_URL, &youtube_api_url),
+        (keys::GOOGLE_TOKEN_URL, &google_token_url),
+    ] {
+        if let (false, Err(e)) = (url.is_empty(), url::Url::parse(url)) {
+            let reason = e.to_string();
+            invalid.push(InvalidConfigValueError { key, reason });
+        }
+    }
+
+    if !missing.is_empty() || !invalid.is_empty() {
+        return Err(ConfigError { missing, invalid });
+    }
+
+    AppConfig {
+        telegram_bot_token,
+        port,
+        host,
+        redis_youtube_access_token_storage: lookup(keys::REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE),
+        file_youtube_access_token_storage: lookup(keys::FILE_YOUTUBE_ACCESS_TOKEN_STORAGE),
+        path_to_google_oauth_secret,
+        bot_redirect_url,
+        playlist_search_concurrency,
+        youtube_api_url,
+        google_token_url,
+    }
+    .in_ok()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn env_from<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        let vars = vars.iter().copied().collect::<HashMap<_, _>>();
+        move |key| vars.get(key).map(|v| (*v).to_owned())
+    }
+
+    const FULL_ENV: [(&str, &str); 5] = [
+        ("TELEGRAM_BOT_TOKEN", "bot_token"),
+        ("PORT", "8080"),
+        ("HOST", "https://example.com"),
+        ("PATH_TO_GOOGLE_OAUTH_SECRET", "secret.json"),
+        ("BOT_REDIRECT_URL", "https://t.me/some_bot"),
+    ];
+
+    #[test]
+    fn build_config_from_env_test() {
+        let config = build_config(env_from(&FULL_ENV), None).unwrap();
+
+        assert_eq!(config.telegram_bot_token, "bot_token");
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.host, "https://example.com");
+        assert_eq!(config.redis_youtube_access_token_storage, None);
+        assert_eq!(config.file_youtube_access_token_storage, None);
+        assert_eq!(
+            config.playlist_search_concurrency,
+            DEFAULT_PLAYLIST_SEARCH_CONCURRENCY
+        );
+        assert_eq!(config.youtube_api_url, YOUTUBE_API_URL);
+        assert_eq!(config.google_token_url, GET_ACCESS_TOKEN_URL);
+    }
+
+    #[test]
+    fn all_missing_keys_reported_test() {
+        let env = [("PORT", "8080"), ("BOT_REDIRECT_URL", "  ")];
+        let err = build_config(env_from(&env), None).unwrap_err();
+        let missing = err.missing.iter().map(|e| e.var).collect::<Vec<_>>();
+
+        assert_eq!(
+            missing,
+            vec![
+                "TELEGRAM_BOT_TOKEN",
+                "HOST",
+                "PATH_TO_GOOGLE_OAUTH_SECRET",
+                "BOT_REDIRECT_URL"
+            ]
+        );
+        assert!(err.invalid.is_empty());
+        assert!(err.to_string().contains("TELEGRAM_BOT_TOKEN, HOST"));
+    }
+
+    #[test]
+    fn invalid_values_reported_test() {
+        let mut env = FULL_ENV.to_vec();
+        env.retain(|(k, _)| *k != "PORT" && *k != "HOST" && *k != "TELEGRAM_BOT_TOKEN");
+        env.extend([
+            ("PORT", "80_000"),
+            ("HOST", "not a url"),
+            ("PLAYLIST_SEARCH_CONCURRENCY", "0"),
+            ("YOUTUBE_API_URL", "localhost"),
+        ]);
+        let err = build_config(env_from(&env), None).unwrap_err();
+        let invalid = err.invalid.iter().map(|e| e.key).collect::<Vec<_>>();
+
+        assert_eq!(err.missing.len(), 1);
+        assert_eq!(
+            invalid,
+            vec![
+                "PORT",
+                "PLAYLIST_SEARCH_CONCURRENCY",
+                "HOST",
+                "YOUTUBE_API_URL"
+            ]
+        );
+    }
+
+    #[test]
+    fn env_overrides_file_test() {
+        let file = r#"
+            telegram_bot_token = "file_bot_token"
+            port = 443
+            host = "https://example.com"
+            path_to_google_oauth_secret = "secret.json"
+            bot_redirect_url = "https://t.me/some_bot"
+            file_youtube_access_token_storage = "tokens.json"
+            playlist_search_concurrency = 8
+            youtube_api_url = "http://127.0.0.1:8081/youtube/v3"
+        "#
+        .parse::<toml::Table>()
+        .unwrap();
+        let env = [("HOST", "https://staging.example.com")];
+        let config = build_config(env_from(&env), Some(&file)).unwrap();
+
+        assert_eq!(config.telegram_bot_token, "file_bot_token");
+        assert_eq!(config.port, 443);
+        assert_eq!(config.host, "https://staging.example.com");
+        assert_eq!(
+            config.file_youtube_access_token_storage.as_deref(),
+            Some("tokens.json")
+        );
+        assert_eq!(config.playlist_search_concurrency, 8);
+        assert_eq!(config.youtube_api_url, "http://127.0.0.1:8081/youtube/v3");
+    }
+}
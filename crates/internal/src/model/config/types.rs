@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use maptypings::WrapInRes;
+
+use crate::model::config::funcs::{build_config, read_config_file};
+
+/// Env var pointing at an optional `toml` config file.
+pub const CONFIG_PATH_VAR: &str = "APP_CONFIG_PATH";
+
+/// Names of config keys.
+/// Environment variables use them as is, `toml` file uses lowercase versions.
+pub(crate) mod keys {
+    pub(crate) const TELEGRAM_BOT_TOKEN: &str = "TELEGRAM_BOT_TOKEN";
+    pub(crate) const PORT: &str = "PORT";
+    pub(crate) const HOST: &str = "HOST";
+    pub(crate) const REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE: &str =
+        "REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE";
+    pub(crate) const FILE_YOUTUBE_ACCESS_TOKEN_STORAGE: &str = "FILE_YOUTUBE_ACCESS_TOKEN_STORAGE";
+    pub(crate) const PATH_TO_GOOGLE_OAUTH_SECRET: &str = "PATH_TO_GOOGLE_OAUTH_SECRET";
+    pub(crate) const BOT_REDIRECT_URL: &str = "BOT_REDIRECT_URL";
+    pub(crate) const PLAYLIST_SEARCH_CONCURRENCY: &str = "PLAYLIST_SEARCH_CONCURRENCY";
+    pub(crate) const YOUTUBE_API_URL: &str = "YOUTUBE_API_URL";
+    pub(crate) const GOOGLE_TOKEN_URL: &str = "GOOGLE_TOKEN_URL";
+}
+
+/// How many playlists are scanned at once, if `PLAYLIST_SEARCH_CONCURRENCY` isn't set.
+pub(crate) const DEFAULT_PLAYLIST_SEARCH_CONCURRENCY: usize = 4;
+
+/// Shared as a `dptree` dependency and as an `axum` extension.
+pub type WrappedConfig = Arc<AppConfig>;
+
+/// Runtime configuration, loaded once at startup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppConfig {
+    pub telegram_bot_token: String,
+    pub port: u16,
+    /// Public url of the webhook server.
+    pub host: String,
+    /// If set, access tokens and playlist snapshots are stored in `Redis`.
+    pub redis_youtube_access_token_storage: Option<String>,
+    /// If set (and `Redis` isn't), access tokens are stored in this file,
+    /// and playlist snapshots in `<file stem>.snapshots.json` next to it.
+    pub file_youtube_access_token_storage: Option<String>,
+    pub path_to_google_oauth_secret: String,
+    /// Where user is sent after successful authorization.
+    pub bot_redirect_url: String,
+    /// How many playlists `search_videos_in_my_playlists` scans at once.
+    pub playlist_search_concurrency: usize,
+    /// Base url of `YouTube Data API`, can be pointed at a mock server.
+    pub youtube_api_url: String,
+    /// Url for acquiring and refreshing `access token`.
+    pub google_token_url: String,
+}
+
+impl AppConfig {
+    /// Loads config from environment variables and an optional `toml` file,
+    /// which path is taken from `APP_CONFIG_PATH`.
+    /// Environment variables take precedence over the file.
+    pub fn load() -> eyre::Result<Self> {
+        let file = std::env::var(CONFIG_PATH_VAR)
+            .ok()
+            .map(read_config_file)
+            .transpose()?;
+        build_config(|key| std::env::var(key).ok(), file.as_ref())?.in_ok()
+    }
+}
@@ -0,0 +1,168 @@
+use crate::model::db::traits::TokenStore;
+use crate::model::net::funcs::build_post_request;
+use crate::model::utils::log;
+use google_youtube3::oauth2::ApplicationSecret;
+use log::Level;
+use maptypings::WrapInRes;
+
+use crate::model::youtube::types::YouTubeAccessToken;
+
+/// Because `refresh token` is received only once, it needs to be moved from old token to a new one.
+pub(crate) async fn combine_old_new_tokens(
+    user_id: &str,
+    new_token: YouTubeAccessToken,
+    token_store: &dyn TokenStore,
+) -> YouTubeAccessToken {
+    match token_store.get_token(user_id).await {
+        Ok(YouTubeAccessToken {
+            refresh_token: Some(ref_token),
+            ..
+        }) => YouTubeAccessToken {
+            refresh_token: Some(ref_token),
+            ..new_token
+        },
+        _ => new_token,
+    }
+}
+
+/// Constructs request for acquiring new `access token`.
+pub(crate) fn build_refresh_access_token_req(
+    token_url: &str,
+    oauth2_secret: ApplicationSecret,
+    token: &YouTubeAccessToken,
+) -> eyre::Result<reqwest::RequestBuilder> {
+    let params = [
+        ("client_id", oauth2_secret.client_id.as_str()),
+        ("client_secret", oauth2_secret.client_secret.as_str()),
+        (
+            "refresh_token",
+            token
+                .refresh_token
+                .as_ref()
+                .ok_or(eyre::eyre!("No refresh token"))?,
+        ),
+        ("grant_type", "refresh_token"),
+    ];
+    build_post_request(token_url, params)
+}
+
+/// Makes request for new `access token` if needed, then saves and returns it.
+pub(crate) async fn refresh_access_token(
+    user_id: &str,
+    token: YouTubeAccessToken,
+    token_store: &dyn TokenStore,
+    refresh_access_token_req: reqwest::RequestBuilder,
+) -> eyre::Result<YouTubeAccessToken> {
+    let op = "crates/internal/src/model/db/funcs.rs:refresh_access_token";
+
+    let time_remains = token.expires_in - time::OffsetDateTime::now_utc();
+    let token_expires_after = time_remains.whole_minutes();
+
+    log(Level::Info, op, "token is valid for", time_remains);
+
+    if token_expires_after > 10 {
+        return token.in_ok();
+    }
+
+    let resp = refresh_access_token_req.send().await?;
+
+    log(Level::Info, op, "resp status is", resp.status());
+
+    let new_token = resp.json::<YouTubeAccessToken>().await?;
+    let combined_token = YouTubeAccessToken {
+        refresh_token: token.refresh_token,
+        ..new_token
+    };
+    token_store.set_token(user_id, &combined_token).await?;
+    combined_token.in_ok()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::db::types::InMemTokenStore;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_REFRESH_TOKEN};
+    use crate::model::youtube::types::YouTubeAccessToken;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn refresh_access_token_test() {
+        let mock_api = MockYouTubeApi::start();
+        let secret = ApplicationSecret {
+            client_id: "client_id_7654".to_owned(),
+            client_secret: "client_secret_8765".to_owned(),
+            ..Default::default()
+        };
+        let user_id = "Александр Иванов";
+        let token = |expires_in, refresh_token: &str| YouTubeAccessToken {
+            access_token: "old_access_token".to_owned(),
+            expires_in,
+            refresh_token: Some(refresh_token.to_owned()),
+            scope: vec!["first".to_owned(), "second".to_owned()],
+            token_type: "Bearer".to_owned(),
+        };
+        let token_req = |token: &YouTubeAccessToken| {
+            build_refresh_access_token_req(&mock_api.token_url, secret.clone(), token).unwrap()
+        };
+        let token_store = InMemTokenStore::default();
+
+        // Token that is about to expire is refreshed, `refresh token` stays the same.
+        let expiring = token(time::OffsetDateTime::now_utc(), MOCK_REFRESH_TOKEN);
+        let refreshed = refresh_access_token(
+            user_id,
+            expiring.clone(),
+            &token_store,
+            token_req(&expiring),
+        )
+        .await
+        .unwrap();
+        assert_ne!(refreshed.access_token, expiring.access_token);
+        assert_eq!(refreshed.refresh_token, expiring.refresh_token);
+        assert!(
+            refreshed.expires_in > time::OffsetDateTime::now_utc() + time::Duration::minutes(50)
+        );
+        assert_eq!(token_store.get_token(user_id).await.unwrap(), refreshed);
+
+        // Token that is still valid is returned as is.
+        let valid = token(
+            time::OffsetDateTime::now_utc() + time::Duration::hours(1),
+            "revoked",
+        );
+        let same = refresh_access_token(user_id, valid.clone(), &token_store, token_req(&valid))
+            .await
+            .unwrap();
+        assert_eq!(same, valid);
+
+        // Revoked `refresh token` is rejected.
+        let revoked = token(time::OffsetDateTime::now_utc(), "revoked");
+        let res =
+            refresh_access_token(user_id, revoked.clone(), &token_store, token_req(&revoked)).await;
+        assert!(res.is_err());
+        assert_eq!(token_store.get_token(user_id).await.unwrap(), refreshed);
+    }
+
+    #[tokio::test]
+    async fn combine_old_new_tokens_test() {
+        let user_id = "Александр Иванов";
+        let token = |access_token: &str, refresh_token: Option<&str>| YouTubeAccessToken {
+            access_token: access_token.to_owned(),
+            expires_in: time::OffsetDateTime::now_utc(),
+            refresh_token: refresh_token.map(<_>::to_owned),
+            scope: vec![],
+            token_type: "Bearer".to_owned(),
+        };
+        let token_store = InMemTokenStore::default();
+
+        let combined = combine_old_new_tokens(user_id, token("new", None), &token_store).await;
+        assert_eq!(combined.refresh_token, None);
+
+        let old_token = token("old", Some("refresh"));
+        token_store.set_token(user_id, &old_token).await.unwrap();
+
+        let combined = combine_old_new_tokens(user_id, token("new", None), &token_store).await;
+        assert_eq!(combined.access_token, "new");
+        assert_eq!(combined.refresh_token.as_deref(), Some("refresh"));
+    }
+}
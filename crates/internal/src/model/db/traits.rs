@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt};
+use maptypings::WrapInRes;
+use redis::AsyncCommands;
+
+use crate::model::db::types::{
+    FileTokenStore, InMemTokenStore, LoginState, RedisTokenStore, LOGIN_STATE_PREFIX,
+    SNAPSHOT_PREFIX, TOKEN_PREFIX,
+};
+use crate::model::errors::NotFound;
+use crate::model::snapshot::types::PlaylistSnapshots;
+use crate::model::youtube::types::YouTubeAccessToken;
+
+/// Storage for users' `YouTube` access tokens and pending logins.
+///
+/// Implementors are shared between handlers as `WrappedTokenStore`.
+pub trait TokenStore: Send + Sync {
+    /// Returns saved token, or an error if there is none.
+    fn get_token<'a>(&'a self, user_id: &'a str)
+        -> BoxFuture<'a, eyre::Result<YouTubeAccessToken>>;
+
+    /// Saves token, replacing the old one.
+    fn set_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        token: &'a YouTubeAccessToken,
+    ) -> BoxFuture<'a, eyre::Result<()>>;
+
+    fn delete_token<'a>(&'a self, user_id: &'a str) -> BoxFuture<'a, eyre::Result<()>>;
+
+    /// Ids of all users that have a token.
+    fn list_users(&self) -> BoxFuture<'_, eyre::Result<Vec<String>>>;
+
+    /// Saves login state under a `nonce`, it's forgotten after `ttl`.
+    fn save_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+        login_state: &'a LoginState,
+        ttl: Duration,
+    ) -> BoxFuture<'a, eyre::Result<()>>;
+
+    /// Atomically removes and returns login state.
+    /// Returns `None` if `nonce` is unknown, expired or was already used.
+    fn take_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<Option<LoginState>>>;
+}
+
+/// Storage for snapshots of users' playlists.
+///
+/// Implementors are shared between handlers as `WrappedSnapshotStore`.
+pub trait SnapshotStore: Send + Sync {
+    /// Returns last saved snapshots of user's playlists (empty if there are none).
+    fn get_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<PlaylistSnapshots>>;
+
+    /// Saves snapshots of user's playlists, replacing the old ones.
+    fn set_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+        snapshots: &'a PlaylistSnapshots,
+    ) -> BoxFuture<'a, eyre::Result<()>>;
+}
+
+impl TokenStore for RedisTokenStore {
+    fn get_token<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<YouTubeAccessToken>> {
+        async move {
+            log::info!("getting access_token from a database | (silent on failure)");
+
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            let serialized_token = con
+                .get::<_, Option<String>>(format!("{TOKEN_PREFIX}{user_id}"))
+                .await?
+                .ok_or_else(|| NotFound::new("access token"))?;
+            let token = serde_json::from_str::<YouTubeAccessToken>(&serialized_token)?;
+
+            log::info!("access_token acquired!");
+
+            token.in_ok()
+        }
+        .boxed()
+    }
+
+    fn set_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        token: &'a YouTubeAccessToken,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            log::info!("saving access_token to a database | (silent on failure)");
+
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            let serialized_token = serde_json::to_string(token)?;
+            con.set::<_, _, ()>(format!("{TOKEN_PREFIX}{user_id}"), serialized_token)
+                .await?;
+
+            log::info!("access_token saved!");
+
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn delete_token<'a>(&'a self, user_id: &'a str) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let op = "crates/internal/src/model/db/traits.rs:delete_token";
+
+            log::info!("[LOG]  loc: '{op}'  ( deleting access token... | silent on failure )");
+
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            con.del::<_, ()>(format!("{TOKEN_PREFIX}{user_id}")).await?;
+
+            log::info!("[LOG]  loc: '{op}'  ( access token deleted! )");
+
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn list_users(&self) -> BoxFuture<'_, eyre::Result<Vec<String>>> {
+        async move {
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            let keys: Vec<String> = con
+                .scan_match::<_, String>(format!("{TOKEN_PREFIX}*"))
+                .await?
+                .collect()
+                .await;
+            keys.iter()
+                .filter_map(|k| k.strip_prefix(TOKEN_PREFIX))
+                .map(<_>::to_owned)
+                .collect::<Vec<_>>()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn save_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+        login_state: &'a LoginState,
+        ttl: Duration,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            let serialized_state = serde_json::to_string(login_state)?;
+            con.set_ex::<_, _, ()>(
+                format!("{LOGIN_STATE_PREFIX}{nonce}"),
+                serialized_state,
+                ttl.as_secs(),
+            )
+            .await?;
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn take_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<Option<LoginState>>> {
+        async move {
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            // `GETDEL` makes sure that the same state can't be used twice.
+            con.get_del::<_, Option<String>>(format!("{LOGIN_STATE_PREFIX}{nonce}"))
+                .await?
+                .map(|s| serde_json::from_str::<LoginState>(&s))
+                .transpose()?
+                .in_ok()
+        }
+        .boxed()
+    }
+}
+
+impl SnapshotStore for RedisTokenStore {
+    fn get_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<PlaylistSnapshots>> {
+        async move {
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            con.get::<_, Option<String>>(format!("{SNAPSHOT_PREFIX}{user_id}"))
+                .await?
+                .map(|s| serde_json::from_str::<PlaylistSnapshots>(&s))
+                .transpose()?
+                .unwrap_or_default()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn set_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+        snapshots: &'a PlaylistSnapshots,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let mut con = self.client.get_multiplexed_async_connection().await?;
+            let serialized_snapshots = serde_json::to_string(snapshots)?;
+            con.set::<_, _, ()>(format!("{SNAPSHOT_PREFIX}{user_id}"), serialized_snapshots)
+                .await?;
+            ().in_ok()
+        }
+        .boxed()
+    }
+}
+
+impl TokenStore for InMemTokenStore {
+    fn get_token<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<YouTubeAccessToken>> {
+        async move {
+            self.tokens
+                .lock()
+                .await
+                .get(user_id)
+                .cloned()
+                .ok_or_else(|| NotFound::new("access token").into())
+        }
+        .boxed()
+    }
+
+    fn set_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        token: &'a YouTubeAccessToken,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            self.tokens
+                .lock()
+                .await
+                .insert(user_id.to_owned(), token.clone());
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn delete_token<'a>(&'a self, user_id: &'a str) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            self.tokens.lock().await.remove(user_id);
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn list_users(&self) -> BoxFuture<'_, eyre::Result<Vec<String>>> {
+        async move {
+            self.tokens
+                .lock()
+                .await
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn save_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+        login_state: &'a LoginState,
+        ttl: Duration,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            self.login_states.save(nonce, login_state, ttl).await;
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn take_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<Option<LoginState>>> {
+        async move { self.login_states.take(nonce).await.in_ok() }.boxed()
+    }
+}
+
+impl SnapshotStore for InMemTokenStore {
+    fn get_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<PlaylistSnapshots>> {
+        async move {
+            self.snapshots
+                .lock()
+                .await
+                .get(user_id)
+                .cloned()
+                .unwrap_or_default()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn set_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+        snapshots: &'a PlaylistSnapshots,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            self.snapshots
+                .lock()
+                .await
+                .insert(user_id.to_owned(), snapshots.clone());
+            ().in_ok()
+        }
+        .boxed()
+    }
+}
+
+impl FileTokenStore {
+    /// Reads all tokens. Missing file means there are no tokens yet.
+    async fn read_all(&self) -> eyre::Result<HashMap<String, YouTubeAccessToken>> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new().in_ok(),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str::<HashMap<_, _>>(&contents)?.in_ok()
+    }
+
+    async fn write_all(&self, tokens: &HashMap<String, YouTubeAccessToken>) -> eyre::Result<()> {
+        tokio::fs::write(&self.path, serde_json::to_string(tokens)?).await?;
+        ().in_ok()
+    }
+
+    /// Reads snapshots of all users. Missing file means there are no snapshots yet.
+    async fn read_all_snapshots(&self) -> eyre::Result<HashMap<String, PlaylistSnapshots>> {
+        let contents = match tokio::fs::read_to_string(&self.snapshots_path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return HashMap::new().in_ok(),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str::<HashMap<_, _>>(&contents)?.in_ok()
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn get_token<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<YouTubeAccessToken>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            self.read_all()
+                .await?
+                .remove(user_id)
+                .ok_or_else(|| NotFound::new("access token").into())
+        }
+        .boxed()
+    }
+
+    fn set_token<'a>(
+        &'a self,
+        user_id: &'a str,
+        token: &'a YouTubeAccessToken,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            let mut tokens = self.read_all().await?;
+            tokens.insert(user_id.to_owned(), token.clone());
+            self.write_all(&tokens).await
+        }
+        .boxed()
+    }
+
+    fn delete_token<'a>(&'a self, user_id: &'a str) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            let mut tokens = self.read_all().await?;
+            if tokens.remove(user_id).is_some() {
+                self.write_all(&tokens).await?;
+            }
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn list_users(&self) -> BoxFuture<'_, eyre::Result<Vec<String>>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            self.read_all()
+                .await?
+                .into_keys()
+                .collect::<Vec<_>>()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn save_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+        login_state: &'a LoginState,
+        ttl: Duration,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            self.login_states.save(nonce, login_state, ttl).await;
+            ().in_ok()
+        }
+        .boxed()
+    }
+
+    fn take_login_state<'a>(
+        &'a self,
+        nonce: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<Option<LoginState>>> {
+        async move { self.login_states.take(nonce).await.in_ok() }.boxed()
+    }
+}
+
+impl SnapshotStore for FileTokenStore {
+    fn get_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+    ) -> BoxFuture<'a, eyre::Result<PlaylistSnapshots>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            self.read_all_snapshots()
+                .await?
+                .remove(user_id)
+                .unwrap_or_default()
+                .in_ok()
+        }
+        .boxed()
+    }
+
+    fn set_playlist_snapshots<'a>(
+        &'a self,
+        user_id: &'a str,
+        snapshots: &'a PlaylistSnapshots,
+    ) -> BoxFuture<'a, eyre::Result<()>> {
+        async move {
+            let _guard = self.lock.lock().await;
+            let mut all_snapshots = self.read_all_snapshots().await?;
+            all_snapshots.insert(user_id.to_owned(), snapshots.clone());
+            let serialized_snapshots = serde_json::to_string(&all_snapshots)?;
+            tokio::fs::write(&self.snapshots_path, serialized_snapshots).await?;
+            ().in_ok()
+        }
+        .boxed()
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use teloxide::types::ChatId;
+
+    use crate::model::snapshot::types::{PlaylistSnapshot, SnapshotItem};
+
+    use super::*;
+
+    fn create_token(access_token: &str) -> YouTubeAccessToken {
+        YouTubeAccessToken {
+            access_token: access_token.to_owned(),
+            expires_in: time::OffsetDateTime::now_utc(),
+            refresh_token: "refresh_token".to_owned().into(),
+            scope: vec!["first".to_owned(), "second".to_owned()],
+            token_type: "Bearer".to_owned(),
+        }
+    }
+
+    async fn get_set_delete(store: &dyn TokenStore) {
+        let user_id = "Александр Иванов";
+        let token = create_token("access_token");
+
+        assert!(store.get_token(user_id).await.is_err());
+
+        store.set_token(user_id, &token).await.unwrap();
+        assert_eq!(store.get_token(user_id).await.unwrap(), token);
+        assert_eq!(store.list_users().await.unwrap(), vec![user_id.to_owned()]);
+
+        let new_token = create_token("new_access_token");
+        store.set_token(user_id, &new_token).await.unwrap();
+        assert_eq!(store.get_token(user_id).await.unwrap(), new_token);
+
+        store.delete_token(user_id).await.unwrap();
+        assert!(store.get_token(user_id).await.is_err());
+        assert!(store.list_users().await.unwrap().is_empty());
+    }
+
+    async fn save_take_login_state(store: &dyn TokenStore) {
+        let login_state = LoginState {
+            user_id: "Александр Иванов".to_owned(),
+            chat_id: ChatId(-1001234567890),
+            code_verifier: "code_verifier".to_owned(),
+            pending_command: None,
+        };
+        let ttl = Duration::from_secs(60);
+
+        store
+            .save_login_state("nonce", &login_state, ttl)
+            .await
+            .unwrap();
+        assert!(store
+            .take_login_state("other_nonce")
+            .await
+            .unwrap()
+            .is_none());
+        let taken = store.take_login_state("nonce").await.unwrap().unwrap();
+        assert_eq!(taken.user_id, login_state.user_id);
+        assert_eq!(taken.chat_id, login_state.chat_id);
+        assert_eq!(taken.code_verifier, login_state.code_verifier);
+        // Replayed state.
+        assert!(store.take_login_state("nonce").await.unwrap().is_none());
+
+        // Expired state.
+        store
+            .save_login_state("nonce", &login_state, Duration::ZERO)
+            .await
+            .unwrap();
+        assert!(store.take_login_state("nonce").await.unwrap().is_none());
+    }
+
+    async fn get_set_playlist_snapshots(store: &dyn SnapshotStore) {
+        let user_id = "Александр Иванов";
+        let snapshot = PlaylistSnapshot {
+            playlist_id: "PL1".to_owned(),
+            title: "Music".to_owned(),
+            taken_at: time::OffsetDateTime::UNIX_EPOCH,
+            items: vec![SnapshotItem {
+                video_id: "v1".to_owned(),
+                title: "Song".to_owned(),
+                channel_title: None,
+                position: Some(0),
+            }],
+        };
+        let snapshots = PlaylistSnapshots::from([(snapshot.playlist_id.clone(), snapshot)]);
+
+        assert!(store
+            .get_playlist_snapshots(user_id)
+            .await
+            .unwrap()
+            .is_empty());
+        store
+            .set_playlist_snapshots(user_id, &snapshots)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_playlist_snapshots(user_id).await.unwrap(),
+            snapshots
+        );
+        assert!(store
+            .get_playlist_snapshots("other_user")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn in_mem_token_store_test() {
+        get_set_delete(&InMemTokenStore::default()).await;
+        save_take_login_state(&InMemTokenStore::default()).await;
+        get_set_playlist_snapshots(&InMemTokenStore::default()).await;
+    }
+
+    #[tokio::test]
+    async fn file_token_store_test() {
+        let path = std::env::temp_dir().join(format!(
+            "youtube_search_bot_tokens_{}.json",
+            std::process::id()
+        ));
+        get_set_delete(&FileTokenStore::new(&path)).await;
+        save_take_login_state(&FileTokenStore::new(&path)).await;
+        get_set_playlist_snapshots(&FileTokenStore::new(&path)).await;
+        std::fs::remove_file(FileTokenStore::new(&path).snapshots_path).unwrap();
+
+        // Tokens must survive re-opening the file.
+        let token = create_token("access_token");
+        FileTokenStore::new(&path)
+            .set_token("user", &token)
+            .await
+            .unwrap();
+        let saved_token = FileTokenStore::new(&path).get_token("user").await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(saved_token, token);
+    }
+
+    // Requires a running `redis` at `REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE` url.
+    #[tokio::test]
+    #[ignore]
+    async fn redis_token_store_test() {
+        let url = std::env::var("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE").unwrap();
+        get_set_delete(&RedisTokenStore::open(&url).unwrap()).await;
+    }
+}
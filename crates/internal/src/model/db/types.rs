@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use maptypings::WrapInRes;
+use serde::{Deserialize, Serialize};
+use teloxide::types::ChatId;
+use tokio::sync::Mutex;
+
+use crate::model::db::traits::{SnapshotStore, TokenStore};
+use crate::model::dialogue::types::CommandConfig;
+use crate::model::snapshot::types::PlaylistSnapshots;
+use crate::model::youtube::types::YouTubeAccessToken;
+
+/// Required to avoid key collisions.
+pub(crate) const TOKEN_PREFIX: &str = "youtube_access_token_rand_fuy6776d75ygku8i7_user_id_";
+
+/// Required to avoid key collisions.
+pub(crate) const LOGIN_STATE_PREFIX: &str = "youtube_login_state_rand_h7gd53kw0q_nonce_";
+
+/// Required to avoid key collisions.
+pub(crate) const SNAPSHOT_PREFIX: &str = "youtube_playlist_snapshots_rand_p3x8ka02nd_user_id_";
+
+/// How long a login link stays valid.
+pub(crate) const LOGIN_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Length of a random nonce, used as `state` in authorization url.
+pub(crate) const LOGIN_STATE_NONCE_LEN: usize = 43;
+
+/// Pending login attempt.
+/// Saved under a random nonce, which is sent to Google as `state` and comes back with `auth code`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LoginState {
+    pub(crate) user_id: String,
+    /// Chat where the interrupted command was started, it's resumed there.
+    pub(crate) chat_id: ChatId,
+    /// `PKCE` code verifier, sent with `auth code` in token exchange.
+    pub(crate) code_verifier: String,
+    /// Command that was interrupted by login, it's executed right after login.
+    pub(crate) pending_command: Option<CommandConfig>,
+}
+
+/// Type-erased `TokenStore`, shared between handlers as a `dptree` dependency.
+pub type WrappedTokenStore = Arc<dyn TokenStore>;
+
+/// Type-erased `SnapshotStore`, shared between handlers as a `dptree` dependency.
+pub type WrappedSnapshotStore = Arc<dyn SnapshotStore>;
+
+/// Stores access tokens in `Redis`, using `TOKEN_PREFIX` key scheme.
+#[derive(Clone, Debug)]
+pub struct RedisTokenStore {
+    pub(crate) client: redis::Client,
+}
+
+impl RedisTokenStore {
+    /// Creates store from `Redis` url (doesn't connect until first use).
+    pub fn open(db_url: &str) -> eyre::Result<Self> {
+        let client = redis::Client::open(db_url)?;
+        Self { client }.in_ok()
+    }
+}
+
+/// Keeps access tokens in memory. Everything is lost on restart.
+#[derive(Default, Debug)]
+pub struct InMemTokenStore {
+    pub(crate) tokens: Mutex<HashMap<String, YouTubeAccessToken>>,
+    pub(crate) login_states: InMemLoginStates,
+    pub(crate) snapshots: Mutex<HashMap<String, PlaylistSnapshots>>,
+}
+
+/// Keeps access tokens in a `json` file (user id -> token).
+/// As `SnapshotStore`, keeps playlist snapshots in a separate file next to it
+/// (`tokens.json` -> `tokens.snapshots.json`).
+/// Login states are short-lived, so they are kept in memory.
+#[derive(Debug)]
+pub struct FileTokenStore {
+    pub(crate) path: PathBuf,
+    pub(crate) snapshots_path: PathBuf,
+    /// Guards read-modify-write cycles on the file.
+    pub(crate) lock: Mutex<()>,
+    pub(crate) login_states: InMemLoginStates,
+}
+
+impl FileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            snapshots_path: path.with_extension("snapshots.json"),
+            path,
+            lock: Mutex::new(()),
+            login_states: InMemLoginStates::default(),
+        }
+    }
+}
+
+/// Login states with their expiration time (nonce -> (state, expires at)).
+#[derive(Default, Debug)]
+pub(crate) struct InMemLoginStates(Mutex<HashMap<String, (LoginState, Instant)>>);
+
+impl InMemLoginStates {
+    pub(crate) async fn save(&self, nonce: &str, login_state: &LoginState, ttl: Duration) {
+        let now = Instant::now();
+        let mut login_states = self.0.lock().await;
+        login_states.retain(|_, (_, expires_at)| *expires_at > now);
+        login_states.insert(nonce.to_owned(), (login_state.clone(), now + ttl));
+    }
+
+    /// Removes login state, returning it only if it hasn't expired yet.
+    pub(crate) async fn take(&self, nonce: &str) -> Option<LoginState> {
+        let (login_state, expires_at) = self.0.lock().await.remove(nonce)?;
+        (expires_at > Instant::now()).then_some(login_state)
+    }
+}
@@ -1,16 +1,23 @@
-use google_youtube3::oauth2::read_application_secret;
+use google_youtube3::oauth2::{read_application_secret, ApplicationSecret};
 use maptypings::WrapInRes;
-use teloxide::types::{CallbackQuery, Message};
+use teloxide::types::{CallbackQuery, ChatId, Message};
 use url::Url;
 
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::db::types::{LoginState, LOGIN_STATE_NONCE_LEN, LOGIN_STATE_TTL};
+use crate::model::dialogue::traits::TextInput;
 use crate::model::dialogue::types::{
-    DialogueData, Either, ListCommandSettings, SearchCommandSettings,
+    CommandConfig, DialogueData, ListCommandSettings, SearchCommandSettings,
     SearchVideosInPlaylistsCommandSettings, State, TheDialogue,
 };
 use crate::model::errors::{DialogueStateStorageError, NoCallbackDataError, NoTextError};
-use crate::model::net::types::{QUERY_SEPARATOR, STATE_CODE};
-use crate::model::youtube::funcs::common::make_auth_url;
-use crate::model::youtube::types::{ACCESS_TYPE, RESPONSE_TYPE, SCOPE_YOUTUBE_READONLY};
+use crate::model::keyboards::types::Buttons;
+use crate::model::utils::random_string;
+use crate::model::youtube::funcs::common::{make_auth_url, make_code_challenge};
+use crate::model::youtube::types::{
+    ACCESS_TYPE, CODE_CHALLENGE_METHOD, CODE_VERIFIER_LEN, RESPONSE_TYPE, SCOPE_YOUTUBE_READONLY,
+};
 use crate::StdRes;
 
 pub(crate) fn search_settings_update_or_default(d_state: State) -> SearchCommandSettings {
@@ -68,16 +75,45 @@ pub(crate) async fn get_callback_data(
 }
 
 /// Construct authorization url.
-pub(crate) async fn default_auth_url(user_id: &str) -> eyre::Result<Url> {
-    let secret_path = env!("PATH_TO_GOOGLE_OAUTH_SECRET");
-    let secret = read_application_secret(secret_path).await?;
+/// Each url gets its own single-use `state`, which is mapped to the user
+/// to the chat it came from and to the command that should be executed after login.
+pub(crate) async fn default_auth_url(
+    user_id: &str,
+    chat_id: ChatId,
+    pending_command: Option<CommandConfig>,
+    app_config: &AppConfig,
+    token_store: &dyn TokenStore,
+) -> eyre::Result<Url> {
+    let secret = read_application_secret(&app_config.path_to_google_oauth_secret).await?;
+
+    let state = random_string(LOGIN_STATE_NONCE_LEN);
+    let login_state = LoginState {
+        user_id: user_id.to_owned(),
+        chat_id,
+        code_verifier: random_string(CODE_VERIFIER_LEN),
+        pending_command,
+    };
+    token_store
+        .save_login_state(&state, &login_state, LOGIN_STATE_TTL)
+        .await?;
+
+    build_auth_url(&secret, &state, &login_state.code_verifier)
+}
 
+/// Authorization url with `state` and `PKCE` code challenge.
+pub(crate) fn build_auth_url(
+    secret: &ApplicationSecret,
+    state: &str,
+    code_verifier: &str,
+) -> eyre::Result<Url> {
     let (client_id, redirect_uri) = (secret.client_id.as_str(), secret.redirect_uris[0].as_str());
     let (scope, response_type) = (&[SCOPE_YOUTUBE_READONLY], RESPONSE_TYPE);
-    let state = format!("for_user={user_id}{QUERY_SEPARATOR}state_code={STATE_CODE}");
+    let code_challenge = make_code_challenge(code_verifier);
     let optional_params = &[
         ("ACCESS_TYPE".to_owned().to_lowercase(), ACCESS_TYPE),
-        ("state".to_owned(), state.as_str()),
+        ("state".to_owned(), state),
+        ("code_challenge".to_owned(), code_challenge.as_str()),
+        ("code_challenge_method".to_owned(), CODE_CHALLENGE_METHOD),
     ];
 
     make_auth_url(
@@ -91,59 +127,65 @@ pub(crate) async fn default_auth_url(user_id: &str) -> eyre::Result<Url> {
 }
 
 /// Helper function used for `handle_text` handler.
-/// Parses user input as number in order to set it as `result limit` setting.
-pub(crate) fn parse_number(
+/// Saves user input into command settings, according to the last pressed button.
+pub(crate) fn save_text_input<T: TextInput>(
+    settings: &T,
+    button: &Buttons,
     text: &str,
-    configs: Either<&SearchCommandSettings, &ListCommandSettings>,
     dialogue_data: &DialogueData,
 ) -> (&'static str, Option<DialogueData>) {
-    log::info!(" [:: LOG ::]     @[fn]:[parse_number] :: [Started]");
-    match text.parse::<u16>() {
-        Ok(num) if num >= 1 => (
+    log::info!(" [:: LOG ::]     @[fn]:[save_text_input] :: [Started]");
+    let mut settings = settings.clone();
+    match settings.save_text_input(button, text) {
+        Some(Ok(())) => (
             "Accepted! ✅",
             Some(DialogueData {
-                state: save_res_limit(configs, num),
+                state: settings.into(),
                 ..dialogue_data.clone()
             }),
         ),
-        _ => ("Send a number greater than 0", None),
+        Some(Err(user_msg)) => (user_msg, None),
+        None => {
+            log::info!(
+                " [:: LOG ::] ... ( @[fn]:[save_text_input] [:: {:?} ::] )",
+                (settings, button)
+            );
+            ("Oops! 🤷‍♂️", None)
+        }
     }
 }
 
-fn save_res_limit(
-    configs: Either<&SearchCommandSettings, &ListCommandSettings>,
-    num: u16,
-) -> State {
-    use crate::model::dialogue::types::State::{ListCommandActive, SearchCommandActive};
-    let result_limit = Some(u32::from(num));
-    match configs {
-        Either::First(search_settings) => SearchCommandActive(SearchCommandSettings {
-            result_limit,
-            ..search_settings.clone()
-        }),
-        Either::Last(list_settings) => ListCommandActive(ListCommandSettings {
-            result_limit,
-            ..list_settings.clone()
-        }),
-    }
-}
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// Save text to search.
-pub(crate) fn save_text(
-    text: &str,
-    search_settings: SearchCommandSettings,
-    dialogue_data: &DialogueData,
-) -> (&'static str, Option<DialogueData>) {
-    log::info!(" [:: LOG ::]     @[fn]:[save_text] :: [Started]");
-    let state = State::SearchCommandActive(SearchCommandSettings {
-        text_to_search: text.to_owned().into(),
-        ..search_settings
-    });
-    (
-        "Accepted! ✅",
-        Some(DialogueData {
-            state,
-            ..dialogue_data.clone()
-        }),
-    )
+    #[test]
+    fn auth_url_build_test() {
+        let secret = ApplicationSecret {
+            client_id: "client_id_7654".to_owned(),
+            redirect_uris: vec!["https://example.com/google_callback_auth_code".to_owned()],
+            ..Default::default()
+        };
+        // Example from RFC 7636 (Appendix B).
+        let code_verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+        let url = build_auth_url(&secret, "nonce_123", code_verifier).unwrap();
+        let find = |key: &str| {
+            url.query_pairs()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.into_owned())
+        };
+
+        assert_eq!(url.host_str(), Some("accounts.google.com"));
+        assert_eq!(find("client_id").unwrap(), "client_id_7654");
+        assert_eq!(find("state").unwrap(), "nonce_123");
+        assert_eq!(
+            find("code_challenge").unwrap(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(find("code_challenge_method").unwrap(), "S256");
+        assert_eq!(find("code_verifier"), None);
+    }
 }
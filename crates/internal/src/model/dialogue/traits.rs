@@ -0,0 +1,84 @@
+use std::fmt::Debug;
+
+use crate::model::dialogue::types::{
+    ListCommandSettings, SearchCommandSettings, SearchVideosInPlaylistsCommandSettings, State,
+};
+use crate::model::keyboards::types::{
+    Buttons, ListCommandButtons, SearchCommandButtons, SearchVideoInPlaylistsCommandButtons,
+};
+use crate::StdRes;
+
+/// Command settings that can be filled in with text sent by user.
+/// `button` is the last pressed button, it tells which setting the text is for.
+pub(crate) trait TextInput: Clone + Debug + Into<State> {
+    /// Saves `text` as the setting `button` asks for.
+    /// Returns `None` if `button` doesn't ask for text,
+    /// or a message for user if `text` isn't valid.
+    fn save_text_input(&mut self, button: &Buttons, text: &str)
+        -> Option<StdRes<(), &'static str>>;
+}
+
+/// Result limit must be a number greater than 0.
+fn parse_result_limit(text: &str) -> StdRes<u32, &'static str> {
+    match text.trim().parse::<u16>() {
+        Ok(num) if num >= 1 => Ok(u32::from(num)),
+        _ => Err("Send a number greater than 0"),
+    }
+}
+
+impl TextInput for SearchCommandSettings {
+    fn save_text_input(
+        &mut self,
+        button: &Buttons,
+        text: &str,
+    ) -> Option<StdRes<(), &'static str>> {
+        match button {
+            Buttons::SearchButtons(SearchCommandButtons::ResultLimit) => {
+                parse_result_limit(text).map(|r| self.result_limit = Some(r))
+            }
+            Buttons::SearchButtons(SearchCommandButtons::TextToSearch) => {
+                self.text_to_search = Some(text.to_owned());
+                Ok(())
+            }
+            _ => return None,
+        }
+        .into()
+    }
+}
+
+impl TextInput for ListCommandSettings {
+    fn save_text_input(
+        &mut self,
+        button: &Buttons,
+        text: &str,
+    ) -> Option<StdRes<(), &'static str>> {
+        match button {
+            Buttons::ListButtons(ListCommandButtons::ResultLimit) => {
+                parse_result_limit(text).map(|r| self.result_limit = Some(r))
+            }
+            _ => return None,
+        }
+        .into()
+    }
+}
+
+impl TextInput for SearchVideosInPlaylistsCommandSettings {
+    fn save_text_input(
+        &mut self,
+        button: &Buttons,
+        text: &str,
+    ) -> Option<StdRes<(), &'static str>> {
+        use SearchVideoInPlaylistsCommandButtons::{ResultLimit, TextToSearch};
+        match button {
+            Buttons::SearchVideoInPlaylistsButtons(ResultLimit) => {
+                parse_result_limit(text).map(|r| self.result_limit = Some(r))
+            }
+            Buttons::SearchVideoInPlaylistsButtons(TextToSearch) => {
+                self.text_to_search = Some(text.to_owned());
+                Ok(())
+            }
+            _ => return None,
+        }
+        .into()
+    }
+}
@@ -4,7 +4,9 @@ use teloxide::dispatching::dialogue::ErasedStorage;
 use teloxide::prelude::{CallbackQuery, Dialogue, Message};
 use teloxide::types::InlineKeyboardMarkup;
 
-use crate::model::keyboards::types::{Requestable, SearchIn, Sorting};
+use crate::model::keyboards::types::{
+    ExportFormat, MatchMode, Requestable, SearchIn, Sorting, StatsFormat, SubscriptionStatsFormat,
+};
 use crate::model::utils::{print_if_none, HTMLise};
 use crate::StdRes;
 
@@ -22,6 +24,13 @@ pub(crate) enum CommandConfig {
     SearchConfig(SearchConfig),
     ListConfig(ListConfig),
     SearchVideosInPlaylistsConfig(SearchVideosInPlaylistsConfig),
+    ExportConfig(ExportConfig),
+    SnapshotPlaylists,
+    PlaylistChanges,
+    PlaylistStatsConfig(PlaylistStatsConfig),
+    FindDuplicatesConfig(FindDuplicatesConfig),
+    InactiveSubscriptionsConfig(InactiveSubscriptionsConfig),
+    SubscriptionStatsConfig(SubscriptionStatsConfig),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -30,6 +39,8 @@ pub(crate) struct SearchConfig {
     pub(crate) result_limit: u32,
     pub(crate) search_in: SearchIn,
     pub(crate) text_to_search: String,
+    #[serde(default)]
+    pub(crate) match_mode: MatchMode,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -44,15 +55,47 @@ pub(crate) struct SearchVideosInPlaylistsConfig {
     pub(crate) result_limit: u32,
     pub(crate) search_in: SearchIn,
     pub(crate) text_to_search: String,
+    #[serde(default)]
+    pub(crate) match_mode: MatchMode,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub(crate) struct ExportConfig {
+    pub(crate) target: Requestable,
+    pub(crate) format: ExportFormat,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub(crate) struct PlaylistStatsConfig {
+    pub(crate) format: StatsFormat,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub(crate) struct FindDuplicatesConfig {
+    /// Title, id or link of the only playlist to check.
+    pub(crate) playlist: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub(crate) struct SubscriptionStatsConfig {
+    pub(crate) format: SubscriptionStatsFormat,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub(crate) struct InactiveSubscriptionsConfig {
+    /// Channels without uploads for this many months are shown.
+    pub(crate) months: u8,
 }
 
 /// Stores settings for `search` command (fields may be 'None').
+/// Unlike other fields, `match_mode` is optional and defaults to `MatchMode::Exact`.
 #[derive(Default, Clone, Serialize, Deserialize, Debug)]
 pub struct SearchCommandSettings {
     pub(crate) target: Option<Requestable>,
     pub(crate) result_limit: Option<u32>,
     pub(crate) search_in: Option<SearchIn>,
     pub(crate) text_to_search: Option<String>,
+    pub(crate) match_mode: Option<MatchMode>,
 }
 
 impl SearchCommandSettings {
@@ -63,17 +106,20 @@ impl SearchCommandSettings {
                 result_limit: Some(r),
                 search_in: Some(s),
                 text_to_search: Some(text),
+                match_mode: m,
             } => Ok(SearchConfig {
                 target: t,
                 result_limit: r,
                 search_in: s,
                 text_to_search: text,
+                match_mode: m.unwrap_or_default(),
             }),
             Self {
                 target: t,
                 result_limit: r,
                 search_in: s,
                 text_to_search: text,
+                ..
             } => {
                 let t = print_if_none(t, format!("\n🎯 {}", "Target".to_bold()).as_str());
                 let r = print_if_none(r, format!("\n🧮 {}", "Result limit".to_bold()));
@@ -91,6 +137,10 @@ impl SearchCommandSettings {
     pub(crate) fn update_search_in(&mut self, search_in: SearchIn) {
         self.search_in = Some(search_in);
     }
+
+    pub(crate) fn update_match_mode(&mut self, match_mode: MatchMode) {
+        self.match_mode = Some(match_mode);
+    }
 }
 
 /// Stores settings for `list` command (fields may be 'None').
@@ -141,6 +191,7 @@ pub struct SearchVideosInPlaylistsCommandSettings {
     pub(crate) result_limit: Option<u32>,
     pub(crate) search_in: Option<SearchIn>,
     pub(crate) text_to_search: Option<String>,
+    pub(crate) match_mode: Option<MatchMode>,
 }
 
 impl SearchVideosInPlaylistsCommandSettings {
@@ -150,15 +201,18 @@ impl SearchVideosInPlaylistsCommandSettings {
                 result_limit: Some(r),
                 search_in: Some(s),
                 text_to_search: Some(text),
+                match_mode: m,
             } => Ok(SearchVideosInPlaylistsConfig {
                 result_limit: r,
                 search_in: s,
                 text_to_search: text,
+                match_mode: m.unwrap_or_default(),
             }),
             Self {
                 result_limit: r,
                 search_in: s,
                 text_to_search: text,
+                ..
             } => {
                 let r = print_if_none(r, format!("\n🧮 {}", "Result limit".to_bold()));
                 let s = print_if_none(s, format!("\n💳 {}", "Search in".to_bold()));
@@ -171,6 +225,10 @@ impl SearchVideosInPlaylistsCommandSettings {
     pub(crate) fn update_search_in(&mut self, search_in: SearchIn) {
         self.search_in = Some(search_in);
     }
+
+    pub(crate) fn update_match_mode(&mut self, match_mode: MatchMode) {
+        self.match_mode = Some(match_mode);
+    }
 }
 
 /// Stores `dialogue state`.
@@ -183,6 +241,24 @@ pub enum State {
     SearchVideosInPlaylistsCommandActive(SearchVideosInPlaylistsCommandSettings),
 }
 
+impl From<SearchCommandSettings> for State {
+    fn from(settings: SearchCommandSettings) -> Self {
+        State::SearchCommandActive(settings)
+    }
+}
+
+impl From<ListCommandSettings> for State {
+    fn from(settings: ListCommandSettings) -> Self {
+        State::ListCommandActive(settings)
+    }
+}
+
+impl From<SearchVideosInPlaylistsCommandSettings> for State {
+    fn from(settings: SearchVideosInPlaylistsCommandSettings) -> Self {
+        State::SearchVideosInPlaylistsCommandActive(settings)
+    }
+}
+
 impl AsRef<State> for State {
     #[inline]
     fn as_ref(&self) -> &State {
@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::model::duplicates::types::{Duplicate, VideoCopy};
+use crate::model::youtube::traits::{add_playlist_title, IntoSearchableItem};
+use crate::model::youtube::types::{PlaylistContents, SearchableItem};
+
+/// Playlists matching `only`, by id, link or case insensitive title.
+/// All playlists if `only` is `None`.
+pub(crate) fn select_playlists(
+    playlists: Vec<(String, String)>,
+    only: Option<&str>,
+) -> Vec<(String, String)> {
+    let Some(only) = only.map(str::trim).filter(|s| !s.is_empty()) else {
+        return playlists;
+    };
+    let only_lowercase = only.to_lowercase();
+    playlists
+        .into_iter()
+        .filter(|(id, title)| {
+            id == only
+                || only.contains(&format!("list={id}"))
+                || title.to_lowercase() == only_lowercase
+        })
+        .collect()
+}
+
+/// Groups items of all playlists by video id, and keeps videos found more than once.
+/// Videos with more copies go first, otherwise videos keep order they were found in.
+pub(crate) fn find_duplicates(playlists: Vec<PlaylistContents>) -> Vec<Duplicate> {
+    let mut index_of_video: HashMap<String, usize> = HashMap::new();
+    let mut videos: Vec<Duplicate> = vec![];
+    for playlist in playlists {
+        for item in playlist.items {
+            let video_id = item
+                .snippet
+                .as_ref()
+                .and_then(|s| s.resource_id.as_ref()?.video_id.clone())
+                .filter(|id| !id.trim().is_empty());
+            let Some(video_id) = video_id else { continue };
+
+            let copy = VideoCopy {
+                playlist_title: playlist.title.clone(),
+                item,
+            };
+            match index_of_video.get(&video_id) {
+                Some(&i) => videos[i].copies.push(copy),
+                None => {
+                    index_of_video.insert(video_id, videos.len());
+                    videos.push(Duplicate { copies: vec![copy] });
+                }
+            }
+        }
+    }
+    videos.retain(|v| v.copies.len() > 1);
+    videos.sort_by_key(|v| Reverse(v.copies.len()));
+    videos
+}
+
+/// One result per copy, so that each one has a link to its own place in a playlist.
+pub(crate) fn duplicates_to_items(duplicates: Vec<Duplicate>) -> Vec<SearchableItem> {
+    duplicates
+        .into_iter()
+        .flat_map(|duplicate| {
+            let copy_count = duplicate.copies.len();
+            duplicate
+                .copies
+                .into_iter()
+                .enumerate()
+                .map(move |(i, copy)| {
+                    let position = match copy.item.snippet.as_ref().and_then(|s| s.position) {
+                        Some(p) => (p + 1).to_string(),
+                        None => "?".to_owned(),
+                    };
+                    let mut item = copy.item.into_item();
+                    item.description = format!(
+                        "🔁 Copy {} of {copy_count} \n📜 {}, position {position}",
+                        i + 1,
+                        copy.playlist_title
+                    )
+                    .into();
+                    add_playlist_title(&mut item, &copy.playlist_title);
+                    item
+                })
+        })
+        .collect()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use google_youtube3::api::{PlaylistItem, PlaylistItemListResponse};
+
+    use super::*;
+
+    fn playlist(playlist_id: &str, title: &str) -> PlaylistContents {
+        let response: PlaylistItemListResponse = serde_json::from_str(include_str!(
+            "../../../../../test_assets/playlist_item_list_json_response.json"
+        ))
+        .unwrap();
+        let items: Vec<PlaylistItem> = response
+            .items
+            .unwrap()
+            .into_iter()
+            .filter(|i| {
+                let snippet = i.snippet.as_ref().unwrap();
+                snippet.playlist_id.as_deref() == Some(playlist_id)
+            })
+            .collect();
+        PlaylistContents {
+            playlist_id: playlist_id.to_owned(),
+            title: title.to_owned(),
+            items,
+        }
+    }
+
+    fn interviews() -> PlaylistContents {
+        playlist("PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a", "Интервью")
+    }
+
+    fn reviews() -> PlaylistContents {
+        playlist("PL1Ng5zOyG8ExhDHpEMRYJPR-1JoIFr0i9", "Обзоры")
+    }
+
+    #[test]
+    fn select_playlists_test() {
+        let playlists = vec![
+            (
+                "PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a".to_owned(),
+                "Интервью".to_owned(),
+            ),
+            (
+                "PL1Ng5zOyG8ExhDHpEMRYJPR-1JoIFr0i9".to_owned(),
+                "Обзоры".to_owned(),
+            ),
+        ];
+        let selected = |only| select_playlists(playlists.clone(), only);
+
+        assert_eq!(selected(None), playlists);
+        assert_eq!(selected(Some("  ")), playlists);
+        assert_eq!(selected(Some(" обзоры ")), playlists[1..]);
+        assert_eq!(
+            selected(Some("PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a")),
+            playlists[..1]
+        );
+        assert_eq!(
+            selected(Some(
+                "https://youtube.com/playlist?list=PL1Ng5zOyG8ExhDHpEMRYJPR-1JoIFr0i9"
+            )),
+            playlists[1..]
+        );
+        assert!(selected(Some("Истории")).is_empty());
+    }
+
+    #[test]
+    fn find_duplicates_test() {
+        let duplicates = find_duplicates(vec![interviews(), reviews()]);
+        let found: Vec<_> = duplicates
+            .iter()
+            .map(|d| {
+                let titles = d.copies.iter().map(|c| c.playlist_title.as_str());
+                let video_id = d.copies[0]
+                    .item
+                    .content_details
+                    .as_ref()
+                    .unwrap()
+                    .video_id
+                    .as_deref();
+                (video_id.unwrap(), titles.collect::<Vec<_>>())
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("kV8dI6dl6bU", vec!["Интервью", "Обзоры"]),
+                ("Yy_m44vlXBk", vec!["Интервью", "Интервью"]),
+            ]
+        );
+
+        let only_reviews = find_duplicates(vec![reviews()]);
+        assert!(only_reviews.is_empty());
+    }
+
+    #[test]
+    fn duplicates_to_items_test() {
+        let items = duplicates_to_items(find_duplicates(vec![interviews()]));
+        assert_eq!(items.len(), 2);
+
+        let second = &items[1];
+        assert_eq!(second.title.as_deref(), Some("Интервью с композитором"));
+        assert_eq!(
+            second.description.as_deref(),
+            Some("🔁 Copy 2 of 2 \n📜 Интервью, position 3")
+        );
+        assert!(second.link.as_deref().unwrap().ends_with("&index=2"));
+        let about = second.about.as_deref().unwrap();
+        let playlist_link = "https://youtube.com/playlist?list=PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a";
+        assert!(about.contains(&format!("[Playlist]\n \nИнтервью \n{playlist_link}")));
+    }
+}
@@ -0,0 +1,14 @@
+use google_youtube3::api::PlaylistItem;
+
+/// Video that appears in playlists more than once.
+#[derive(Debug, Clone)]
+pub(crate) struct Duplicate {
+    pub(crate) copies: Vec<VideoCopy>,
+}
+
+/// One of the places a duplicated video appears in.
+#[derive(Debug, Clone)]
+pub(crate) struct VideoCopy {
+    pub(crate) playlist_title: String,
+    pub(crate) item: PlaylistItem,
+}
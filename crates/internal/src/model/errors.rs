@@ -2,6 +2,8 @@ use std::fmt::{Debug, Display};
 
 use thiserror::Error;
 
+use crate::model::net::types::GoogleErrorResponse;
+
 #[derive(Error, Debug, Clone)]
 pub enum NetworkError {
     #[error(
@@ -46,6 +48,45 @@ pub struct MissingEnvVarError<'a> {
     pub var: &'a str,
 }
 
+#[derive(Error, Debug, Clone)]
+#[error(
+    "[ InvalidConfigValueError ] : ( Invalid value of \"{}\": {}. )",
+    key,
+    reason
+)]
+pub struct InvalidConfigValueError {
+    pub key: &'static str,
+    pub reason: String,
+}
+
+/// Every problem found while loading config, so that all of them can be fixed at once.
+#[derive(Error, Debug, Clone)]
+#[error("[ ConfigError ] : ( {} )", self.describe())]
+pub struct ConfigError {
+    pub missing: Vec<MissingEnvVarError<'static>>,
+    pub invalid: Vec<InvalidConfigValueError>,
+}
+
+impl ConfigError {
+    fn describe(&self) -> String {
+        let missing = self.missing.iter().map(|e| e.var).collect::<Vec<_>>();
+        let invalid = self
+            .invalid
+            .iter()
+            .map(|e| format!("{} ({})", e.key, e.reason))
+            .collect::<Vec<_>>();
+        match (missing.is_empty(), invalid.is_empty()) {
+            (false, true) => format!("Missing keys: {}.", missing.join(", ")),
+            (true, false) => format!("Invalid values: {}.", invalid.join(", ")),
+            _ => format!(
+                "Missing keys: {}. Invalid values: {}.",
+                missing.join(", "),
+                invalid.join(", ")
+            ),
+        }
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 #[error(
     "[ {:?} ] : ( Problem with storage that stores dialogue state. )",
@@ -86,3 +127,153 @@ pub struct NoMessageWithKB;
     Self
 )]
 pub struct MissingType;
+
+/// Failed `YouTube API` request, classified by the reason `Google` gives in error body.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum YouTubeApiError {
+    #[error("[ YouTubeApiError ] : ( Quota exceeded: {message} )")]
+    QuotaExceeded { message: String },
+    #[error("[ YouTubeApiError ] : ( Rate limit exceeded: {message} )")]
+    RateLimitExceeded { message: String },
+    #[error("[ YouTubeApiError ] : ( Authorization failed: {message} )")]
+    AuthError { message: String },
+    #[error("[ YouTubeApiError ] : ( Not found ({reason}): {message} )")]
+    NotFound { reason: String, message: String },
+    #[error("[ YouTubeApiError ] : ( Server error {status}: {message} )")]
+    ServerError { status: u16, message: String },
+    #[error("[ YouTubeApiError ] : ( Request failed with {status} ({reason}): {message} )")]
+    Other {
+        status: u16,
+        reason: String,
+        message: String,
+    },
+}
+
+impl YouTubeApiError {
+    /// Classifies failed response by its status and `Google`'s error `JSON`.
+    /// Body that isn't a valid error `JSON` is kept as a message.
+    pub(crate) fn from_response(status: u16, body: &str) -> Self {
+        let (reason, message) = match serde_json::from_str::<GoogleErrorResponse>(body) {
+            Ok(GoogleErrorResponse { error }) => {
+                let reason = error.errors.first().map(|e| e.reason.clone());
+                (reason.unwrap_or_default(), error.message)
+            }
+            Err(_) => (String::new(), body.trim().to_owned()),
+        };
+        match (status, reason.as_str()) {
+            (_, "quotaExceeded" | "dailyLimitExceeded") => Self::QuotaExceeded { message },
+            (_, "rateLimitExceeded" | "userRateLimitExceeded") | (429, _) => {
+                Self::RateLimitExceeded { message }
+            }
+            (_, "authError" | "invalidCredentials") | (401, _) => Self::AuthError { message },
+            (404, _) => Self::NotFound { reason, message },
+            (500..=599, _) => Self::ServerError { status, message },
+            _ => Self::Other {
+                status,
+                reason,
+                message,
+            },
+        }
+    }
+
+    /// Errors that may go away if the same request is repeated later.
+    pub(crate) fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimitExceeded { .. } | Self::ServerError { .. }
+        )
+    }
+
+    /// Errors every other request would fail with too (expired token, exhausted quota).
+    /// The rest concern only the requested resource, like a deleted or forbidden playlist.
+    pub(crate) fn is_fatal(&self) -> bool {
+        !matches!(self, Self::NotFound { .. } | Self::Other { .. })
+    }
+
+    /// Message that is shown to user instead of results.
+    pub(crate) fn user_message(&self) -> &'static str {
+        match self {
+            Self::QuotaExceeded { .. } => {
+                "YouTube API daily quota is exhausted 🪫 \nPlease try again tomorrow"
+            }
+            Self::RateLimitExceeded { .. } => {
+                "Too many requests to YouTube 🐢 \nPlease try again in a minute"
+            }
+            Self::AuthError { .. } => {
+                "YouTube didn't accept your authorization 🔑 \nUse /log_out and try again"
+            }
+            Self::NotFound { .. } => "YouTube couldn't find what was requested 🤷‍♂️",
+            Self::ServerError { .. } => "YouTube is having problems 🛠 \nPlease try again later",
+            Self::Other { .. } => "YouTube refused the request ⛔",
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn google_error(code: u16, reason: &str, message: &str) -> String {
+        serde_json::json!({
+            "error": {
+                "code": code,
+                "message": message,
+                "errors": [{ "message": message, "domain": "youtube.quota", "reason": reason }],
+                "status": "PERMISSION_DENIED"
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn youtube_api_error_from_response_test() {
+        let quota = YouTubeApiError::from_response(
+            403,
+            &google_error(403, "quotaExceeded", "The request cannot be completed"),
+        );
+        assert_eq!(
+            quota,
+            YouTubeApiError::QuotaExceeded {
+                message: "The request cannot be completed".to_owned()
+            }
+        );
+        assert!(!quota.is_retryable());
+        assert!(quota.is_fatal());
+
+        let rate = YouTubeApiError::from_response(403, &google_error(403, "rateLimitExceeded", ""));
+        assert!(matches!(rate, YouTubeApiError::RateLimitExceeded { .. }));
+        assert!(rate.is_retryable());
+
+        let auth = YouTubeApiError::from_response(401, &google_error(401, "authError", ""));
+        assert!(matches!(auth, YouTubeApiError::AuthError { .. }));
+        assert!(auth.is_fatal());
+
+        let not_found =
+            YouTubeApiError::from_response(404, &google_error(404, "playlistNotFound", "nope"));
+        assert_eq!(
+            not_found,
+            YouTubeApiError::NotFound {
+                reason: "playlistNotFound".to_owned(),
+                message: "nope".to_owned()
+            }
+        );
+        assert!(!not_found.is_fatal());
+
+        let server = YouTubeApiError::from_response(503, "<html>Service Unavailable</html>");
+        assert_eq!(
+            server,
+            YouTubeApiError::ServerError {
+                status: 503,
+                message: "<html>Service Unavailable</html>".to_owned()
+            }
+        );
+        assert!(server.is_retryable());
+
+        let other = YouTubeApiError::from_response(400, &google_error(400, "invalidValue", ""));
+        assert!(matches!(other, YouTubeApiError::Other { status: 400, .. }));
+        assert!(!other.is_retryable());
+        assert!(!other.is_fatal());
+    }
+}
@@ -0,0 +1,235 @@
+use maptypings::WrapInRes;
+
+use crate::model::export::types::{ExportDocument, ExportRecord, CSV_HEADER};
+use crate::model::keyboards::types::ExportFormat;
+
+/// Quotes `CSV` field if it contains separators, quotes or line breaks.
+pub(crate) fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Escapes text for use in `XML` attribute.
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut s, c| {
+            match c {
+                '&' => s.push_str("&amp;"),
+                '<' => s.push_str("&lt;"),
+                '>' => s.push_str("&gt;"),
+                '"' => s.push_str("&quot;"),
+                '\'' => s.push_str("&apos;"),
+                '\n' => s.push_str("&#10;"),
+                c => s.push(c),
+            }
+            s
+        })
+}
+
+pub(crate) fn to_csv(records: &[ExportRecord]) -> String {
+    let mut csv = CSV_HEADER.join(",") + "\r\n";
+    for r in records {
+        let fields = [
+            &r.id,
+            &r.title,
+            &r.description,
+            &r.published_at,
+            &r.url,
+            &r.feed_url,
+        ];
+        let line = fields.map(|f| escape_csv(f)).join(",");
+        csv.push_str(&line);
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub(crate) fn to_json(records: &[ExportRecord]) -> eyre::Result<String> {
+    serde_json::to_string_pretty(records)?.in_ok()
+}
+
+/// `OPML` outline of `RSS` feeds, can be imported into most feed readers.
+pub(crate) fn to_opml(records: &[ExportRecord], title: &str) -> String {
+    let outlines: String = records
+        .iter()
+        .map(|r| {
+            let text = escape_xml(&r.title);
+            format!(
+                "      <outline type=\"rss\" text=\"{text}\" title=\"{text}\" \
+                xmlUrl=\"{}\" htmlUrl=\"{}\"/>\n",
+                escape_xml(&r.feed_url),
+                escape_xml(&r.url)
+            )
+        })
+        .collect();
+    let title = escape_xml(title);
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <opml version=\"1.0\">\n\
+        \x20 <head>\n\
+        \x20   <title>{title}</title>\n\
+        \x20 </head>\n\
+        \x20 <body>\n\
+        \x20   <outline text=\"{title}\" title=\"{title}\">\n\
+        {outlines}\
+        \x20   </outline>\n\
+        \x20 </body>\n\
+        </opml>\n"
+    )
+}
+
+fn file_extension(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Csv => "csv",
+        ExportFormat::Json => "json",
+        ExportFormat::Opml => "opml",
+    }
+}
+
+/// Serializes records into a file named `name` with extension of `format`.
+pub(crate) fn build_document(
+    records: &[ExportRecord],
+    format: ExportFormat,
+    name: &str,
+) -> eyre::Result<ExportDocument> {
+    let content = match format {
+        ExportFormat::Csv => to_csv(records),
+        ExportFormat::Json => to_json(records)?,
+        ExportFormat::Opml => to_opml(records, &format!("YouTube {name}")),
+    };
+    ExportDocument {
+        file_name: format!("youtube_{name}.{}", file_extension(format)),
+        content: content.into_bytes(),
+        record_count: records.len(),
+    }
+    .in_ok()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use google_youtube3::api::{PlaylistListResponse, SubscriptionListResponse};
+
+    use crate::model::youtube::traits::Exportable;
+
+    use super::*;
+
+    fn subscription_records() -> Vec<ExportRecord> {
+        let subs =
+            std::fs::read_to_string("../../test_assets/subscription_list_json_response.json")
+                .unwrap();
+        let subs_list_resp = serde_json::from_str::<SubscriptionListResponse>(&subs).unwrap();
+        subs_list_resp
+            .items
+            .unwrap()
+            .iter()
+            .filter_map(Exportable::export_record)
+            .collect()
+    }
+
+    fn tricky_record() -> ExportRecord {
+        ExportRecord {
+            id: "UC123".to_owned(),
+            title: "Tom & \"Jerry\", <live>".to_owned(),
+            description: "line one\nline two".to_owned(),
+            published_at: String::new(),
+            url: "https://youtube.com/channel/UC123".to_owned(),
+            feed_url: "https://www.youtube.com/feeds/videos.xml?channel_id=UC123".to_owned(),
+        }
+    }
+
+    #[test]
+    fn subscription_records_test() {
+        let records = subscription_records();
+        assert!(!records.is_empty());
+
+        let first = &records[0];
+        assert_eq!(first.id, "UCYmna5rFHIesFteksAvFOfg");
+        assert_eq!(first.title, "Dr. Plants");
+        assert_eq!(
+            first.url,
+            "https://youtube.com/channel/UCYmna5rFHIesFteksAvFOfg"
+        );
+        assert_eq!(
+            first.feed_url,
+            "https://www.youtube.com/feeds/videos.xml?channel_id=UCYmna5rFHIesFteksAvFOfg"
+        );
+    }
+
+    #[test]
+    fn playlist_records_test() {
+        let plists =
+            std::fs::read_to_string("../../test_assets/playlist_list_json_response.json").unwrap();
+        let plist_list_resp = serde_json::from_str::<PlaylistListResponse>(&plists).unwrap();
+        let items = plist_list_resp.items.unwrap();
+        let records: Vec<_> = items.iter().filter_map(Exportable::export_record).collect();
+        assert!(!records.is_empty());
+
+        let plist_id = items[0].id.as_deref().unwrap();
+        assert_eq!(records[0].id, plist_id);
+        assert!(records[0]
+            .feed_url
+            .ends_with(&format!("?playlist_id={plist_id}")));
+    }
+
+    #[test]
+    fn csv_test() {
+        let records = subscription_records();
+        let csv = to_csv(&records);
+        let lines: Vec<_> = csv.split("\r\n").filter(|l| !l.is_empty()).collect();
+
+        assert_eq!(lines[0], "id,title,description,published_at,url,feed_url");
+        assert!(lines[1].starts_with("UCYmna5rFHIesFteksAvFOfg,Dr. Plants,"));
+        assert!(lines[1].ends_with(
+            ",https://www.youtube.com/feeds/videos.xml?channel_id=UCYmna5rFHIesFteksAvFOfg"
+        ));
+
+        let csv = to_csv(&[tricky_record()]);
+        assert!(csv.contains("UC123,\"Tom & \"\"Jerry\"\", <live>\",\"line one\nline two\","));
+    }
+
+    #[test]
+    fn json_test() {
+        let records = subscription_records();
+        let json = to_json(&records).unwrap();
+        let parsed: Vec<ExportRecord> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, records);
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["title"], "Dr. Plants");
+    }
+
+    #[test]
+    fn opml_test() {
+        let records = subscription_records();
+        let opml = to_opml(&records, "YouTube subscriptions");
+
+        assert!(
+            opml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"1.0\">")
+        );
+        assert!(opml.contains("<title>YouTube subscriptions</title>"));
+        assert_eq!(opml.matches("<outline type=\"rss\"").count(), records.len());
+        assert!(opml.contains(
+            "<outline type=\"rss\" text=\"Dr. Plants\" title=\"Dr. Plants\" \
+            xmlUrl=\"https://www.youtube.com/feeds/videos.xml?channel_id=UCYmna5rFHIesFteksAvFOfg\" \
+            htmlUrl=\"https://youtube.com/channel/UCYmna5rFHIesFteksAvFOfg\"/>"
+        ));
+
+        let opml = to_opml(&[tricky_record()], "a < b");
+        assert!(opml.contains("text=\"Tom &amp; &quot;Jerry&quot;, &lt;live&gt;\""));
+        assert!(opml.contains("<title>a &lt; b</title>"));
+    }
+
+    #[test]
+    fn build_document_test() {
+        let records = subscription_records();
+        let doc = build_document(&records, ExportFormat::Opml, "subscriptions").unwrap();
+        assert_eq!(doc.file_name, "youtube_subscriptions.opml");
+        assert_eq!(doc.record_count, records.len());
+        assert!(String::from_utf8(doc.content).unwrap().contains("<opml"));
+    }
+}
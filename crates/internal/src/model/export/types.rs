@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// `RSS` feed of a channel, channel id goes after `=`.
+pub(crate) const CHANNEL_FEED_URL: &str = "https://www.youtube.com/feeds/videos.xml?channel_id=";
+
+/// `RSS` feed of a playlist, playlist id goes after `=`.
+pub(crate) const PLAYLIST_FEED_URL: &str = "https://www.youtube.com/feeds/videos.xml?playlist_id=";
+
+/// Maximum number of items in one export.
+pub(crate) const EXPORT_LIMIT: u32 = 5000;
+
+/// Column names of exported `CSV` file, in the same order as `ExportRecord` fields.
+pub(crate) const CSV_HEADER: [&str; 6] = [
+    "id",
+    "title",
+    "description",
+    "published_at",
+    "url",
+    "feed_url",
+];
+
+/// Exported subscription or playlist.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportRecord {
+    /// Channel id for subscriptions, playlist id for playlists.
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) description: String,
+    pub(crate) published_at: String,
+    pub(crate) url: String,
+    /// `RSS` feed url, that can be added to any feed reader.
+    pub(crate) feed_url: String,
+}
+
+/// File, ready to be sent as `Telegram` document.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportDocument {
+    pub(crate) file_name: String,
+    pub(crate) content: Vec<u8>,
+    pub(crate) record_count: usize,
+}
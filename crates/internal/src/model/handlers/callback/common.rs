@@ -1,49 +1,118 @@
-use crate::dialogue::DialogueData;
-use crate::model::db::{build_refresh_access_token_req, refresh_access_token};
+use crate::model::config::types::AppConfig;
+use crate::model::db::funcs::{build_refresh_access_token_req, refresh_access_token};
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::funcs::{default_auth_url, get_callback_data, get_dialogue_data};
-use crate::model::dialogue::types::TheDialogue;
+use crate::model::dialogue::types::{CommandConfig, TheDialogue};
+use crate::model::errors::YouTubeApiError;
+use crate::model::handlers::callback::export_cmd::callback_helper_for_export_kb;
+use crate::model::handlers::callback::inactive_cmd::callback_helper_for_inactive_subscriptions_kb;
 use crate::model::handlers::callback::list_cmd::callback_helper_for_list_kb;
+use crate::model::handlers::callback::pagination::callback_helper_for_page_kb;
+use crate::model::handlers::callback::playlist_stats_cmd::callback_helper_for_playlist_stats_kb;
 use crate::model::handlers::callback::search_cmd::callback_helper_for_search_kb;
 use crate::model::handlers::callback::search_videos_in_playlits::callback_helper_for_search_videos_in_playlists_kb;
+use crate::model::handlers::callback::subscription_stats_cmd::callback_helper_for_subscription_stats_kb;
 use crate::model::keyboards::types::Buttons;
 use crate::model::keyboards::types::Buttons::{
-    ListButtons, SearchButtons, SearchVideoInPlaylistsButtons,
+    ExportButtons, InactiveSubscriptionsButtons, ListButtons, PageButtons, PlaylistStatsButtons,
+    SearchButtons, SearchVideoInPlaylistsButtons, SubscriptionStatsButtons,
 };
+use crate::model::pagination::types::ResultCache;
 use crate::model::utils::HTMLise;
 use crate::model::youtube::types::{SearchableItem, YouTubeAccessToken};
 use crate::view::types::Sendable;
+use crate::StdRes;
 use error_traits::{MergeOkErr, PassErrWith};
 use google_youtube3::oauth2::read_application_secret;
 use maptypings::WrapInRes;
 use teloxide::prelude::CallbackQuery;
+use teloxide::types::ChatId;
 
 pub(crate) type ResTriplet = (Option<String>, Vec<SearchableItem>, Option<String>);
 
-pub(crate) async fn get_required_callback_data(
-    callback: &CallbackQuery,
-    dialogue: TheDialogue,
-) -> eyre::Result<(DialogueData, Buttons)> {
+/// Shown instead of results when user has no channel to take uploads from.
+pub(crate) fn no_channel_msg() -> ResTriplet {
+    let msg = "You don't have a YouTube channel yet 🤷‍♂️";
+    (msg.to_owned().into(), vec![], None)
+}
+
+/// Shown instead of results when text to search isn't a valid regular expression.
+pub(crate) fn invalid_regex_msg(error: &regex::Error) -> ResTriplet {
+    let msg = format!(
+        "Text to search is not a valid regular expression ❌ \n<pre>{}</pre>",
+        error.to_escaped()
+    );
+    (msg.into(), vec![], None)
+}
+
+/// Text that user sees when command fails.
+/// `YouTube API` errors get a specific message, everything else is an internal error.
+pub(crate) fn user_error_message(error: &eyre::Report) -> String {
+    error
+        .downcast_ref::<YouTubeApiError>()
+        .map_or("⚠ Internal error ⚠", YouTubeApiError::user_message)
+        .to_owned()
+}
+
+pub(crate) async fn get_required_callback_data(callback: &CallbackQuery) -> eyre::Result<Buttons> {
     log::info!(" [:: LOG ::]     @[fn]:[get_required_callback_data] :: [Started]");
 
-    let dialogue_data = get_dialogue_data(&dialogue).await?;
     let callback_data = get_callback_data(callback).await?;
     let keyboard: Buttons = serde_json::from_str(&callback_data)?;
 
-    (dialogue_data, keyboard).in_ok()
+    keyboard.in_ok()
 }
 
 /// Main `text` handler.
 pub(crate) async fn handle_callback(
     callback: CallbackQuery,
     dialogue: TheDialogue,
+    result_cache: &ResultCache,
 ) -> Sendable<String> {
     log::info!(" [:: LOG ::]     @[fn]:[handlers::handle_callback] :: [Started]");
 
     let log_prefix = " [:: LOG ::]     @[fn]:[handlers::handle_callback]";
+    let internal_error = || Sendable::SendError("⚠ Internal error ⚠".to_owned());
 
-    let res = get_required_callback_data(&callback, dialogue).await;
-    let Ok((d_data, buttons)) = res.pass_err_with(|e| log::error!("{log_prefix}{e:?}")) else {
-        return Sendable::SendError("⚠ Internal error ⚠".to_owned());
+    let res = get_required_callback_data(&callback).await;
+    let Ok(buttons) = res.pass_err_with(|e| log::error!("{log_prefix}{e:?}")) else {
+        return internal_error();
+    };
+    // Results can be browsed long after the command was executed, so dialogue isn't required.
+    if let PageButtons(page_kb) = &buttons {
+        return callback_helper_for_page_kb(page_kb, callback, result_cache)
+            .await
+            .map_err(Sendable::SendError)
+            .merge_ok_err();
+    }
+    if let ExportButtons(export_kb) = &buttons {
+        return callback_helper_for_export_kb(export_kb)
+            .await
+            .map_err(Sendable::SendError)
+            .merge_ok_err();
+    }
+    if let PlaylistStatsButtons(stats_kb) = &buttons {
+        return callback_helper_for_playlist_stats_kb(stats_kb)
+            .await
+            .map_err(Sendable::SendError)
+            .merge_ok_err();
+    }
+    if let SubscriptionStatsButtons(stats_kb) = &buttons {
+        return callback_helper_for_subscription_stats_kb(stats_kb)
+            .await
+            .map_err(Sendable::SendError)
+            .merge_ok_err();
+    }
+    if let InactiveSubscriptionsButtons(inactive_kb) = &buttons {
+        return callback_helper_for_inactive_subscriptions_kb(inactive_kb)
+            .await
+            .map_err(Sendable::SendError)
+            .merge_ok_err();
+    }
+
+    let res = get_dialogue_data(&dialogue).await;
+    let Ok(d_data) = res.pass_err_with(|e| log::error!("{log_prefix}{e:?}")) else {
+        return internal_error();
     };
     let res = match &buttons {
         SearchButtons(search_kb) => {
@@ -58,23 +127,60 @@ pub(crate) async fn handle_callback(
             )
             .await
         }
+        PageButtons(_)
+        | ExportButtons(_)
+        | PlaylistStatsButtons(_)
+        | SubscriptionStatsButtons(_)
+        | InactiveSubscriptionsButtons(_) => return internal_error(),
     };
     res.map_err(Sendable::SendError).merge_ok_err()
 }
 
-pub(crate) async fn construct_login_url(user_id: &str) -> eyre::Result<String> {
-    let auth_url = default_auth_url(user_id).await?.to_link("Log In");
-    format!("Use this link to log in {auth_url} \nPlease, log in and send your text again").in_ok()
+pub(crate) async fn construct_login_url(
+    user_id: &str,
+    chat_id: ChatId,
+    pending_command: CommandConfig,
+    app_config: &AppConfig,
+    token_store: &dyn TokenStore,
+) -> eyre::Result<String> {
+    let pending_command = pending_command.into();
+    let auth_url = default_auth_url(user_id, chat_id, pending_command, app_config, token_store)
+        .await?
+        .to_link("Log In");
+    format!(
+        "Use this link to log in {auth_url} \nYour request will continue right after you log in"
+    )
+    .in_ok()
+}
+
+/// Returns fresh access token, or a message with login url if user hasn't logged in yet.
+/// In the latter case `pending_command` is executed right after login, in chat `chat_id`.
+pub(crate) async fn access_token_or_login_msg(
+    user_id: &str,
+    chat_id: ChatId,
+    pending_command: CommandConfig,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> eyre::Result<StdRes<String, ResTriplet>> {
+    let Ok(token) = token_store.get_token(user_id).await else {
+        let login_msg =
+            construct_login_url(user_id, chat_id, pending_command, app_config, token_store).await?;
+        return Ok(Err((login_msg.into(), vec![], None)));
+    };
+    let access_token =
+        update_and_return_access_token(user_id, token, token_store, app_config).await?;
+    Ok(Ok(access_token))
 }
 
 pub(crate) async fn update_and_return_access_token(
     user_id: &str,
     token: YouTubeAccessToken,
-    db_url: &str,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
 ) -> eyre::Result<String> {
-    let secret = read_application_secret(env!("PATH_TO_GOOGLE_OAUTH_SECRET")).await?;
-    let token_req = build_refresh_access_token_req(secret, &token)?;
-    refresh_access_token(user_id, token, db_url, token_req)
+    let secret = read_application_secret(&app_config.path_to_google_oauth_secret).await?;
+    let token_req = build_refresh_access_token_req(&app_config.google_token_url, secret, &token)?;
+    refresh_access_token(user_id, token, token_store, token_req)
         .await?
         .access_token
         .in_ok()
@@ -0,0 +1,126 @@
+use error_traits::PassErrWith;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::dialogue::types::{CommandConfig, FindDuplicatesConfig};
+use crate::model::duplicates::funcs::{duplicates_to_items, find_duplicates, select_playlists};
+use crate::model::handlers::callback::common::{
+    access_token_or_login_msg, user_error_message, ResTriplet,
+};
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::common::{playlist_contents, playlist_ids_and_titles};
+use crate::StdRes;
+
+pub(crate) async fn execute_find_duplicates_command(
+    duplicates_config: FindDuplicatesConfig,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<ResTriplet, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_find_duplicates_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::FindDuplicatesConfig(duplicates_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            let only = duplicates_config.playlist.as_deref();
+            let concurrency = app_config.playlist_search_concurrency;
+            exec_find_duplicates_helper(only, &client, &access_token, concurrency).await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Final func that looks for duplicates when everything is ready.
+/// If `only` is set, duplicates are looked for only inside of that playlist.
+/// Playlists that can't be read are skipped, and aren't counted as checked.
+pub(crate) async fn exec_find_duplicates_helper(
+    only: Option<&str>,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<ResTriplet> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_find_duplicates_helper] :: [Started]");
+
+    let playlists = playlist_ids_and_titles(client, access_token).await?;
+    let playlists = select_playlists(playlists, only);
+    if playlists.is_empty() {
+        let msg = "There is no such playlist 🤷‍♂️ \nSend its title, id or link after the command";
+        return Ok((msg.to_owned().into(), vec![], None));
+    }
+    let contents = playlist_contents(client, access_token, playlists, concurrency).await?;
+    let playlist_count = contents.len();
+
+    let duplicates = find_duplicates(contents);
+    let video_count = duplicates.len();
+    let results = duplicates_to_items(duplicates);
+    let extra_copies = results.len() - video_count;
+    let postfix = format!(
+        "Finished! ✔ \nChecked {playlist_count} playlists, \
+        found {video_count} videos with {extra_copies} extra copies"
+    );
+    Ok((None, results, postfix.into()))
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn exec_find_duplicates_helper_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let (prefix, results, postfix) =
+            exec_find_duplicates_helper(None, client, MOCK_ACCESS_TOKEN, 2)
+                .await
+                .unwrap();
+        assert!(prefix.is_none());
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            postfix.unwrap(),
+            "Finished! ✔ \nChecked 6 playlists, found 2 videos with 2 extra copies"
+        );
+
+        let only = Some("обзоры");
+        let (_, results, postfix) = exec_find_duplicates_helper(only, client, MOCK_ACCESS_TOKEN, 2)
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+        assert!(postfix
+            .unwrap()
+            .contains("Checked 1 playlists, found 0 videos"));
+
+        let only = Some("Unknown");
+        let (prefix, results, postfix) =
+            exec_find_duplicates_helper(only, client, MOCK_ACCESS_TOKEN, 2)
+                .await
+                .unwrap();
+        assert!(prefix.unwrap().starts_with("There is no such playlist"));
+        assert!(results.is_empty() && postfix.is_none());
+    }
+}
@@ -0,0 +1,105 @@
+use error_traits::PassErrWith;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::dialogue::types::{CommandConfig, ExportConfig};
+use crate::model::export::funcs::build_document;
+use crate::model::export::types::ExportDocument;
+use crate::model::handlers::callback::common::{access_token_or_login_msg, user_error_message};
+use crate::model::keyboards::traits::{CreateKB, KeyboardText};
+use crate::model::keyboards::types::{ExportCommandButtons, ExportFormat, Requestable};
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::export_cmd::export_items;
+use crate::view::types::Sendable;
+use crate::StdRes;
+
+/// Helper function used for `handle_callback_data` handler.
+/// Each button holds everything needed, so dialogue isn't required.
+pub(crate) async fn callback_helper_for_export_kb(
+    export_kb: &ExportCommandButtons,
+) -> StdRes<Sendable<String>, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[callback_helper_for_export_kb] :: [Started]");
+
+    match export_kb {
+        ExportCommandButtons::Export(target, format) => {
+            let config = ExportConfig {
+                target: target.clone(),
+                format: *format,
+            };
+            Sendable::ExecuteCommand(CommandConfig::ExportConfig(config)).in_ok()
+        }
+        ExportCommandButtons::ButtonList => {
+            let kb = export_kb
+                .create_kb()
+                .ok_or_else(|| "⚠ Internal error ⚠".to_owned())?;
+            Sendable::SendKeyboard {
+                text: export_kb.kb_text(),
+                kb,
+                save_msg_id: false,
+                d_data: None,
+            }
+            .in_ok()
+        }
+    }
+}
+
+/// Returns a file to send, or a text (error or login link) to send instead.
+pub(crate) async fn execute_export_command(
+    export_config: ExportConfig,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<ExportDocument, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_export_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::ExportConfig(export_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            let (target, format) = (export_config.target, export_config.format);
+            exec_export_helper(target, format, &client, &access_token).await
+        }
+        Ok(Err((login_msg, ..))) => return Err(login_msg.unwrap_or_default()),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Final func that does exporting when everything is ready.
+pub(crate) async fn exec_export_helper(
+    requestable: Requestable,
+    format: ExportFormat,
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<ExportDocument> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_export_helper] :: [Started]");
+
+    let (records, name) = match requestable {
+        Requestable::Subscription(s) => (
+            export_items(client, s, access_token).await?,
+            "subscriptions",
+        ),
+        Requestable::Playlist(p) => (export_items(client, p, access_token).await?, "playlists"),
+        Requestable::LikedVideo(_) => eyre::bail!("Liked videos have no feed to export"),
+        Requestable::Uploads(_) => eyre::bail!("Uploads have no feed to export"),
+    };
+    build_document(&records, format, name)
+}
@@ -0,0 +1,146 @@
+use error_traits::PassErrWith;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::dialogue::types::{CommandConfig, InactiveSubscriptionsConfig};
+use crate::model::handlers::callback::common::{
+    access_token_or_login_msg, user_error_message, ResTriplet,
+};
+use crate::model::inactive::funcs::{channels_to_items, inactive_channels};
+use crate::model::keyboards::traits::{CreateKB, KeyboardText};
+use crate::model::keyboards::types::InactiveSubscriptionsCommandButtons;
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::inactive_cmd::subscriptions_activity;
+use crate::view::types::Sendable;
+use crate::StdRes;
+
+/// Helper function used for `handle_callback_data` handler.
+/// Each button holds everything needed, so dialogue isn't required.
+pub(crate) async fn callback_helper_for_inactive_subscriptions_kb(
+    inactive_kb: &InactiveSubscriptionsCommandButtons,
+) -> StdRes<Sendable<String>, String> {
+    log::info!(
+        " [:: LOG ::]     @[fn]:[callback_helper_for_inactive_subscriptions_kb] :: [Started]"
+    );
+
+    match inactive_kb {
+        InactiveSubscriptionsCommandButtons::Months(months) => {
+            let config = InactiveSubscriptionsConfig { months: *months };
+            Sendable::ExecuteCommand(CommandConfig::InactiveSubscriptionsConfig(config)).in_ok()
+        }
+        InactiveSubscriptionsCommandButtons::ButtonList => {
+            let kb = inactive_kb
+                .create_kb()
+                .ok_or_else(|| "⚠ Internal error ⚠".to_owned())?;
+            Sendable::SendKeyboard {
+                text: inactive_kb.kb_text(),
+                kb,
+                save_msg_id: false,
+                d_data: None,
+            }
+            .in_ok()
+        }
+    }
+}
+
+pub(crate) async fn execute_inactive_subscriptions_command(
+    inactive_config: InactiveSubscriptionsConfig,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<ResTriplet, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_inactive_subscriptions_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::InactiveSubscriptionsConfig(inactive_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            exec_inactive_subscriptions_helper(
+                inactive_config.months,
+                &client,
+                &access_token,
+                app_config.playlist_search_concurrency,
+                time::OffsetDateTime::now_utc().date(),
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Final func that looks for inactive channels when everything is ready.
+pub(crate) async fn exec_inactive_subscriptions_helper(
+    months: u8,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+    today: time::Date,
+) -> eyre::Result<ResTriplet> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_inactive_subscriptions_helper] :: [Started]");
+
+    let channels = subscriptions_activity(client, access_token, concurrency).await?;
+    let channel_count = channels.len();
+    let inactive = inactive_channels(channels, months, today);
+    let postfix = format!(
+        "Finished! ✔ \nChecked {channel_count} subscriptions, \
+        {} of them had no uploads in the last {months} months",
+        inactive.len()
+    );
+    Ok((None, channels_to_items(&inactive, today), postfix.into()))
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month};
+
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn exec_inactive_subscriptions_helper_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+        let today = Date::from_calendar_date(2023, Month::November, 9).unwrap();
+
+        let (prefix, results, postfix) =
+            exec_inactive_subscriptions_helper(3, client, MOCK_ACCESS_TOKEN, 2, today)
+                .await
+                .unwrap();
+        assert!(prefix.is_none());
+        let titles: Vec<_> = results.into_iter().map(|i| i.title.unwrap()).collect();
+        assert_eq!(titles, ["Dr. Plants", "iXBT games", "AWS Events"]);
+        assert_eq!(
+            postfix.unwrap(),
+            "Finished! ✔ \nChecked 3 subscriptions, 3 of them had no uploads in the last 3 months"
+        );
+
+        let (_, results, _) =
+            exec_inactive_subscriptions_helper(12, client, MOCK_ACCESS_TOKEN, 2, today)
+                .await
+                .unwrap();
+        assert_eq!(results.len(), 2);
+    }
+}
@@ -1,20 +1,28 @@
 use crate::dialogue::DialogueData;
-use crate::model::db::get_access_token;
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::funcs::list_settings_update_or_default;
 use crate::model::dialogue::types::State::ListCommandActive;
 use crate::model::dialogue::types::{CommandConfig, ListConfig};
 use crate::model::handlers::callback::common::{
-    construct_login_url, update_and_return_access_token, ResTriplet,
+    access_token_or_login_msg, no_channel_msg, user_error_message, ResTriplet,
 };
 use crate::model::keyboards::traits::{CreateKB, KeyboardText};
 use crate::model::keyboards::types::{ListCommandButtons, Requestable, Sorting};
-use crate::model::youtube::funcs::list_cmd::list_items;
+use crate::model::net::types::{PlaylistItemRequester, YouTubeClient};
+use crate::model::pagination::types::{ResultCache, LIST_FETCH_LIMIT};
+use crate::model::youtube::funcs::last_activity::add_last_activity;
+use crate::model::youtube::funcs::list_cmd::{list_items, sort_items};
+use crate::model::youtube::funcs::uploads::uploads_playlist_id;
+use crate::model::youtube::funcs::video_details::add_video_details;
+use crate::model::youtube::types::SearchableItem;
 use crate::view::types::Sendable;
 use crate::StdRes;
 use error_traits::PassErrWith;
 use maptypings::WrapInRes;
+use std::sync::Arc;
 use teloxide::prelude::CallbackQuery;
-use teloxide::types::User;
+use teloxide::types::{ChatId, UserId};
 
 /// Helper function used for `handle_callback_data` handler.
 pub(crate) async fn callback_helper_for_list_kb(
@@ -73,51 +81,208 @@ pub(crate) async fn callback_helper_for_list_kb(
 
 pub(crate) async fn execute_list_command(
     list_config: ListConfig,
-    callback: CallbackQuery,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+    result_cache: &ResultCache,
 ) -> StdRes<ResTriplet, String> {
-    log::info!(" [:: LOG ::]     @[fn]:[exec_search_helper] :: [Started]");
+    log::info!(" [:: LOG ::]     @[fn]:[execute_list_command] :: [Started]");
 
-    let err = |_| "⚠ Internal error ⚠".to_owned();
+    let err = |e| user_error_message(&e);
     let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
 
-    let (requestable, res_limit, sorting) = (
-        list_config.target,
-        list_config.result_limit,
-        list_config.sorting,
+    let pending_command = CommandConfig::ListConfig(list_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
     );
-
-    let res = exec_list_helper(callback.from, res_limit, &sorting, requestable).await;
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            exec_list_helper(
+                &list_config,
+                &client,
+                &access_token,
+                app_config.playlist_search_concurrency,
+                &user_id,
+                result_cache,
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
     res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
         .map_err(err)
 }
 
-/// Helper function used for `handle_text` handler.
-/// Final func that does searching when everything is ready.
+/// Id user's full list of `requestable` is cached under.
+/// Last activity takes extra requests, so list with it is cached separately.
+fn list_cache_id(user_id: &str, requestable: &Requestable, sorting: &Sorting) -> String {
+    let target = match requestable {
+        Requestable::Subscription(_) => "subscriptions",
+        Requestable::Playlist(_) => "playlists",
+        Requestable::LikedVideo(_) => "liked",
+        Requestable::Uploads(_) => "uploads",
+    };
+    let activity = match sorting {
+        Sorting::LastActivity(_) => "_activity",
+        _ => "",
+    };
+    format!("{user_id}_{target}{activity}")
+}
+
+/// Final func that does listing when everything is ready.
+/// Sorting applies to the whole collection, so all of it is fetched, or taken from
+/// `result_cache`, and only then cut to the result limit.
 pub(crate) async fn exec_list_helper(
-    user_id: User,
-    res_limit: u32,
-    sorting: &Sorting,
-    requestable: Requestable,
+    list_config: &ListConfig,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+    user_id: &str,
+    result_cache: &ResultCache,
 ) -> eyre::Result<ResTriplet> {
-    log::info!(" [:: LOG ::]     @[fn]:[execute_search_command] :: [Started]");
-
-    let user_id = user_id.id.0.to_string();
-    let db_url = env!("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE");
+    log::info!(" [:: LOG ::]     @[fn]:[exec_list_helper] :: [Started]");
 
-    let Ok(token) = get_access_token(&user_id, db_url) else {
-        return (construct_login_url(&user_id).await?.into(), vec![], None).in_ok();
+    let (requestable, sorting) = (&list_config.target, &list_config.sorting);
+    let cache_id = list_cache_id(user_id, requestable, sorting);
+    let mut results = match result_cache.get_list(&cache_id).await {
+        Some(items) => items.as_ref().clone(),
+        None => {
+            let Some(mut items) = list_all(requestable, client, access_token).await? else {
+                return Ok(no_channel_msg());
+            };
+            if let Sorting::LastActivity(_) = sorting {
+                add_last_activity(client, access_token, &mut items, concurrency).await?;
+            }
+            result_cache
+                .save_list(&cache_id, Arc::new(items.clone()))
+                .await;
+            items
+        }
     };
+    let total_count = results.len();
+    sort_items(&mut results, sorting);
+    results.truncate(list_config.result_limit as usize);
+    add_video_details(client, access_token, &mut results).await?;
 
-    let access_token = update_and_return_access_token(&user_id, token, db_url).await?;
+    let postfix = list_postfix(results.len(), total_count);
+    Ok((None, results, postfix.into()))
+}
+
+/// Tells how many results are shown, and whether the collection was cut at `LIST_FETCH_LIMIT`.
+fn list_postfix(result_count: usize, total_count: usize) -> String {
+    if total_count < LIST_FETCH_LIMIT as usize {
+        return format!("Finished! ✔ \nShowing {result_count} of {total_count} results");
+    }
+    format!(
+        "Finished! ✔ \nShowing {result_count} of at least {total_count} results \n\
+        Only the first {LIST_FETCH_LIMIT} items were fetched and sorted"
+    )
+}
 
-    let results = match requestable {
-        Requestable::Subscription(s) => list_items(s, &access_token, sorting, res_limit).await,
-        Requestable::Playlist(p) => list_items(p, &access_token, sorting, res_limit).await,
+/// All items of `requestable`, `None` if user has no channel to take uploads from.
+async fn list_all(
+    requestable: &Requestable,
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<Option<Vec<SearchableItem>>> {
+    let limit = LIST_FETCH_LIMIT;
+    let items = match requestable {
+        Requestable::Subscription(s) => list_items(client, s.clone(), access_token, limit).await?,
+        Requestable::Playlist(p) => list_items(client, p.clone(), access_token, limit).await?,
+        Requestable::LikedVideo(v) => list_items(client, v.clone(), access_token, limit).await?,
+        Requestable::Uploads(_) => {
+            let Some(playlist_id) = uploads_playlist_id(client, access_token).await? else {
+                return Ok(None);
+            };
+            let req_builder = PlaylistItemRequester {
+                playlist_id: &playlist_id,
+            };
+            list_items(client, req_builder, access_token, limit).await?
+        }
     };
-    let result_count = results.len();
-    let (prefix, postfix) = (
-        None::<String>,
-        format!("Finished! ✔ \nFound {result_count} results").into(),
-    );
-    Ok((prefix, results, postfix))
+    Ok(Some(items))
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::keyboards::types::SortOrder;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::PlaylistRequester;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn exec_list_helper_test() {
+        let mock_api = MockYouTubeApi::start();
+        let (client, result_cache) = (&mock_api.client, ResultCache::default());
+        let list = |result_limit, sorting| {
+            let list_config = ListConfig {
+                target: Requestable::Playlist(PlaylistRequester),
+                result_limit,
+                sorting,
+            };
+            let result_cache = &result_cache;
+            async move {
+                exec_list_helper(
+                    &list_config,
+                    client,
+                    MOCK_ACCESS_TOKEN,
+                    2,
+                    "42",
+                    result_cache,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let titles = |results: &[SearchableItem]| {
+            results
+                .iter()
+                .map(|i| i.title.clone().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Oldest playlist is on the last page, but it's still found.
+        let (_, results, postfix) = list(2, Sorting::Date(SortOrder::Ascending)).await;
+        assert_eq!(titles(&results), ["Favorites", "Обзоры"]);
+        assert_eq!(postfix.unwrap(), "Finished! ✔ \nShowing 2 of 6 results");
+        let requests = mock_api.api_requests();
+
+        // Full list is taken from cache for another sorting.
+        let (_, results, _) = list(3, Sorting::Alphabetical(SortOrder::Descending)).await;
+        assert_eq!(titles(&results), ["Трансляции", "Обзоры", "Истории"]);
+        assert_eq!(mock_api.api_requests(), requests);
+
+        // Full list can't be reached by a page id.
+        let sorting = Sorting::Alphabetical(SortOrder::Descending);
+        let cache_id = list_cache_id("42", &Requestable::Playlist(PlaylistRequester), &sorting);
+        assert!(result_cache.get_list(&cache_id).await.is_some());
+        assert!(result_cache.get(&cache_id, UserId(42)).await.is_none());
+    }
+
+    #[test]
+    fn list_postfix_test() {
+        assert_eq!(list_postfix(2, 6), "Finished! ✔ \nShowing 2 of 6 results");
+        let limit = LIST_FETCH_LIMIT as usize;
+        assert_eq!(
+            list_postfix(50, limit),
+            format!(
+                "Finished! ✔ \nShowing 50 of at least {limit} results \n\
+                Only the first {limit} items were fetched and sorted"
+            )
+        );
+    }
 }
@@ -0,0 +1,111 @@
+use maptypings::WrapInRes;
+use teloxide::prelude::CallbackQuery;
+use teloxide::types::InlineKeyboardMarkup;
+
+use crate::model::keyboards::types::PageButtons;
+use crate::model::pagination::funcs::{page_kb, render_page};
+use crate::model::pagination::types::ResultCache;
+use crate::view::types::Sendable;
+use crate::StdRes;
+
+/// Helper function used for `handle_callback_data` handler.
+/// Doesn't need dialogue data, results are taken from `ResultCache`.
+/// Results of another user are treated as expired, so they can't be browsed or closed.
+pub(crate) async fn callback_helper_for_page_kb(
+    page_kb_buttons: &PageButtons,
+    callback: CallbackQuery,
+    result_cache: &ResultCache,
+) -> StdRes<Sendable<String>, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[callback_helper_for_page_kb] :: [Started]");
+
+    let expired = "These results have expired ⌛ \nPlease, run the command again";
+    let internal_error = || "⚠ Internal error ⚠".to_owned();
+    let user_id = callback.from.id;
+    match page_kb_buttons {
+        PageButtons::Page { id, page } => {
+            let Some(results) = result_cache.get(id, user_id).await else {
+                return Err(expired.to_owned());
+            };
+            let msg = callback.message.ok_or_else(internal_error)?;
+            let (text, kb) = (
+                render_page(&results, *page),
+                page_kb(id, *page, results.len()),
+            );
+            Sendable::EditKeyboard(text.into(), kb, msg, None).in_ok()
+        }
+        PageButtons::Close { id } => {
+            if !result_cache.remove(id, user_id).await {
+                return Err(expired.to_owned());
+            }
+            let msg = callback.message.ok_or_else(internal_error)?;
+            Sendable::EditKeyboard(None, InlineKeyboardMarkup::default(), msg, None).in_ok()
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use teloxide::types::{User, UserId};
+
+    use crate::model::pagination::types::RESULTS_TTL;
+    use crate::model::youtube::types::SearchableItem;
+
+    use super::*;
+
+    fn create_callback(user_id: UserId) -> CallbackQuery {
+        let from = User {
+            id: user_id,
+            is_bot: false,
+            first_name: "hgjggo".to_owned(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            is_premium: false,
+            added_to_attachment_menu: false,
+        };
+        let (message, inline_message_id, data, game_short_name) = Default::default();
+        CallbackQuery {
+            id: 87568758.to_string(),
+            from,
+            message,
+            inline_message_id,
+            chat_instance: 76876959.to_string(),
+            data,
+            game_short_name,
+        }
+    }
+
+    #[tokio::test]
+    async fn results_of_another_user_test() {
+        let result_cache = ResultCache::default();
+        let (owner, stranger) = (UserId(1), UserId(2));
+        let results = vec![SearchableItem::default()];
+        let id = result_cache.save(owner, results, RESULTS_TTL).await;
+
+        let buttons = [
+            PageButtons::Page {
+                id: id.clone(),
+                page: 0,
+            },
+            PageButtons::Close { id: id.clone() },
+        ];
+        for page_kb_buttons in &buttons {
+            let res = callback_helper_for_page_kb(
+                page_kb_buttons,
+                create_callback(stranger),
+                &result_cache,
+            )
+            .await;
+            assert!(matches!(res, Err(e) if e.contains("expired")));
+        }
+        assert!(result_cache.get(&id, owner).await.is_some());
+
+        // Owner gets past the check, here only because callback has no message.
+        let res =
+            callback_helper_for_page_kb(&buttons[1], create_callback(owner), &result_cache).await;
+        assert!(matches!(res, Err(e) if e.contains("Internal error")));
+        assert!(result_cache.get(&id, owner).await.is_none());
+    }
+}
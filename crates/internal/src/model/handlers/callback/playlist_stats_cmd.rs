@@ -0,0 +1,110 @@
+use error_traits::PassErrWith;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::dialogue::types::{CommandConfig, PlaylistStatsConfig};
+use crate::model::export::types::ExportDocument;
+use crate::model::handlers::callback::common::{
+    access_token_or_login_msg, user_error_message, ResTriplet,
+};
+use crate::model::keyboards::traits::{CreateKB, KeyboardText};
+use crate::model::keyboards::types::{PlaylistStatsCommandButtons, StatsFormat};
+use crate::model::net::types::YouTubeClient;
+use crate::model::playlist_stats::funcs::{
+    build_stats_document, sort_stats, stats_summary, stats_to_items,
+};
+use crate::model::youtube::funcs::playlist_stats_cmd::collect_playlist_stats;
+use crate::view::types::Sendable;
+use crate::StdRes;
+
+/// Stats as results to browse, or as a file to send.
+pub(crate) enum StatsReport {
+    Table(ResTriplet),
+    Document(ExportDocument),
+}
+
+/// Helper function used for `handle_callback_data` handler.
+/// Each button holds everything needed, so dialogue isn't required.
+pub(crate) async fn callback_helper_for_playlist_stats_kb(
+    stats_kb: &PlaylistStatsCommandButtons,
+) -> StdRes<Sendable<String>, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[callback_helper_for_playlist_stats_kb] :: [Started]");
+
+    match stats_kb {
+        PlaylistStatsCommandButtons::Stats(format) => {
+            let config = PlaylistStatsConfig { format: *format };
+            Sendable::ExecuteCommand(CommandConfig::PlaylistStatsConfig(config)).in_ok()
+        }
+        PlaylistStatsCommandButtons::ButtonList => {
+            let kb = stats_kb
+                .create_kb()
+                .ok_or_else(|| "⚠ Internal error ⚠".to_owned())?;
+            Sendable::SendKeyboard {
+                text: stats_kb.kb_text(),
+                kb,
+                save_msg_id: false,
+                d_data: None,
+            }
+            .in_ok()
+        }
+    }
+}
+
+pub(crate) async fn execute_playlist_stats_command(
+    stats_config: PlaylistStatsConfig,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<StatsReport, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_playlist_stats_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::PlaylistStatsConfig(stats_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            let concurrency = app_config.playlist_search_concurrency;
+            exec_playlist_stats_helper(stats_config.format, &client, &access_token, concurrency)
+                .await
+        }
+        Ok(Err(login_msg)) => StatsReport::Table(login_msg).in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Final func that counts stats when everything is ready.
+pub(crate) async fn exec_playlist_stats_helper(
+    format: StatsFormat,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<StatsReport> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_playlist_stats_helper] :: [Started]");
+
+    let mut stats = collect_playlist_stats(client, access_token, concurrency).await?;
+    let sorting = match format {
+        StatsFormat::Csv => return StatsReport::Document(build_stats_document(&stats)).in_ok(),
+        StatsFormat::Table(sorting) => sorting,
+    };
+    sort_stats(&mut stats, sorting);
+    let postfix = format!("Finished! ✔ \n{}", stats_summary(&stats));
+    StatsReport::Table((None, stats_to_items(&stats), postfix.into())).in_ok()
+}
@@ -1,20 +1,25 @@
 use crate::dialogue::DialogueData;
-use crate::model::db::get_access_token;
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::funcs::search_settings_update_or_default;
 use crate::model::dialogue::types::State::SearchCommandActive;
 use crate::model::dialogue::types::{CommandConfig, SearchConfig};
 use crate::model::handlers::callback::common::{
-    construct_login_url, update_and_return_access_token, ResTriplet,
+    access_token_or_login_msg, invalid_regex_msg, no_channel_msg, user_error_message, ResTriplet,
 };
 use crate::model::keyboards::traits::{CreateKB, KeyboardText};
 use crate::model::keyboards::types::{Requestable, SearchCommandButtons, SearchIn};
+use crate::model::matching::types::Matcher;
+use crate::model::net::types::{PlaylistItemRequester, YouTubeClient};
 use crate::model::youtube::funcs::search_cmd::search_items;
+use crate::model::youtube::funcs::uploads::uploads_playlist_id;
+use crate::model::youtube::funcs::video_details::add_video_details;
 use crate::view::types::Sendable;
 use crate::StdRes;
 use error_traits::PassErrWith;
 use maptypings::WrapInRes;
 use teloxide::prelude::CallbackQuery;
-use teloxide::types::User;
+use teloxide::types::{ChatId, UserId};
 
 /// Helper function used for `handle_callback_data` handler.
 pub(crate) async fn callback_helper_for_search_kb(
@@ -39,6 +44,14 @@ pub(crate) async fn callback_helper_for_search_kb(
                 ..dialogue_data
             })
         }
+        (SearchCommandButtons::MatchMode(match_mode), _) => {
+            let mut search_settings = search_settings_update_or_default(dialogue_data.state);
+            search_settings.update_match_mode(*match_mode);
+            Some(DialogueData {
+                state: SearchCommandActive(search_settings),
+                ..dialogue_data
+            })
+        }
         (SearchCommandButtons::Target(target), _) => {
             let mut search_settings = search_settings_update_or_default(dialogue_data.state);
             search_settings.update_target(target.clone());
@@ -75,60 +88,98 @@ pub(crate) async fn callback_helper_for_search_kb(
 
 pub(crate) async fn execute_search_command(
     search_config: SearchConfig,
-    callback: CallbackQuery,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
 ) -> StdRes<ResTriplet, String> {
-    log::info!(" [:: LOG ::]     @[fn]:[exec_search_helper] :: [Started]");
+    log::info!(" [:: LOG ::]     @[fn]:[execute_search_command] :: [Started]");
 
-    let err = |_| "⚠ Internal error ⚠".to_owned();
+    let err = |e| user_error_message(&e);
     let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
 
-    let (requestable, search_for, res_limit, search_in) = (
+    let pending_command = CommandConfig::SearchConfig(search_config.clone());
+    let (requestable, res_limit, search_in) = (
         search_config.target,
-        search_config.text_to_search,
         search_config.result_limit,
         search_config.search_in,
     );
+    let matcher = match Matcher::new(search_config.match_mode, &search_config.text_to_search) {
+        Ok(matcher) => matcher,
+        Err(e) => return invalid_regex_msg(&e).in_ok(),
+    };
 
-    let res = exec_search_helper(
-        callback.from,
-        &search_for,
-        res_limit,
-        &search_in,
-        requestable,
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
     )
-    .await;
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            exec_search_helper(
+                &matcher,
+                res_limit,
+                &search_in,
+                requestable,
+                &client,
+                &access_token,
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
     res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
         .map_err(err)
 }
 
-/// Helper function used for `handle_text` handler.
 /// Final func that does searching when everything is ready.
 pub(crate) async fn exec_search_helper(
-    user_id: User,
-    search_for: &str,
+    matcher: &Matcher,
     res_limit: u32,
     search_in: &SearchIn,
     requestable: Requestable,
+    client: &YouTubeClient,
+    access_token: &str,
 ) -> eyre::Result<ResTriplet> {
-    log::info!(" [:: LOG ::]     @[fn]:[execute_search_command] :: [Started]");
-
-    let user_id = user_id.id.0.to_string();
-    let db_url = env!("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE");
-
-    let Ok(token) = get_access_token(&user_id, db_url) else {
-        return (construct_login_url(&user_id).await?.into(), vec![], None).in_ok();
-    };
-
-    let access_token = update_and_return_access_token(&user_id, token, db_url).await?;
+    log::info!(" [:: LOG ::]     @[fn]:[exec_search_helper] :: [Started]");
 
-    let results = match requestable {
+    let mut results = match requestable {
         Requestable::Subscription(s) => {
-            search_items(search_in, s, search_for, &access_token, res_limit).await
+            search_items(client, search_in, s, matcher, access_token, res_limit).await?
         }
         Requestable::Playlist(p) => {
-            search_items(search_in, p, search_for, &access_token, res_limit).await
+            search_items(client, search_in, p, matcher, access_token, res_limit).await?
+        }
+        Requestable::LikedVideo(v) => {
+            search_items(client, search_in, v, matcher, access_token, res_limit).await?
+        }
+        Requestable::Uploads(_) => {
+            let Some(playlist_id) = uploads_playlist_id(client, access_token).await? else {
+                return Ok(no_channel_msg());
+            };
+            let req_builder = PlaylistItemRequester {
+                playlist_id: &playlist_id,
+            };
+            search_items(
+                client,
+                search_in,
+                req_builder,
+                matcher,
+                access_token,
+                res_limit,
+            )
+            .await?
         }
     };
+    add_video_details(client, access_token, &mut results).await?;
     let result_count = results.len();
     let (prefix, postfix) = (
         None::<String>,
@@ -145,7 +196,7 @@ mod tests {
     use crate::model::dialogue::types::{SearchCommandSettings, State};
     use crate::model::net::types::SubscriptionRequester;
     use std::default::Default;
-    use teloxide::types::UserId;
+    use teloxide::types::User;
 
     fn create_user() -> User {
         let (id, first_name) = (UserId(8587687687), "hgjggo".to_owned());
@@ -1,20 +1,26 @@
 use crate::dialogue::DialogueData;
-use crate::model::db::get_access_token;
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::funcs::search_videos_in_playlists_update_or_default;
 use crate::model::dialogue::types::State::SearchVideosInPlaylistsCommandActive;
 use crate::model::dialogue::types::{CommandConfig, SearchVideosInPlaylistsConfig};
 use crate::model::handlers::callback::common::{
-    construct_login_url, update_and_return_access_token, ResTriplet,
+    access_token_or_login_msg, invalid_regex_msg, user_error_message, ResTriplet,
 };
 use crate::model::keyboards::traits::{CreateKB, KeyboardText};
 use crate::model::keyboards::types::{SearchIn, SearchVideoInPlaylistsCommandButtons};
+use crate::model::matching::types::Matcher;
+use crate::model::net::types::YouTubeClient;
 use crate::model::youtube::funcs::search_videos_in_playlists::search_videos_in_playlists;
+use crate::model::youtube::types::PlaylistScan;
 use crate::view::types::Sendable;
 use crate::StdRes;
 use error_traits::PassErrWith;
+use futures::{Stream, StreamExt};
 use maptypings::WrapInRes;
 use teloxide::prelude::CallbackQuery;
-use teloxide::types::User;
+use teloxide::types::{ChatId, UserId};
+use tokio::sync::mpsc::UnboundedSender;
 
 /// Helper function used for `handle_callback_data` handler.
 pub(crate) async fn callback_helper_for_search_videos_in_playlists_kb(
@@ -40,6 +46,15 @@ pub(crate) async fn callback_helper_for_search_videos_in_playlists_kb(
                 ..dialogue_data
             })
         }
+        (SearchVideoInPlaylistsCommandButtons::MatchMode(match_mode), _) => {
+            let mut search_videos_in_playlists_settings =
+                search_videos_in_playlists_update_or_default(dialogue_data.state);
+            search_videos_in_playlists_settings.update_match_mode(*match_mode);
+            Some(DialogueData {
+                state: SearchVideosInPlaylistsCommandActive(search_videos_in_playlists_settings),
+                ..dialogue_data
+            })
+        }
         (ResultLimit | TextToSearch, _) => {
             let search_videos_in_playlists_settings =
                 search_videos_in_playlists_update_or_default(dialogue_data.state);
@@ -67,53 +82,178 @@ pub(crate) async fn callback_helper_for_search_videos_in_playlists_kb(
     }
 }
 
+/// Matches are sent to `progress` as soon as they're found, and are collected into pages there,
+/// so returned results are empty.
 pub(crate) async fn execute_search_videos_in_playlists_command(
     search_config: SearchVideosInPlaylistsConfig,
-    callback: CallbackQuery,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+    progress: UnboundedSender<PlaylistScan>,
 ) -> StdRes<ResTriplet, String> {
-    log::info!(" [:: LOG ::]     @[fn]:[exec_search_helper] :: [Started]");
+    log::info!(" [:: LOG ::]     @[fn]:[execute_search_videos_in_playlists_command] :: [Started]");
 
-    let err = |_| "⚠ Internal error ⚠".to_owned();
+    let err = |e| user_error_message(&e);
     let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
 
-    let (search_for, res_limit, search_in) = (
-        search_config.text_to_search,
-        search_config.result_limit,
-        search_config.search_in,
-    );
+    let pending_command = CommandConfig::SearchVideosInPlaylistsConfig(search_config.clone());
+    let (res_limit, search_in) = (search_config.result_limit, search_config.search_in);
+    let matcher = match Matcher::new(search_config.match_mode, &search_config.text_to_search) {
+        Ok(matcher) => matcher,
+        Err(e) => return invalid_regex_msg(&e).in_ok(),
+    };
 
-    let res =
-        exec_search_videos_in_playlists_helper(callback.from, &search_for, res_limit, &search_in)
-            .await;
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            exec_search_videos_in_playlists_helper(
+                &matcher,
+                res_limit,
+                &search_in,
+                &client,
+                &access_token,
+                app_config.playlist_search_concurrency,
+                &progress,
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
     res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
         .map_err(err)
 }
 
-/// Helper function used for `handle_text` handler.
 /// Final func that does searching when everything is ready.
 pub(crate) async fn exec_search_videos_in_playlists_helper(
-    user_id: User,
-    search_for: &str,
+    matcher: &Matcher,
     res_limit: u32,
     search_in: &SearchIn,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+    progress: &UnboundedSender<PlaylistScan>,
 ) -> eyre::Result<ResTriplet> {
-    log::info!(" [:: LOG ::]     @[fn]:[execute_search_command] :: [Started]");
+    log::info!(" [:: LOG ::]     @[fn]:[exec_search_videos_in_playlists_helper] :: [Started]");
 
-    let user_id = user_id.id.0.to_string();
-    let db_url = env!("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE");
+    let scans =
+        search_videos_in_playlists(client, search_in, matcher, access_token, concurrency).await?;
+    let result_count = forward_matches(scans, res_limit, progress).await?;
+    let postfix = format!("Finished! ✔ \nFound {result_count} results").into();
+    Ok((None, vec![], postfix))
+}
 
-    let Ok(token) = get_access_token(&user_id, db_url) else {
-        return (construct_login_url(&user_id).await?.into(), vec![], None).in_ok();
-    };
+/// Forwards matches to `progress` until `res_limit` of them are found, returns their count.
+/// Stops polling `scans` right after that, so remaining playlists are never scanned.
+/// Errors of single playlists are already skipped in `scans`, ones left here are fatal.
+async fn forward_matches(
+    scans: impl Stream<Item = eyre::Result<PlaylistScan>>,
+    res_limit: u32,
+    progress: &UnboundedSender<PlaylistScan>,
+) -> eyre::Result<usize> {
+    let res_limit = res_limit as usize;
+    let mut result_count = 0;
+    let mut scans = std::pin::pin!(scans);
+    while let Some(scan) = scans.next().await {
+        let mut scan = scan?;
+        scan.matches.truncate(res_limit - result_count);
+        result_count += scan.matches.len();
+        progress.send(scan)?;
+        if result_count >= res_limit {
+            break;
+        }
+    }
+    Ok(result_count)
+}
 
-    // let access_token = token.access_token;
-    let access_token = update_and_return_access_token(&user_id, token, db_url).await?;
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-    let results = search_videos_in_playlists(search_in, search_for, &access_token, res_limit).await;
-    let result_count = results.len();
-    let (prefix, postfix) = (
-        None::<String>,
-        format!("Finished! ✔ \nFound {result_count} results").into(),
-    );
-    Ok((prefix, results, postfix))
+    use futures::stream;
+    use tokio::sync::mpsc;
+
+    use crate::model::youtube::types::SearchableItem;
+
+    use super::*;
+
+    fn scans(matches_per_playlist: &[usize]) -> Vec<eyre::Result<PlaylistScan>> {
+        let total = matches_per_playlist.len();
+        matches_per_playlist
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| {
+                Ok(PlaylistScan {
+                    scanned: i + 1,
+                    total,
+                    matches: vec![(1.0, SearchableItem::default()); n],
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn stops_after_result_limit_test() {
+        let polled = AtomicUsize::new(0);
+        let scans = stream::iter(scans(&[1, 0, 3, 2, 5])).inspect(|_| {
+            polled.fetch_add(1, Ordering::SeqCst);
+        });
+        let (progress, mut received) = mpsc::unbounded_channel();
+
+        let result_count = forward_matches(scans, 3, &progress).await.unwrap();
+        drop(progress);
+
+        assert_eq!(result_count, 3);
+        assert_eq!(polled.load(Ordering::SeqCst), 3);
+
+        let mut sent = vec![];
+        while let Some(scan) = received.recv().await {
+            sent.push((scan.scanned, scan.matches.len()));
+        }
+        assert_eq!(sent, [(1, 1), (2, 0), (3, 2)]);
+    }
+
+    #[tokio::test]
+    async fn scans_everything_below_result_limit_test() {
+        let (progress, mut received) = mpsc::unbounded_channel();
+        let scans = stream::iter(scans(&[1, 0, 2]));
+        let result_count = forward_matches(scans, 10, &progress).await.unwrap();
+        drop(progress);
+
+        assert_eq!(result_count, 3);
+        let mut last_scanned = 0;
+        while let Some(scan) = received.recv().await {
+            last_scanned = scan.scanned;
+        }
+        assert_eq!(last_scanned, 3);
+    }
+
+    #[tokio::test]
+    async fn stops_on_error_test() {
+        let mut scans = scans(&[1, 1, 1]);
+        scans[1] = Err(eyre::eyre!("quota exceeded"));
+        let (progress, mut received) = mpsc::unbounded_channel();
+
+        assert!(forward_matches(stream::iter(scans), 10, &progress)
+            .await
+            .is_err());
+        drop(progress);
+
+        assert_eq!(received.recv().await.unwrap().scanned, 1);
+        assert!(received.recv().await.is_none());
+    }
 }
@@ -0,0 +1,186 @@
+use error_traits::PassErrWith;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::{SnapshotStore, TokenStore};
+use crate::model::dialogue::types::CommandConfig;
+use crate::model::handlers::callback::common::{
+    access_token_or_login_msg, user_error_message, ResTriplet,
+};
+use crate::model::net::types::YouTubeClient;
+use crate::model::snapshot::funcs::{diff_snapshot, diff_to_items, update_snapshots};
+use crate::model::youtube::funcs::snapshot_cmd::take_playlist_snapshots;
+use crate::StdRes;
+
+pub(crate) async fn execute_snapshot_playlists_command(
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    snapshot_store: &dyn SnapshotStore,
+    app_config: &AppConfig,
+) -> StdRes<ResTriplet, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_snapshot_playlists_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::SnapshotPlaylists;
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            let concurrency = app_config.playlist_search_concurrency;
+            exec_snapshot_playlists_helper(
+                &user_id,
+                snapshot_store,
+                &client,
+                &access_token,
+                concurrency,
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+pub(crate) async fn execute_playlist_changes_command(
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    snapshot_store: &dyn SnapshotStore,
+    app_config: &AppConfig,
+) -> StdRes<ResTriplet, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_playlist_changes_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::PlaylistChanges;
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            let concurrency = app_config.playlist_search_concurrency;
+            exec_playlist_changes_helper(
+                &user_id,
+                snapshot_store,
+                &client,
+                &access_token,
+                concurrency,
+            )
+            .await
+        }
+        Ok(Err(login_msg)) => login_msg.in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Saves current state of all playlists.
+/// Titles of videos that disappeared since the last snapshot are kept,
+/// as well as old snapshots of playlists that are gone or can't be read.
+pub(crate) async fn exec_snapshot_playlists_helper(
+    user_id: &str,
+    snapshot_store: &dyn SnapshotStore,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<ResTriplet> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_snapshot_playlists_helper] :: [Started]");
+
+    let old_snapshots = snapshot_store.get_playlist_snapshots(user_id).await?;
+    let fresh = take_playlist_snapshots(client, access_token, concurrency)
+        .await?
+        .snapshots;
+    let (playlist_count, video_count) = (
+        fresh.len(),
+        fresh.values().map(|s| s.items.len()).sum::<usize>(),
+    );
+    let snapshots = update_snapshots(old_snapshots, fresh);
+    snapshot_store
+        .set_playlist_snapshots(user_id, &snapshots)
+        .await?;
+
+    let kept = match snapshots.len() - playlist_count {
+        0 => String::new(),
+        n => format!("\nKept old snapshots of {n} playlists that are gone or can't be read"),
+    };
+    let postfix = format!(
+        "Saved snapshots of {playlist_count} playlists ({video_count} videos) 📸 {kept}\n\
+        Use /playlist_changes later to see which videos disappeared"
+    );
+    Ok((None, vec![], postfix.into()))
+}
+
+/// Compares current playlists with the last snapshot.
+/// Playlists that can't be read right now are skipped, rather than reported as gone.
+pub(crate) async fn exec_playlist_changes_helper(
+    user_id: &str,
+    snapshot_store: &dyn SnapshotStore,
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<ResTriplet> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_playlist_changes_helper] :: [Started]");
+
+    let old_snapshots = snapshot_store.get_playlist_snapshots(user_id).await?;
+    let Some(taken_at) = old_snapshots.values().map(|s| s.taken_at).min() else {
+        let msg = "There are no snapshots yet 🤷‍♂️ \nUse /snapshot_playlists to take one";
+        return Ok((msg.to_owned().into(), vec![], None));
+    };
+
+    let current = take_playlist_snapshots(client, access_token, concurrency).await?;
+    let (current_snapshots, unreadable) = (current.snapshots, current.unreadable);
+    let mut old_snapshots: Vec<_> = old_snapshots.into_values().collect();
+    old_snapshots.sort_by(|a, b| a.title.cmp(&b.title));
+
+    let results: Vec<_> = old_snapshots
+        .iter()
+        .filter(|old| !unreadable.contains(&old.playlist_id))
+        .flat_map(|old| {
+            // Whole playlist is gone, so are all of its videos.
+            let current = current_snapshots
+                .get(&old.playlist_id)
+                .map(|s| s.items.as_slice())
+                .unwrap_or_default();
+            diff_to_items(&old.title, diff_snapshot(old, current))
+        })
+        .collect();
+
+    let result_count = results.len();
+    let prefix = format!("Changes since snapshot from {} 🔍", taken_at.date());
+    let skipped = match unreadable.len() {
+        0 => String::new(),
+        n => format!("\nSkipped {n} playlists that can't be read right now"),
+    };
+    let postfix = format!(
+        "Finished! ✔ \nFound {result_count} vanished videos {skipped}\n\
+        Use /snapshot_playlists to save current state"
+    );
+    Ok((prefix.into(), results, postfix.into()))
+}
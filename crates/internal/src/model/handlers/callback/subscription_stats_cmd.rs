@@ -0,0 +1,140 @@
+use error_traits::PassErrWith;
+use futures::TryStreamExt;
+use maptypings::WrapInRes;
+use teloxide::types::{ChatId, UserId};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::dialogue::types::{CommandConfig, SubscriptionStatsConfig};
+use crate::model::handlers::callback::common::{access_token_or_login_msg, user_error_message};
+use crate::model::handlers::callback::playlist_stats_cmd::StatsReport;
+use crate::model::keyboards::traits::{CreateKB, KeyboardText};
+use crate::model::keyboards::types::{SubscriptionStatsCommandButtons, SubscriptionStatsFormat};
+use crate::model::net::types::{SubscriptionRequester, YouTubeClient};
+use crate::model::subscription_stats::funcs::{
+    build_subscription_stats_document, stats_report, subscription_stats,
+};
+use crate::model::youtube::funcs::common::item_stream;
+use crate::view::types::Sendable;
+use crate::StdRes;
+
+/// Helper function used for `handle_callback_data` handler.
+/// Each button holds everything needed, so dialogue isn't required.
+pub(crate) async fn callback_helper_for_subscription_stats_kb(
+    stats_kb: &SubscriptionStatsCommandButtons,
+) -> StdRes<Sendable<String>, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[callback_helper_for_subscription_stats_kb] :: [Started]");
+
+    match stats_kb {
+        SubscriptionStatsCommandButtons::Stats(format) => {
+            let config = SubscriptionStatsConfig { format: *format };
+            Sendable::ExecuteCommand(CommandConfig::SubscriptionStatsConfig(config)).in_ok()
+        }
+        SubscriptionStatsCommandButtons::ButtonList => {
+            let kb = stats_kb
+                .create_kb()
+                .ok_or_else(|| "⚠ Internal error ⚠".to_owned())?;
+            Sendable::SendKeyboard {
+                text: stats_kb.kb_text(),
+                kb,
+                save_msg_id: false,
+                d_data: None,
+            }
+            .in_ok()
+        }
+    }
+}
+
+pub(crate) async fn execute_subscription_stats_command(
+    stats_config: SubscriptionStatsConfig,
+    user_id: UserId,
+    chat_id: ChatId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+) -> StdRes<StatsReport, String> {
+    log::info!(" [:: LOG ::]     @[fn]:[execute_subscription_stats_command] :: [Started]");
+
+    let err = |e| user_error_message(&e);
+    let log_prefix = " [:: LOG ::]  :  @fn:[dialogue::callback_handling]  ->  error: ";
+
+    let pending_command = CommandConfig::SubscriptionStatsConfig(stats_config.clone());
+    let (user_id, client) = (
+        user_id.0.to_string(),
+        YouTubeClient::from_config(app_config),
+    );
+    let res = match access_token_or_login_msg(
+        &user_id,
+        chat_id,
+        pending_command,
+        token_store,
+        app_config,
+    )
+    .await
+    {
+        Ok(Ok(access_token)) => {
+            exec_subscription_stats_helper(stats_config.format, &client, &access_token).await
+        }
+        Ok(Err(login_msg)) => StatsReport::Table(login_msg).in_ok(),
+        Err(e) => Err(e),
+    };
+    res.pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
+        .map_err(err)
+}
+
+/// Final func that counts stats when everything is ready.
+/// Report is a single message, so there are no results to browse.
+pub(crate) async fn exec_subscription_stats_helper(
+    format: SubscriptionStatsFormat,
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<StatsReport> {
+    log::info!(" [:: LOG ::]     @[fn]:[exec_subscription_stats_helper] :: [Started]");
+
+    let subscriptions: Vec<_> = item_stream(client, SubscriptionRequester, access_token)
+        .try_collect()
+        .await?;
+    let stats = subscription_stats(&subscriptions);
+    match format {
+        SubscriptionStatsFormat::Message => {
+            StatsReport::Table((stats_report(&stats).into(), vec![], None)).in_ok()
+        }
+        SubscriptionStatsFormat::Csv => {
+            StatsReport::Document(build_subscription_stats_document(&stats)).in_ok()
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn exec_subscription_stats_helper_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let format = SubscriptionStatsFormat::Message;
+        let report = exec_subscription_stats_helper(format, client, MOCK_ACCESS_TOKEN)
+            .await
+            .unwrap();
+        let StatsReport::Table((Some(report), results, None)) = report else {
+            panic!("report should be a single message");
+        };
+        assert!(report.contains("Total: 3"));
+        assert!(results.is_empty());
+
+        let format = SubscriptionStatsFormat::Csv;
+        let report = exec_subscription_stats_helper(format, client, MOCK_ACCESS_TOKEN)
+            .await
+            .unwrap();
+        let StatsReport::Document(document) = report else {
+            panic!("report should be a file");
+        };
+        assert_eq!(document.file_name, "youtube_subscription_stats.csv");
+        assert_eq!(document.record_count, 3);
+    }
+}
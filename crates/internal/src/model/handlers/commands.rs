@@ -3,13 +3,16 @@ use teloxide::prelude::Message;
 
 use crate::model::commands::funcs::{info, log_out};
 use crate::model::commands::types::Command;
+use crate::model::db::traits::TokenStore;
 use crate::model::dialogue::types::{
-    DialogueData, ListCommandSettings, MessageTriplet, SearchCommandSettings,
-    SearchVideosInPlaylistsCommandSettings, State, TheDialogue,
+    CommandConfig, DialogueData, FindDuplicatesConfig, ListCommandSettings, MessageTriplet,
+    SearchCommandSettings, SearchVideosInPlaylistsCommandSettings, State, TheDialogue,
 };
 use crate::model::keyboards::traits::{CreateKB, KeyboardText};
 use crate::model::keyboards::types::{
-    ListCommandButtons, SearchCommandButtons, SearchVideoInPlaylistsCommandButtons,
+    ExportCommandButtons, InactiveSubscriptionsCommandButtons, ListCommandButtons,
+    PlaylistStatsCommandButtons, SearchCommandButtons, SearchVideoInPlaylistsCommandButtons,
+    SubscriptionStatsCommandButtons,
 };
 use crate::view::types::Sendable;
 
@@ -17,6 +20,7 @@ pub(crate) async fn handle_commands(
     msg: Message,
     dialogue: TheDialogue,
     cmd: Command,
+    token_store: &dyn TokenStore,
 ) -> Sendable<impl Into<String>> {
     log::info!(" [:: LOG ::]     @[fn]:[handlers::handle_commands] :: [Started]");
 
@@ -26,7 +30,12 @@ pub(crate) async fn handle_commands(
     let (message_text, opt_keyboard, opt_dialogue_data): MessageTriplet = match cmd {
         Command::Start => (
             "Bot started, send something ⌨ \nUse one of /search, /list or \
-                    /search_videos_in_my_playlists commands 🚀"
+                    /search_videos_in_my_playlists commands 🚀 \nUse /export to save them to a file 📦 \n\
+                    Use /snapshot_playlists and later /playlist_changes to find vanished videos 👻 \n\
+                    Use /playlist_stats to see how long your playlists are ⏱ \n\
+                    Use /find_duplicates to find videos saved more than once 🔁 \n\
+                    Use /inactive_subscriptions to find channels that stopped uploading 💤 \n\
+                    Use /stats to see how your subscriptions grew over the years 📈"
                 .into(),
             None,
             None,
@@ -55,7 +64,7 @@ pub(crate) async fn handle_commands(
                 return Sendable::SendError("⚠ Internal error ⚠".to_owned());
             };
             let user_id = user_id.id.to_string();
-            log_out(&user_id, env!("REDIS_YOUTUBE_ACCESS_TOKEN_STORAGE"))
+            log_out(&user_id, token_store)
                 .await
                 .pass_err_with(|e| log::error!("{log_prefix}{e:?}"))
                 .map_err_by(err)
@@ -72,6 +81,33 @@ pub(crate) async fn handle_commands(
             let buttons = SearchVideoInPlaylistsCommandButtons::ButtonList;
             (buttons.kb_text(), buttons.create_kb(), d_data.into())
         }
+        Command::SnapshotPlaylists => {
+            return Sendable::ExecuteCommand(CommandConfig::SnapshotPlaylists);
+        }
+        Command::PlaylistChanges => {
+            return Sendable::ExecuteCommand(CommandConfig::PlaylistChanges);
+        }
+        Command::FindDuplicates(playlist) => {
+            let playlist = Some(playlist.trim().to_owned()).filter(|p| !p.is_empty());
+            let config = FindDuplicatesConfig { playlist };
+            return Sendable::ExecuteCommand(CommandConfig::FindDuplicatesConfig(config));
+        }
+        Command::Export => {
+            let buttons = ExportCommandButtons::ButtonList;
+            (buttons.kb_text(), buttons.create_kb(), None)
+        }
+        Command::PlaylistStats => {
+            let buttons = PlaylistStatsCommandButtons::ButtonList;
+            (buttons.kb_text(), buttons.create_kb(), None)
+        }
+        Command::Stats => {
+            let buttons = SubscriptionStatsCommandButtons::ButtonList;
+            (buttons.kb_text(), buttons.create_kb(), None)
+        }
+        Command::InactiveSubscriptions => {
+            let buttons = InactiveSubscriptionsCommandButtons::ButtonList;
+            (buttons.kb_text(), buttons.create_kb(), None)
+        }
     };
     if let (d, Some(kb)) = (opt_dialogue_data, opt_keyboard) {
         Sendable::SendKeyboard {
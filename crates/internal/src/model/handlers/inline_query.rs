@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use maptypings::WrapInRes;
+use teloxide::types::{
+    InlineQueryResult, InlineQueryResultArticle, InputMessageContent, InputMessageContentText,
+    ParseMode, UserId,
+};
+
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::TokenStore;
+use crate::model::handlers::callback::common::update_and_return_access_token;
+use crate::model::keyboards::types::{SortOrder, Sorting};
+use crate::model::net::types::{PlaylistRequester, SubscriptionRequester, YouTubeClient};
+use crate::model::pagination::funcs::{render_item, truncate};
+use crate::model::pagination::types::{
+    ResultCache, INLINE_FETCH_LIMIT, MAX_DESCRIPTION_LEN, MAX_INLINE_RESULTS,
+};
+use crate::model::youtube::funcs::list_cmd::{list_items, sort_items};
+use crate::model::youtube::types::SearchableItem;
+
+/// Main `inline query` handler.
+/// Returns `None` if user hasn't logged in yet.
+/// Items are fetched once per `INLINE_RESULTS_TTL`, queries typed meanwhile wait for them.
+pub(crate) async fn handle_inline_query(
+    query: &str,
+    user_id: UserId,
+    token_store: &dyn TokenStore,
+    app_config: &AppConfig,
+    result_cache: &ResultCache,
+) -> eyre::Result<Option<Vec<InlineQueryResult>>> {
+    log::info!(" [:: LOG ::]     @[fn]:[handlers::handle_inline_query] :: [Started]");
+
+    let shared_fetch = result_cache.inline_items(user_id).await;
+    let items = match shared_fetch.get() {
+        Some(items) => items.clone(),
+        None => {
+            let user_id = user_id.0.to_string();
+            let Ok(token) = token_store.get_token(&user_id).await else {
+                return None.in_ok();
+            };
+            let fetch = || async {
+                let access_token =
+                    update_and_return_access_token(&user_id, token, token_store, app_config)
+                        .await?;
+                let client = YouTubeClient::from_config(app_config);
+                let items = subscriptions_and_playlists(&client, &access_token).await?;
+                eyre::Ok(Arc::new(items))
+            };
+            shared_fetch.get_or_try_init(fetch).await?.clone()
+        }
+    };
+
+    let results = find_by_title(&items, query)
+        .enumerate()
+        .map(|(i, item)| to_inline_result(i, item))
+        .collect();
+    Some(results).in_ok()
+}
+
+/// All user's subscriptions and playlists, sorted by title.
+async fn subscriptions_and_playlists(
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<Vec<SearchableItem>> {
+    let sorting = Sorting::Alphabetical(SortOrder::Ascending);
+    let mut items = list_items(
+        client,
+        SubscriptionRequester,
+        access_token,
+        INLINE_FETCH_LIMIT,
+    )
+    .await?;
+    sort_items(&mut items, &sorting);
+    let mut playlists =
+        list_items(client, PlaylistRequester, access_token, INLINE_FETCH_LIMIT).await?;
+    sort_items(&mut playlists, &sorting);
+    items.extend(playlists);
+    Ok(items)
+}
+
+/// Case-insensitive search by title. Empty query matches everything.
+fn find_by_title<'a>(
+    items: &'a [SearchableItem],
+    query: &str,
+) -> impl Iterator<Item = &'a SearchableItem> {
+    let query = query.trim().to_lowercase();
+    items
+        .iter()
+        .filter(move |i| {
+            i.title
+                .as_deref()
+                .is_some_and(|t| t.to_lowercase().contains(&query))
+        })
+        .take(MAX_INLINE_RESULTS)
+}
+
+fn to_inline_result(index: usize, item: &SearchableItem) -> InlineQueryResult {
+    let title = item.title.as_deref().unwrap_or("No title 🤷‍♂️");
+    let content = InputMessageContentText::new(render_item(item)).parse_mode(ParseMode::Html);
+    let mut article =
+        InlineQueryResultArticle::new(index.to_string(), title, InputMessageContent::Text(content));
+    if let Some(descr) = item.description.as_deref() {
+        article = article.description(truncate(descr, MAX_DESCRIPTION_LEN));
+    }
+    if let Some(url) = item.link.as_deref().and_then(|l| l.parse().ok()) {
+        article = article.url(url);
+    }
+    InlineQueryResult::Article(article)
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    fn item(title: &str) -> SearchableItem {
+        SearchableItem {
+            title: title.to_owned().into(),
+            link: format!("https://www.youtube.com/channel/{title}").into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_by_title_test() {
+        let items = vec![
+            item("Rust Lang"),
+            item("Jon Gjengset"),
+            item("Crust of Rust"),
+        ];
+
+        let titles = |q| {
+            find_by_title(&items, q)
+                .map(|i| i.title.as_deref().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles("rust"), ["Rust Lang", "Crust of Rust"]);
+        assert_eq!(titles("  JON "), ["Jon Gjengset"]);
+        assert_eq!(titles("").len(), items.len());
+        assert!(titles("python").is_empty());
+
+        let many = vec![item("same"); MAX_INLINE_RESULTS + 10];
+        assert_eq!(find_by_title(&many, "same").count(), MAX_INLINE_RESULTS);
+    }
+
+    #[tokio::test]
+    async fn inline_items_fetched_once_test() {
+        let result_cache = ResultCache::default();
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            eyre::Ok(Arc::new(vec![item("Rust Lang")]))
+        };
+        let query = |user_id| {
+            let (result_cache, fetch) = (&result_cache, &fetch);
+            async move {
+                let shared_fetch = result_cache.inline_items(user_id).await;
+                let items = shared_fetch.get_or_try_init(fetch).await.unwrap();
+                items.len()
+            }
+        };
+
+        // Keystrokes that come while items are being fetched wait for the same fetch.
+        let counts = join_all([UserId(1), UserId(1), UserId(1)].map(query)).await;
+        assert_eq!(counts, [1, 1, 1]);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        query(UserId(2)).await;
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn to_inline_result_test() {
+        let InlineQueryResult::Article(article) = to_inline_result(7, &item("Rust Lang")) else {
+            panic!("not an article");
+        };
+        assert_eq!(article.id, "7");
+        assert_eq!(article.title, "Rust Lang");
+        assert_eq!(
+            article.url.unwrap().as_str(),
+            "https://www.youtube.com/channel/Rust%20Lang"
+        );
+        let InputMessageContent::Text(content) = article.input_message_content else {
+            panic!("not a text");
+        };
+        assert!(content.message_text.contains("<b>Rust Lang</b>"));
+    }
+}
@@ -5,11 +5,13 @@ use maptypings::WrapInRes;
 use teloxide::types::Message;
 
 use crate::model::dialogue::funcs::{
-    get_callback_data, get_dialogue_data, get_text, parse_number, save_text,
+    get_callback_data, get_dialogue_data, get_text, save_text_input,
+};
+use crate::model::dialogue::types::State::{
+    ListCommandActive, SearchCommandActive, SearchVideosInPlaylistsCommandActive,
 };
-use crate::model::dialogue::types::State::{ListCommandActive, SearchCommandActive};
 use crate::model::dialogue::types::{DialogueData, Either, State, TheDialogue};
-use crate::model::keyboards::types::{Buttons, ListCommandButtons, SearchCommandButtons};
+use crate::model::keyboards::types::Buttons;
 use crate::view::types::Sendable;
 
 const GREETING: &str = "Bot started, send something ⌨ \nUse one of \
@@ -58,28 +60,255 @@ pub(crate) async fn handle_text(msg: Message, dialogue: TheDialogue) -> Sendable
             Err(_) => return Sendable::SendError("⚠ Internal error ⚠".to_owned()),
         };
 
-    let (message_text, opt_dialogue_data): (&str, Option<DialogueData>) =
-        match (d_data.state.as_ref(), buttons) {
-            (State::Starting, ..) => (GREETING, None),
-            (
-                SearchCommandActive(search_config),
-                Buttons::SearchButtons(SearchCommandButtons::ResultLimit),
-            ) => parse_number(&text, Either::First(search_config), &d_data),
-            (
-                ListCommandActive(list_config),
-                Buttons::ListButtons(ListCommandButtons::ResultLimit),
-            ) => parse_number(&text, Either::Last(list_config), &d_data),
+    let (message_text, opt_dialogue_data) = save_text_for_state(&text, &d_data, &buttons);
+    Sendable::SendOrEditMessage(message_text.into(), None, opt_dialogue_data)
+}
+
+/// Saves `text` into settings of the active command.
+/// `buttons` is the last pressed button, it tells which setting the text is for.
+fn save_text_for_state(
+    text: &str,
+    d_data: &DialogueData,
+    buttons: &Buttons,
+) -> (&'static str, Option<DialogueData>) {
+    match d_data.state.as_ref() {
+        State::Starting => (GREETING, None),
+        SearchCommandActive(settings) => save_text_input(settings, buttons, text, d_data),
+        ListCommandActive(settings) => save_text_input(settings, buttons, text, d_data),
+        SearchVideosInPlaylistsCommandActive(settings) => {
+            save_text_input(settings, buttons, text, d_data)
+        }
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::dialogue::types::{
+        ListCommandSettings, SearchCommandSettings, SearchVideosInPlaylistsCommandSettings,
+    };
+    use crate::model::keyboards::types::{
+        ExportCommandButtons, ExportFormat, InactiveSubscriptionsCommandButtons,
+        ListCommandButtons, MatchMode, PageButtons, PlaylistStatsCommandButtons, Requestable,
+        SearchCommandButtons, SearchIn, SearchVideoInPlaylistsCommandButtons, SortOrder, Sorting,
+        StatsFormat, StatsSorting, SubscriptionStatsCommandButtons, SubscriptionStatsFormat,
+    };
+    use crate::model::net::types::SubscriptionRequester;
+
+    use super::*;
+
+    /// What `save_text_for_state` should do with the text.
+    #[derive(Debug, PartialEq)]
+    enum Expected {
+        Greeting,
+        ResultLimit,
+        TextToSearch,
+        Rejected,
+    }
+
+    fn all_states() -> Vec<State> {
+        vec![
+            State::Starting,
+            SearchCommandActive(SearchCommandSettings::default()),
+            ListCommandActive(ListCommandSettings::default()),
+            SearchVideosInPlaylistsCommandActive(SearchVideosInPlaylistsCommandSettings::default()),
+        ]
+    }
+
+    fn all_buttons() -> Vec<Buttons> {
+        let target = || Requestable::Subscription(SubscriptionRequester);
+        let search = [
+            SearchCommandButtons::ButtonList,
+            SearchCommandButtons::Execute,
+            SearchCommandButtons::ResultLimit,
+            SearchCommandButtons::TargetOptions,
+            SearchCommandButtons::Target(target()),
+            SearchCommandButtons::SearchInOptions,
+            SearchCommandButtons::SearchIn(SearchIn::Title),
+            SearchCommandButtons::MatchModeOptions,
+            SearchCommandButtons::MatchMode(MatchMode::Fuzzy),
+            SearchCommandButtons::TextToSearch,
+        ]
+        .map(Buttons::SearchButtons);
+        let list = [
+            ListCommandButtons::ButtonList,
+            ListCommandButtons::Execute,
+            ListCommandButtons::ResultLimit,
+            ListCommandButtons::TargetOptions,
+            ListCommandButtons::Target(target()),
+            ListCommandButtons::SortingOptions,
+            ListCommandButtons::Sorting(Sorting::Date(SortOrder::Ascending)),
+            ListCommandButtons::Sorting(Sorting::LastActivity(SortOrder::Descending)),
+        ]
+        .map(Buttons::ListButtons);
+        let search_videos = [
+            SearchVideoInPlaylistsCommandButtons::ButtonList,
+            SearchVideoInPlaylistsCommandButtons::Execute,
+            SearchVideoInPlaylistsCommandButtons::ResultLimit,
+            SearchVideoInPlaylistsCommandButtons::SearchInOptions,
+            SearchVideoInPlaylistsCommandButtons::SearchIn(SearchIn::Description),
+            SearchVideoInPlaylistsCommandButtons::MatchModeOptions,
+            SearchVideoInPlaylistsCommandButtons::MatchMode(MatchMode::AllWords),
+            SearchVideoInPlaylistsCommandButtons::TextToSearch,
+        ]
+        .map(Buttons::SearchVideoInPlaylistsButtons);
+        let export = [
+            ExportCommandButtons::ButtonList,
+            ExportCommandButtons::Export(target(), ExportFormat::Csv),
+        ]
+        .map(Buttons::ExportButtons);
+        let stats = [
+            PlaylistStatsCommandButtons::ButtonList,
+            PlaylistStatsCommandButtons::Stats(StatsFormat::Table(StatsSorting::ItemCount)),
+            PlaylistStatsCommandButtons::Stats(StatsFormat::Csv),
+        ]
+        .map(Buttons::PlaylistStatsButtons);
+        let inactive = [
+            InactiveSubscriptionsCommandButtons::ButtonList,
+            InactiveSubscriptionsCommandButtons::Months(12),
+        ]
+        .map(Buttons::InactiveSubscriptionsButtons);
+        let subscription_stats = [
+            SubscriptionStatsCommandButtons::ButtonList,
+            SubscriptionStatsCommandButtons::Stats(SubscriptionStatsFormat::Message),
+            SubscriptionStatsCommandButtons::Stats(SubscriptionStatsFormat::Csv),
+        ]
+        .map(Buttons::SubscriptionStatsButtons);
+        let page = [
+            PageButtons::Page {
+                id: "id".to_owned(),
+                page: 1,
+            },
+            PageButtons::Close {
+                id: "id".to_owned(),
+            },
+        ]
+        .map(Buttons::PageButtons);
+
+        search
+            .into_iter()
+            .chain(list)
+            .chain(search_videos)
+            .chain(export)
+            .chain(stats)
+            .chain(inactive)
+            .chain(subscription_stats)
+            .chain(page)
+            .collect()
+    }
+
+    fn expected(state: &State, button: &Buttons) -> Expected {
+        use SearchVideoInPlaylistsCommandButtons as SV;
+        match (state, button) {
+            (State::Starting, _) => Expected::Greeting,
+            (SearchCommandActive(_), Buttons::SearchButtons(SearchCommandButtons::ResultLimit))
+            | (ListCommandActive(_), Buttons::ListButtons(ListCommandButtons::ResultLimit))
+            | (
+                SearchVideosInPlaylistsCommandActive(_),
+                Buttons::SearchVideoInPlaylistsButtons(SV::ResultLimit),
+            ) => Expected::ResultLimit,
             (
-                SearchCommandActive(search_config),
+                SearchCommandActive(_),
                 Buttons::SearchButtons(SearchCommandButtons::TextToSearch),
-            ) => save_text(&text, search_config.clone(), &d_data),
-            other => {
-                log::info!(
-                    " [:: LOG ::] ... ( @[fn]:[handle_text] [:: {:?} ::] )",
-                    other
-                );
-                ("Oops! 🤷‍♂️", None)
+            )
+            | (
+                SearchVideosInPlaylistsCommandActive(_),
+                Buttons::SearchVideoInPlaylistsButtons(SV::TextToSearch),
+            ) => Expected::TextToSearch,
+            _ => Expected::Rejected,
+        }
+    }
+
+    /// `(result_limit, text_to_search)` of the state.
+    fn saved_values(state: &State) -> (Option<u32>, Option<&str>) {
+        match state {
+            State::Starting => (None, None),
+            SearchCommandActive(s) => (s.result_limit, s.text_to_search.as_deref()),
+            ListCommandActive(s) => (s.result_limit, None),
+            SearchVideosInPlaylistsCommandActive(s) => {
+                (s.result_limit, s.text_to_search.as_deref())
             }
+        }
+    }
+
+    #[test]
+    fn save_text_for_every_state_and_button_test() {
+        for state in all_states() {
+            for button in all_buttons() {
+                let d_data = DialogueData {
+                    state: state.clone(),
+                    ..Default::default()
+                };
+                let case = format!("{state:?} / {button:?}");
+                let (msg, opt_d_data) = save_text_for_state("42", &d_data, &button);
+                let saved_state = opt_d_data.as_ref().map(|d| &d.state);
+
+                match expected(&state, &button) {
+                    Expected::Greeting => {
+                        assert_eq!(msg, GREETING, "{case}");
+                        assert!(opt_d_data.is_none(), "{case}");
+                    }
+                    Expected::ResultLimit => {
+                        assert_eq!(msg, "Accepted! ✅", "{case}");
+                        let saved = saved_values(saved_state.unwrap());
+                        assert_eq!(saved, (Some(42), None), "{case}");
+                    }
+                    Expected::TextToSearch => {
+                        assert_eq!(msg, "Accepted! ✅", "{case}");
+                        let saved = saved_values(saved_state.unwrap());
+                        assert_eq!(saved, (None, Some("42")), "{case}");
+                    }
+                    Expected::Rejected => {
+                        assert_eq!(msg, "Oops! 🤷‍♂️", "{case}");
+                        assert!(opt_d_data.is_none(), "{case}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn save_text_keeps_command_state_test() {
+        let settings = SearchVideosInPlaylistsCommandSettings {
+            search_in: SearchIn::Title.into(),
+            ..Default::default()
         };
-    Sendable::SendOrEditMessage(message_text.into(), None, opt_dialogue_data)
+        let d_data = DialogueData {
+            state: SearchVideosInPlaylistsCommandActive(settings),
+            ..Default::default()
+        };
+        let button = Buttons::SearchVideoInPlaylistsButtons(
+            SearchVideoInPlaylistsCommandButtons::TextToSearch,
+        );
+        let (_, opt_d_data) = save_text_for_state("rust", &d_data, &button);
+
+        let SearchVideosInPlaylistsCommandActive(settings) = opt_d_data.unwrap().state else {
+            panic!("state has changed");
+        };
+        assert_eq!(settings.search_in, Some(SearchIn::Title));
+        assert_eq!(settings.text_to_search.as_deref(), Some("rust"));
+    }
+
+    #[test]
+    fn invalid_result_limit_test() {
+        let buttons = [
+            Buttons::SearchButtons(SearchCommandButtons::ResultLimit),
+            Buttons::ListButtons(ListCommandButtons::ResultLimit),
+            Buttons::SearchVideoInPlaylistsButtons(
+                SearchVideoInPlaylistsCommandButtons::ResultLimit,
+            ),
+        ];
+        for (state, button) in all_states().into_iter().skip(1).zip(buttons) {
+            let d_data = DialogueData {
+                state,
+                ..Default::default()
+            };
+            for text in ["0", "-3", "ten", "", "100000"] {
+                let (msg, opt_d_data) = save_text_for_state(text, &d_data, &button);
+                assert_eq!(msg, "Send a number greater than 0", "{button:?} / {text:?}");
+                assert!(opt_d_data.is_none());
+            }
+        }
+    }
 }
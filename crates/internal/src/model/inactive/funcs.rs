@@ -0,0 +1,154 @@
+use time::Date;
+
+use crate::model::inactive::types::ChannelActivity;
+use crate::model::youtube::types::SearchableItem;
+
+/// Channels without uploads since `months` before `today`.
+/// Channels that never uploaded go first, then the ones silent for the longest time.
+pub(crate) fn inactive_channels(
+    channels: Vec<ChannelActivity>,
+    months: u8,
+    today: Date,
+) -> Vec<ChannelActivity> {
+    let cutoff = months_before(today, months);
+    let mut inactive: Vec<_> = channels
+        .into_iter()
+        .filter(|c| {
+            c.last_upload
+                .as_deref()
+                .is_none_or(|d| date_part(d) < cutoff.as_str())
+        })
+        .collect();
+    // `RFC 3339` dates in `UTC` compare correctly as strings, and `None` goes first.
+    inactive.sort_by(|a, b| a.last_upload.cmp(&b.last_upload));
+    inactive
+}
+
+/// `YYYY-MM-DD` date `months` before `today`.
+/// Day isn't clamped to the length of month, as it's only used for comparison.
+fn months_before(today: Date, months: u8) -> String {
+    let month_index = today.year() * 12 + i32::from(u8::from(today.month())) - 1;
+    let month_index = month_index - i32::from(months);
+    format!(
+        "{:04}-{:02}-{:02}",
+        month_index.div_euclid(12),
+        month_index.rem_euclid(12) + 1,
+        today.day()
+    )
+}
+
+/// Whole months passed between `YYYY-MM` prefix of `date` and `today`.
+fn months_since(date: &str, today: Date) -> Option<i32> {
+    let year: i32 = date.get(..4)?.parse().ok()?;
+    let month: i32 = date.get(5..7)?.parse().ok()?;
+    let day: u8 = date.get(8..10)?.parse().ok()?;
+    let months = (today.year() - year) * 12 + i32::from(u8::from(today.month())) - month;
+    let months = if today.day() < day {
+        months - 1
+    } else {
+        months
+    };
+    Some(months.max(0))
+}
+
+fn date_part(date: &str) -> &str {
+    date.get(..10).unwrap_or(date)
+}
+
+/// One item per channel, to be shown page by page like any other results.
+pub(crate) fn channels_to_items(channels: &[ChannelActivity], today: Date) -> Vec<SearchableItem> {
+    channels
+        .iter()
+        .map(|channel| {
+            let description = match &channel.last_upload {
+                Some(date) => {
+                    let ago = months_since(date, today)
+                        .map(|m| format!(", {m} months ago"))
+                        .unwrap_or_default();
+                    format!("💤 Last upload {}{ago}", date_part(date))
+                }
+                None => "💀 No uploads".to_owned(),
+            };
+            SearchableItem {
+                title: Some(channel.title.clone()),
+                description: Some(description),
+                link: format!("https://youtube.com/channel/{}", channel.channel_id).into(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    fn today() -> Date {
+        Date::from_calendar_date(2023, Month::November, 9).unwrap()
+    }
+
+    fn channel(title: &str, last_upload: Option<&str>) -> ChannelActivity {
+        ChannelActivity {
+            channel_id: format!("UC{title}"),
+            title: title.to_owned(),
+            last_upload: last_upload.map(str::to_owned),
+        }
+    }
+
+    fn channels() -> Vec<ChannelActivity> {
+        vec![
+            channel("Recent", Some("2023-10-01T10:00:00Z")),
+            channel("Old", Some("2014-05-20T11:00:00Z")),
+            channel("Silent", None),
+            channel("Half year", Some("2023-05-10T17:00:00Z")),
+        ]
+    }
+
+    #[test]
+    fn months_before_test() {
+        assert_eq!(months_before(today(), 3), "2023-08-09");
+        assert_eq!(months_before(today(), 12), "2022-11-09");
+        assert_eq!(months_before(today(), 24), "2021-11-09");
+        let january = Date::from_calendar_date(2024, Month::January, 31).unwrap();
+        assert_eq!(months_before(january, 1), "2023-12-31");
+    }
+
+    #[test]
+    fn inactive_channels_test() {
+        let titles = |months| {
+            inactive_channels(channels(), months, today())
+                .into_iter()
+                .map(|c| c.title)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(titles(3), ["Silent", "Old", "Half year"]);
+        assert_eq!(titles(6), ["Silent", "Old"]);
+        assert_eq!(titles(24), ["Silent", "Old"]);
+    }
+
+    #[test]
+    fn channels_to_items_test() {
+        let inactive = inactive_channels(channels(), 3, today());
+        let items = channels_to_items(&inactive, today());
+        let descriptions: Vec<_> = items
+            .iter()
+            .map(|i| i.description.clone().unwrap())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "💀 No uploads",
+                "💤 Last upload 2014-05-20, 113 months ago",
+                "💤 Last upload 2023-05-10, 5 months ago",
+            ]
+        );
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://youtube.com/channel/UCSilent")
+        );
+    }
+}
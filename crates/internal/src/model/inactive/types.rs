@@ -0,0 +1,8 @@
+/// Subscribed channel and date of its latest upload.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ChannelActivity {
+    pub(crate) channel_id: String,
+    pub(crate) title: String,
+    /// `RFC 3339` date, `None` if channel has no uploads or doesn't exist anymore.
+    pub(crate) last_upload: Option<String>,
+}
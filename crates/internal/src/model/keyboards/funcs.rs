@@ -1,7 +1,7 @@
 use crate::model::keyboards::traits::ButtonText;
-use crate::model::keyboards::types::Buttons;
+use crate::model::keyboards::types::{Buttons, MatchMode};
 use serde::Serialize;
-use teloxide::types::{InlineKeyboardButton, InlineKeyboardButtonKind};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardButtonKind, InlineKeyboardMarkup};
 
 /// Constructs `inline keyboard` button inner data.
 fn callback_data<D: Serialize>(callback_data: D) -> InlineKeyboardButtonKind {
@@ -19,3 +19,20 @@ pub(crate) fn inline_button<S: Into<String>>(text: S, data: Buttons) -> InlineKe
 pub(crate) fn button(kb: Buttons) -> InlineKeyboardButton {
     InlineKeyboardButton::new(kb.button_text(), callback_data(kb))
 }
+
+/// Keyboard to choose `MatchMode`, shared by both search commands.
+pub(crate) fn match_mode_kb<F: Fn(MatchMode) -> Buttons>(
+    mode_button: F,
+    cancel: Buttons,
+) -> InlineKeyboardMarkup {
+    use MatchMode::{AllWords, AnyWord, Exact, Fuzzy, Regex};
+    [[Exact, AllWords, AnyWord].as_slice(), &[Fuzzy, Regex]]
+        .into_iter()
+        .enumerate()
+        .fold(InlineKeyboardMarkup::default(), |kb, (row, modes)| {
+            modes.iter().fold(kb, |kb, &mode| {
+                kb.append_to_row(row, button(mode_button(mode)))
+            })
+        })
+        .append_to_row(2, inline_button("Cancel ❌", cancel))
+}
@@ -1,11 +1,15 @@
 use std::fmt::Display;
 
-use crate::model::keyboards::funcs::{button, inline_button};
+use crate::model::keyboards::funcs::{button, inline_button, match_mode_kb};
 use crate::model::keyboards::types::{
-    Buttons, ListCommandButtons, Requestable, SearchCommandButtons, SearchIn,
-    SearchVideoInPlaylistsCommandButtons, Sorting,
+    Buttons, ExportCommandButtons, ExportFormat, InactiveSubscriptionsCommandButtons,
+    ListCommandButtons, MatchMode, PageButtons, PlaylistStatsCommandButtons, Requestable,
+    SearchCommandButtons, SearchIn, SearchVideoInPlaylistsCommandButtons, SortOrder, Sorting,
+    StatsFormat, StatsSorting, SubscriptionStatsCommandButtons, SubscriptionStatsFormat,
+};
+use crate::model::net::types::{
+    LikedVideoRequester, PlaylistRequester, SubscriptionRequester, UploadsRequester,
 };
-use crate::model::net::types::{PlaylistRequester, SubscriptionRequester};
 use crate::model::utils::HTMLise;
 use teloxide::types::InlineKeyboardMarkup;
 
@@ -34,6 +38,18 @@ impl CreateKB for SearchCommandButtons {
                 )
                 .append_to_row(
                     1,
+                    button(SearchButtons(SearchCommandButtons::Target(
+                        Requestable::LikedVideo(LikedVideoRequester),
+                    ))),
+                )
+                .append_to_row(
+                    1,
+                    button(SearchButtons(SearchCommandButtons::Target(
+                        Requestable::Uploads(UploadsRequester),
+                    ))),
+                )
+                .append_to_row(
+                    2,
                     inline_button("Cancel ❌", SearchButtons(SearchCommandButtons::ButtonList)),
                 )
                 .into(),
@@ -55,8 +71,14 @@ impl CreateKB for SearchCommandButtons {
                     inline_button("Cancel ❌", SearchButtons(SearchCommandButtons::ButtonList)),
                 )
                 .into(),
+            SearchCommandButtons::MatchModeOptions => match_mode_kb(
+                |mode| SearchButtons(SearchCommandButtons::MatchMode(mode)),
+                SearchButtons(SearchCommandButtons::ButtonList),
+            )
+            .into(),
             SearchCommandButtons::Target(_)
             | SearchCommandButtons::SearchIn(_)
+            | SearchCommandButtons::MatchMode(_)
             | SearchCommandButtons::ButtonList
             | SearchCommandButtons::Execute => InlineKeyboardMarkup::default()
                 .append_to_row(
@@ -69,9 +91,13 @@ impl CreateKB for SearchCommandButtons {
                 )
                 .append_to_row(1, button(SearchButtons(SearchCommandButtons::ResultLimit)))
                 .append_to_row(1, button(SearchButtons(SearchCommandButtons::TextToSearch)))
-                .append_to_row(2, button(SearchButtons(SearchCommandButtons::Execute)))
                 .append_to_row(
                     2,
+                    button(SearchButtons(SearchCommandButtons::MatchModeOptions)),
+                )
+                .append_to_row(3, button(SearchButtons(SearchCommandButtons::Execute)))
+                .append_to_row(
+                    3,
                     inline_button("Cancel ❌", SearchButtons(SearchCommandButtons::ButtonList)),
                 )
                 .into(),
@@ -99,25 +125,45 @@ impl CreateKB for ListCommandButtons {
                 )
                 .append_to_row(
                     1,
-                    inline_button("Cancel ❌", ListButtons(ListCommandButtons::ButtonList)),
-                )
-                .into(),
-            ListCommandButtons::SortingOptions => InlineKeyboardMarkup::default()
-                .append_to_row(
-                    0,
-                    button(ListButtons(ListCommandButtons::Sorting(
-                        Sorting::Alphabetical,
+                    button(ListButtons(ListCommandButtons::Target(
+                        Requestable::LikedVideo(LikedVideoRequester),
                     ))),
                 )
                 .append_to_row(
-                    0,
-                    button(ListButtons(ListCommandButtons::Sorting(Sorting::Date))),
+                    1,
+                    button(ListButtons(ListCommandButtons::Target(
+                        Requestable::Uploads(UploadsRequester),
+                    ))),
                 )
                 .append_to_row(
-                    1,
+                    2,
                     inline_button("Cancel ❌", ListButtons(ListCommandButtons::ButtonList)),
                 )
                 .into(),
+            ListCommandButtons::SortingOptions => {
+                let modes: [fn(SortOrder) -> Sorting; 4] = [
+                    Sorting::Alphabetical,
+                    Sorting::Date,
+                    Sorting::ItemCount,
+                    Sorting::LastActivity,
+                ];
+                let kb = modes.into_iter().enumerate().fold(
+                    InlineKeyboardMarkup::default(),
+                    |kb, (row, mode)| {
+                        [SortOrder::Ascending, SortOrder::Descending]
+                            .into_iter()
+                            .fold(kb, |kb, order| {
+                                let sorting = ListCommandButtons::Sorting(mode(order));
+                                kb.append_to_row(row, button(ListButtons(sorting)))
+                            })
+                    },
+                );
+                kb.append_to_row(
+                    modes.len(),
+                    inline_button("Cancel ❌", ListButtons(ListCommandButtons::ButtonList)),
+                )
+                .into()
+            }
             ListCommandButtons::Target(_)
             | ListCommandButtons::Sorting(_)
             | ListCommandButtons::ButtonList
@@ -139,7 +185,7 @@ impl CreateKB for SearchVideoInPlaylistsCommandButtons {
     fn create_kb(&self) -> Option<InlineKeyboardMarkup> {
         use crate::model::keyboards::types::Buttons::SearchVideoInPlaylistsButtons;
         use SearchVideoInPlaylistsCommandButtons::{
-            ButtonList, Execute, ResultLimit, SearchInOptions, TextToSearch,
+            ButtonList, Execute, MatchModeOptions, ResultLimit, SearchInOptions, TextToSearch,
         };
         match *self {
             ResultLimit | TextToSearch => None,
@@ -161,27 +207,131 @@ impl CreateKB for SearchVideoInPlaylistsCommandButtons {
                     inline_button("Cancel ❌", SearchVideoInPlaylistsButtons(ButtonList)),
                 )
                 .into(),
-            ButtonList | Execute | SearchVideoInPlaylistsCommandButtons::SearchIn(_) => {
-                InlineKeyboardMarkup::default()
-                    .append_to_row(0, button(SearchVideoInPlaylistsButtons(TextToSearch)))
-                    .append_to_row(0, button(SearchVideoInPlaylistsButtons(SearchInOptions)))
-                    .append_to_row(1, button(SearchVideoInPlaylistsButtons(ResultLimit)))
-                    .append_to_row(2, button(SearchVideoInPlaylistsButtons(Execute)))
-                    .append_to_row(
-                        2,
-                        inline_button("Cancel ❌", SearchVideoInPlaylistsButtons(ButtonList)),
-                    )
-                    .into()
-            }
+            MatchModeOptions => match_mode_kb(
+                |mode| {
+                    SearchVideoInPlaylistsButtons(SearchVideoInPlaylistsCommandButtons::MatchMode(
+                        mode,
+                    ))
+                },
+                SearchVideoInPlaylistsButtons(ButtonList),
+            )
+            .into(),
+            ButtonList
+            | Execute
+            | SearchVideoInPlaylistsCommandButtons::SearchIn(_)
+            | SearchVideoInPlaylistsCommandButtons::MatchMode(_) => InlineKeyboardMarkup::default()
+                .append_to_row(0, button(SearchVideoInPlaylistsButtons(TextToSearch)))
+                .append_to_row(0, button(SearchVideoInPlaylistsButtons(SearchInOptions)))
+                .append_to_row(1, button(SearchVideoInPlaylistsButtons(ResultLimit)))
+                .append_to_row(2, button(SearchVideoInPlaylistsButtons(MatchModeOptions)))
+                .append_to_row(3, button(SearchVideoInPlaylistsButtons(Execute)))
+                .append_to_row(
+                    3,
+                    inline_button("Cancel ❌", SearchVideoInPlaylistsButtons(ButtonList)),
+                )
+                .into(),
         }
     }
 }
 
+impl CreateKB for ExportCommandButtons {
+    fn create_kb(&self) -> Option<InlineKeyboardMarkup> {
+        let export = |target, format| {
+            button(Buttons::ExportButtons(ExportCommandButtons::Export(
+                target, format,
+            )))
+        };
+        [ExportFormat::Csv, ExportFormat::Json, ExportFormat::Opml]
+            .into_iter()
+            .enumerate()
+            .fold(InlineKeyboardMarkup::default(), |kb, (row, format)| {
+                kb.append_to_row(
+                    row,
+                    export(Requestable::Subscription(SubscriptionRequester), format),
+                )
+                .append_to_row(
+                    row,
+                    export(Requestable::Playlist(PlaylistRequester), format),
+                )
+            })
+            .into()
+    }
+}
+
+impl CreateKB for PlaylistStatsCommandButtons {
+    fn create_kb(&self) -> Option<InlineKeyboardMarkup> {
+        let stats = |format| {
+            button(Buttons::PlaylistStatsButtons(
+                PlaylistStatsCommandButtons::Stats(format),
+            ))
+        };
+        [
+            StatsSorting::Runtime,
+            StatsSorting::ItemCount,
+            StatsSorting::Title,
+        ]
+        .into_iter()
+        .fold(InlineKeyboardMarkup::default(), |kb, sorting| {
+            kb.append_to_row(0, stats(StatsFormat::Table(sorting)))
+        })
+        .append_to_row(1, stats(StatsFormat::Csv))
+        .into()
+    }
+}
+
+impl CreateKB for SubscriptionStatsCommandButtons {
+    fn create_kb(&self) -> Option<InlineKeyboardMarkup> {
+        [
+            SubscriptionStatsFormat::Message,
+            SubscriptionStatsFormat::Csv,
+        ]
+        .into_iter()
+        .fold(InlineKeyboardMarkup::default(), |kb, format| {
+            kb.append_to_row(
+                0,
+                button(Buttons::SubscriptionStatsButtons(
+                    SubscriptionStatsCommandButtons::Stats(format),
+                )),
+            )
+        })
+        .into()
+    }
+}
+
+impl CreateKB for InactiveSubscriptionsCommandButtons {
+    fn create_kb(&self) -> Option<InlineKeyboardMarkup> {
+        [3, 6, 12, 24]
+            .into_iter()
+            .fold(InlineKeyboardMarkup::default(), |kb, months| {
+                kb.append_to_row(
+                    0,
+                    button(Buttons::InactiveSubscriptionsButtons(
+                        InactiveSubscriptionsCommandButtons::Months(months),
+                    )),
+                )
+            })
+            .into()
+    }
+}
+
 /// Text to show in message with inline keyboard.
 pub(crate) trait KeyboardText {
     fn kb_text(&self) -> String;
 }
 
+/// Explains `MatchMode`s, shared by both search commands.
+fn match_mode_text() -> String {
+    format!(
+        "Choose {how} text is matched 🔤\n\n\
+        🎯 Exact  -  whole text as it is\n\
+        🧩 All words  -  every word, in any order\n\
+        🎲 Any word  -  at least one of the words\n\
+        🌫 Fuzzy  -  words with typos\n\
+        ✳ Regex  -  regular expression",
+        how = "how".to_bold()
+    )
+}
+
 impl KeyboardText for SearchCommandButtons {
     fn kb_text(&self) -> String {
         match *self {
@@ -198,10 +348,12 @@ impl KeyboardText for SearchCommandButtons {
                 "Send the {text} you want to search 💬",
                 text = "text".to_bold()
             ),
+            SearchCommandButtons::MatchModeOptions => match_mode_text(),
             SearchCommandButtons::Execute
             | SearchCommandButtons::ButtonList
             | SearchCommandButtons::Target(_)
-            | SearchCommandButtons::SearchIn(_) => "Search command settings ⚙".into(),
+            | SearchCommandButtons::SearchIn(_)
+            | SearchCommandButtons::MatchMode(_) => "Search command settings ⚙".into(),
         }
     }
 }
@@ -231,11 +383,51 @@ impl KeyboardText for SearchVideoInPlaylistsCommandButtons {
                 "Send the {text} you want to search 💬",
                 text = "text".to_bold()
             ),
+            SearchVideoInPlaylistsCommandButtons::MatchModeOptions => match_mode_text(),
             _ => "Search video in playlists command settings ⚙".into(),
         }
     }
 }
 
+impl KeyboardText for ExportCommandButtons {
+    fn kb_text(&self) -> String {
+        format!(
+            "Choose {what} you want to export and file {format} 📦",
+            what = "what".to_bold(),
+            format = "format".to_bold()
+        )
+    }
+}
+
+impl KeyboardText for PlaylistStatsCommandButtons {
+    fn kb_text(&self) -> String {
+        format!(
+            "Choose how to {sort} playlists, or get them as a {file} 📊",
+            sort = "sort".to_bold(),
+            file = "file".to_bold()
+        )
+    }
+}
+
+impl KeyboardText for SubscriptionStatsCommandButtons {
+    fn kb_text(&self) -> String {
+        format!(
+            "Get stats of your subscriptions as a {message} or a {file} 📈",
+            message = "message".to_bold(),
+            file = "file".to_bold()
+        )
+    }
+}
+
+impl KeyboardText for InactiveSubscriptionsCommandButtons {
+    fn kb_text(&self) -> String {
+        format!(
+            "Choose how long a channel has to be {silent} to be shown 💤",
+            silent = "silent".to_bold()
+        )
+    }
+}
+
 pub(crate) trait ButtonText: Display {
     fn button_text(&self) -> String {
         self.to_string()
@@ -248,12 +440,26 @@ impl ButtonText for Requestable {}
 
 impl ButtonText for SearchCommandButtons {}
 impl ButtonText for SearchIn {}
+impl ButtonText for MatchMode {}
 
 impl ButtonText for ListCommandButtons {}
 impl ButtonText for Sorting {}
 
 impl ButtonText for SearchVideoInPlaylistsCommandButtons {}
 
+impl ButtonText for ExportCommandButtons {}
+impl ButtonText for ExportFormat {}
+
+impl ButtonText for PlaylistStatsCommandButtons {}
+impl ButtonText for StatsFormat {}
+
+impl ButtonText for SubscriptionStatsCommandButtons {}
+impl ButtonText for SubscriptionStatsFormat {}
+
+impl ButtonText for InactiveSubscriptionsCommandButtons {}
+
+impl ButtonText for PageButtons {}
+
 // TODO : This trait gives strange error when used.
 /*pub(crate) trait CreateKB
 {
@@ -2,7 +2,9 @@ use parse_display::Display;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 
-use crate::model::net::types::{PlaylistRequester, SubscriptionRequester};
+use crate::model::net::types::{
+    LikedVideoRequester, PlaylistRequester, SubscriptionRequester, UploadsRequester,
+};
 
 /*/// Target of `list` or `search` commands.
 /// Used in `SearchCommandButtons` and `ListCommandButtons`.
@@ -23,6 +25,10 @@ pub enum Requestable {
     Subscription(SubscriptionRequester),
     #[display("Playlist 📜")]
     Playlist(PlaylistRequester),
+    #[display("Liked video 👍")]
+    LikedVideo(LikedVideoRequester),
+    #[display("My upload 📤")]
+    Uploads(UploadsRequester),
 }
 
 impl Debug for Requestable {
@@ -30,6 +36,8 @@ impl Debug for Requestable {
         match self {
             Requestable::Subscription(..) => write!(f, "Subscription"),
             Requestable::Playlist(..) => write!(f, "Playlist"),
+            Requestable::LikedVideo(..) => write!(f, "Liked video"),
+            Requestable::Uploads(..) => write!(f, "My upload"),
         }
     }
 }
@@ -43,6 +51,27 @@ pub enum SearchIn {
     Description,
 }
 
+/// Defines how text to search is matched. Used in `SearchCommandKB`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display, Default)]
+pub enum MatchMode {
+    /// Whole text as a substring.
+    #[display("Exact 🎯")]
+    #[default]
+    Exact,
+    /// Every word, in any order.
+    #[display("All words 🧩")]
+    AllWords,
+    /// At least one of the words.
+    #[display("Any word 🎲")]
+    AnyWord,
+    /// Words with typos or different spelling.
+    #[display("Fuzzy 🌫")]
+    Fuzzy,
+    /// Case insensitive regular expression.
+    #[display("Regex ✳")]
+    Regex,
+}
+
 /// List of `Inline Keyboard` buttons for `search` bot command.
 #[derive(Debug, Clone, Serialize, Deserialize, Display, Default)]
 pub enum SearchCommandButtons {
@@ -63,18 +92,49 @@ pub enum SearchCommandButtons {
     SearchInOptions,
     #[display("{0}")]
     SearchIn(SearchIn),
+    #[display("Matching 🔤")]
+    MatchModeOptions,
+    #[display("{0}")]
+    MatchMode(MatchMode),
     #[display(style = "Title case")]
     #[display("{} 💬")]
     TextToSearch,
 }
 
-/// Sorting foe list command.
+/// Direction of sorting for list command.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display)]
+pub enum SortOrder {
+    #[display("⬆")]
+    Ascending,
+    #[display("⬇")]
+    Descending,
+}
+
+/// Sorting for list command.
+/// Items that have no value to sort by go last in both directions.
 #[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize, Display)]
 pub enum Sorting {
-    #[display("{} 🗓")]
-    Date,
-    #[display("{} 🔠")]
-    Alphabetical,
+    #[display("Date 🗓 {0}")]
+    Date(SortOrder),
+    #[display("Alphabetical 🔠 {0}")]
+    Alphabetical(SortOrder),
+    /// Videos in a playlist, or uploads of a subscribed channel.
+    #[display("Item count 📼 {0}")]
+    ItemCount(SortOrder),
+    /// Latest upload of a subscribed channel, or latest video added to a playlist.
+    #[display("Last activity ⏳ {0}")]
+    LastActivity(SortOrder),
+}
+
+impl Sorting {
+    pub(crate) fn order(&self) -> SortOrder {
+        match *self {
+            Self::Date(order)
+            | Self::Alphabetical(order)
+            | Self::ItemCount(order)
+            | Self::LastActivity(order) => order,
+        }
+    }
 }
 
 /// List of `Inline Keyboard` buttons for `list` bot command.
@@ -115,11 +175,110 @@ pub enum SearchVideoInPlaylistsCommandButtons {
     SearchInOptions,
     #[display("{0}")]
     SearchIn(SearchIn),
+    #[display("Matching 🔤")]
+    MatchModeOptions,
+    #[display("{0}")]
+    MatchMode(MatchMode),
     #[display(style = "Title case")]
     #[display("{} 💬")]
     TextToSearch,
 }
 
+/// File format for `export` command.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display)]
+pub enum ExportFormat {
+    #[display("CSV 📊")]
+    Csv,
+    #[display("JSON 🧾")]
+    Json,
+    #[display("OPML 📡")]
+    Opml,
+}
+
+/// List of `Inline Keyboard` buttons for `export` bot command.
+#[derive(Debug, Clone, Serialize, Deserialize, Display, Default)]
+pub enum ExportCommandButtons {
+    #[display("{} 📦")]
+    #[display(style = "Title case")]
+    #[default]
+    ButtonList,
+    #[display("{0} {1}")]
+    Export(Requestable, ExportFormat),
+}
+
+/// Order of playlists in `playlist_stats` table.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display)]
+pub enum StatsSorting {
+    #[display("By runtime ⏱")]
+    Runtime,
+    #[display("By size 📼")]
+    ItemCount,
+    #[display("By title 🔠")]
+    Title,
+}
+
+/// Output of `playlist_stats` command.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display)]
+pub enum StatsFormat {
+    #[display("{0}")]
+    Table(StatsSorting),
+    #[display("CSV 📊")]
+    Csv,
+}
+
+/// List of `Inline Keyboard` buttons for `playlist_stats` bot command.
+#[derive(Debug, Clone, Serialize, Deserialize, Display, Default)]
+pub enum PlaylistStatsCommandButtons {
+    #[display("{} 📊")]
+    #[display(style = "Title case")]
+    #[default]
+    ButtonList,
+    #[display("{0}")]
+    Stats(StatsFormat),
+}
+
+/// Output of `stats` command.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize, Display)]
+pub enum SubscriptionStatsFormat {
+    #[display("Message 📈")]
+    Message,
+    #[display("CSV 📊")]
+    Csv,
+}
+
+/// List of `Inline Keyboard` buttons for `stats` bot command.
+#[derive(Debug, Clone, Serialize, Deserialize, Display, Default)]
+pub enum SubscriptionStatsCommandButtons {
+    #[display("{} 📈")]
+    #[display(style = "Title case")]
+    #[default]
+    ButtonList,
+    #[display("{0}")]
+    Stats(SubscriptionStatsFormat),
+}
+
+/// List of `Inline Keyboard` buttons for `inactive_subscriptions` bot command.
+/// `Months` is how long a channel has to be silent to be shown.
+#[derive(Debug, Clone, Serialize, Deserialize, Display, Default)]
+pub enum InactiveSubscriptionsCommandButtons {
+    #[display("{} 💤")]
+    #[display(style = "Title case")]
+    #[default]
+    ButtonList,
+    #[display("{0} months")]
+    Months(u8),
+}
+
+/// Buttons under a page of results.
+/// `id` points to results saved in `ResultCache`, `page` starts from `0`.
+#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+pub(crate) enum PageButtons {
+    #[display("Page {page}")]
+    Page { id: String, page: usize },
+    #[display("Close ❌")]
+    Close { id: String },
+}
+
 /// Main wrapper that includes all available keyboards.
 #[derive(Debug, Clone, Serialize, Deserialize, Display)]
 pub(crate) enum Buttons {
@@ -129,6 +288,16 @@ pub(crate) enum Buttons {
     ListButtons(ListCommandButtons),
     #[display("{0}")]
     SearchVideoInPlaylistsButtons(SearchVideoInPlaylistsCommandButtons),
+    #[display("{0}")]
+    ExportButtons(ExportCommandButtons),
+    #[display("{0}")]
+    PlaylistStatsButtons(PlaylistStatsCommandButtons),
+    #[display("{0}")]
+    SubscriptionStatsButtons(SubscriptionStatsCommandButtons),
+    #[display("{0}")]
+    InactiveSubscriptionsButtons(InactiveSubscriptionsCommandButtons),
+    #[display("{0}")]
+    PageButtons(PageButtons),
 }
 
 #[allow(clippy::unwrap_used)]
@@ -145,6 +314,20 @@ mod tests {
     fn serialize_enum_test() {
         assert_eq!(SearchInOptions.to_string(), "Search in 💳");
         assert_eq!(ButtonList.button_text(), "Button list 🔎");
+        assert_eq!(
+            Sorting::ItemCount(SortOrder::Descending).button_text(),
+            "Item count 📼 ⬇"
+        );
+        // `Telegram` limits callback data to 64 bytes.
+        let sorting = ListCommandButtons::Sorting(Sorting::LastActivity(SortOrder::Descending));
+        let match_mode = SearchVideoInPlaylistsCommandButtons::MatchMode(MatchMode::AllWords);
+        for b in [
+            Buttons::ListButtons(sorting),
+            Buttons::SearchVideoInPlaylistsButtons(match_mode),
+        ] {
+            let data = serde_json::to_string(&b).unwrap();
+            assert!(data.len() <= 64, "'{data}' is {} bytes", data.len());
+        }
     }
 
     #[test]
@@ -0,0 +1,134 @@
+/// Lowercase `text`, with `ё` treated as `е`, since they are often used interchangeably.
+pub(crate) fn fold_case(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c == 'ё' { 'е' } else { c })
+        .collect()
+}
+
+/// Distinct case folded words of `text`, in order they appear in.
+pub(crate) fn query_words(text: &str) -> Vec<String> {
+    let mut words: Vec<String> = vec![];
+    for word in split_words(&fold_case(text)) {
+        if !words.iter().any(|w| w == word) {
+            words.push(word.to_owned());
+        }
+    }
+    words
+}
+
+fn split_words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// Average similarity of each word of `words` to the most similar word of `text`.
+/// `text` must be case folded.
+pub(crate) fn fuzzy_score(words: &[String], text: &str) -> f64 {
+    if words.is_empty() {
+        return 0.0;
+    }
+    let text_words: Vec<_> = split_words(text).collect();
+    let best_similarity = |word: &String| {
+        text_words
+            .iter()
+            .map(|t| similarity(word, t))
+            .fold(0.0, f64::max)
+    };
+    words.iter().map(best_similarity).sum::<f64>() / words.len() as f64
+}
+
+/// `1.0` if `text_word` contains `word`, otherwise edit distance scaled to `0.0..1.0`.
+fn similarity(word: &str, text_word: &str) -> f64 {
+    if text_word.contains(word) {
+        return 1.0;
+    }
+    let longest = word.chars().count().max(text_word.chars().count());
+    1.0 - edit_distance(word, text_word) as f64 / longest as f64
+}
+
+/// Levenshtein distance, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev_row[j] + usize::from(a_char != *b_char);
+            row[j + 1] = substitution.min(prev_row[j + 1] + 1).min(row[j] + 1);
+        }
+        prev_row = row;
+    }
+    prev_row[b.len()]
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::keyboards::types::MatchMode;
+    use crate::model::matching::types::Matcher;
+
+    use super::*;
+
+    fn score(match_mode: MatchMode, text_to_search: &str, text: &str) -> Option<f64> {
+        Matcher::new(match_mode, text_to_search)
+            .unwrap()
+            .score(text)
+    }
+
+    #[test]
+    fn edit_distance_test() {
+        assert_eq!(edit_distance("cyberpunk", "cyberpunk"), 0);
+        assert_eq!(edit_distance("cyberpnk", "cyberpunk"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("обзор", "обзоры"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn query_words_test() {
+        assert_eq!(
+            query_words("Обзор, ёлки и ОБЗОР cyberpunk-2077"),
+            ["обзор", "елки", "и", "cyberpunk", "2077"]
+        );
+    }
+
+    #[test]
+    fn exact_and_words_test() {
+        let title = "Интервью с разработчиками Cyberpunk 2077";
+
+        assert!(score(MatchMode::Exact, "CYBERPUNK 2077", title).is_some());
+        assert!(score(MatchMode::Exact, "2077 cyberpunk", title).is_none());
+        assert!(
+            score(MatchMode::Exact, "cyberpunk", "Cyberpunk").unwrap()
+                > score(MatchMode::Exact, "cyberpunk", title).unwrap()
+        );
+
+        assert!(score(MatchMode::AllWords, "2077 cyberpunk", title).is_some());
+        assert!(score(MatchMode::AllWords, "cyberpunk witcher", title).is_none());
+
+        let half = score(MatchMode::AnyWord, "cyberpunk witcher", title).unwrap();
+        assert!((half - 0.5).abs() < f64::EPSILON);
+        assert!(score(MatchMode::AnyWord, "witcher gwent", title).is_none());
+    }
+
+    #[test]
+    fn fuzzy_test() {
+        let title = "Обзор Cyberpunk 2077";
+
+        assert!(score(MatchMode::Fuzzy, "cyberpnk", title).is_some());
+        assert!(score(MatchMode::Fuzzy, "обзоры киберпанк", title).is_none());
+        assert!(score(MatchMode::Fuzzy, "ОБЗОРЫ cyberpunk", title).is_some());
+        assert!(score(MatchMode::Fuzzy, "witcher", title).is_none());
+        assert!(score(MatchMode::Fuzzy, "Ёж", "еж").is_some());
+    }
+
+    #[test]
+    fn regex_test() {
+        assert!(score(MatchMode::Regex, r"cyberpunk \d{4}", "Обзор Cyberpunk 2077").is_some());
+        assert!(score(MatchMode::Regex, "^cyberpunk", "Обзор Cyberpunk 2077").is_none());
+        assert!(Matcher::new(MatchMode::Regex, "(unclosed").is_err());
+        assert!(Matcher::new(MatchMode::Fuzzy, "(unclosed").is_ok());
+    }
+}
@@ -0,0 +1,69 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::model::keyboards::types::MatchMode;
+use crate::model::matching::funcs::{fold_case, fuzzy_score, query_words};
+
+/// Fuzzy match is accepted if words of text to search are at least this similar to a title.
+pub(crate) const FUZZY_THRESHOLD: f64 = 0.7;
+/// Limits memory a user's regular expression may take once compiled.
+pub(crate) const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Text to search, prepared once for its `MatchMode`.
+#[derive(Debug, Clone)]
+pub(crate) enum Matcher {
+    Exact(String),
+    AllWords(Vec<String>),
+    AnyWord(Vec<String>),
+    Fuzzy(Vec<String>),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Fails only if `match_mode` is `MatchMode::Regex` and `text_to_search` isn't a valid one.
+    pub(crate) fn new(match_mode: MatchMode, text_to_search: &str) -> Result<Self, regex::Error> {
+        let matcher = match match_mode {
+            MatchMode::Exact => Matcher::Exact(fold_case(text_to_search)),
+            MatchMode::AllWords => Matcher::AllWords(query_words(text_to_search)),
+            MatchMode::AnyWord => Matcher::AnyWord(query_words(text_to_search)),
+            MatchMode::Fuzzy => Matcher::Fuzzy(query_words(text_to_search)),
+            MatchMode::Regex => Matcher::Regex(
+                RegexBuilder::new(text_to_search)
+                    .case_insensitive(true)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .build()?,
+            ),
+        };
+        Ok(matcher)
+    }
+
+    /// `None` if `text` doesn't match, otherwise a score from `0.0` to `1.0`.
+    /// Better matches get higher scores.
+    pub(crate) fn score(&self, text: &str) -> Option<f64> {
+        let len = |s: &str| s.chars().count().max(1) as f64;
+        let coverage = |matched: usize, text: &str| (matched as f64 / len(text)).min(1.0);
+        match self {
+            Matcher::Exact(query) => {
+                let text = fold_case(text);
+                text.contains(query.as_str())
+                    .then(|| coverage(query.chars().count(), &text))
+            }
+            Matcher::AllWords(words) => {
+                let text = fold_case(text);
+                let matched = words.iter().all(|w| text.contains(w.as_str()));
+                let matched_len = words.iter().map(|w| w.chars().count()).sum();
+                matched.then(|| coverage(matched_len, &text))
+            }
+            Matcher::AnyWord(words) => {
+                let text = fold_case(text);
+                let matched = words.iter().filter(|w| text.contains(w.as_str())).count();
+                (matched > 0).then(|| matched as f64 / words.len() as f64)
+            }
+            Matcher::Fuzzy(words) => {
+                Some(fuzzy_score(words, &fold_case(text))).filter(|s| *s >= FUZZY_THRESHOLD)
+            }
+            Matcher::Regex(regex) => regex
+                .find(text)
+                .map(|m| coverage(m.as_str().chars().count(), text)),
+        }
+    }
+}
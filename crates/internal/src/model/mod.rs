@@ -1,23 +1,43 @@
 pub(crate) mod auth_server;
-pub(crate) mod db;
+pub(crate) mod config {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod db {
+    pub(crate) mod funcs;
+    pub(crate) mod traits;
+    pub(crate) mod types;
+}
 pub(crate) mod errors;
 pub(crate) mod utils;
 pub(crate) mod dialogue {
     pub(crate) mod funcs;
+    pub(crate) mod traits;
     pub(crate) mod types;
 }
 pub(crate) mod youtube {
     pub(crate) mod types;
     pub(crate) mod funcs {
         pub(crate) mod common;
+        pub(crate) mod export_cmd;
+        pub(crate) mod inactive_cmd;
+        pub(crate) mod last_activity;
         pub(crate) mod list_cmd;
+        pub(crate) mod playlist_stats_cmd;
         pub(crate) mod search_cmd;
         pub(crate) mod search_videos_in_playlists;
+        pub(crate) mod snapshot_cmd;
+        pub(crate) mod uploads;
+        pub(crate) mod video_details;
     }
     pub(crate) mod traits;
 }
 pub(crate) mod net {
     pub(crate) mod funcs;
+    #[allow(clippy::unwrap_used)]
+    #[allow(clippy::expect_used)]
+    #[cfg(test)]
+    pub(crate) mod mock_server;
     pub(crate) mod traits;
     pub(crate) mod types;
 }
@@ -30,13 +50,53 @@ pub(crate) mod keyboards {
     pub(crate) mod traits;
     pub(crate) mod types;
 }
+pub(crate) mod export {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod snapshot {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod duplicates {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod matching {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod inactive {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod playlist_stats {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod subscription_stats {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
+pub(crate) mod pagination {
+    pub(crate) mod funcs;
+    pub(crate) mod types;
+}
 pub(crate) mod handlers {
     pub(crate) mod commands;
+    pub(crate) mod inline_query;
     pub(crate) mod text;
     pub(crate) mod callback {
         pub(crate) mod common;
+        pub(crate) mod duplicates_cmd;
+        pub(crate) mod export_cmd;
+        pub(crate) mod inactive_cmd;
         pub(crate) mod list_cmd;
+        pub(crate) mod pagination;
+        pub(crate) mod playlist_stats_cmd;
         pub(crate) mod search_cmd;
         pub(crate) mod search_videos_in_playlits;
+        pub(crate) mod snapshot_cmd;
+        pub(crate) mod subscription_stats_cmd;
     }
 }
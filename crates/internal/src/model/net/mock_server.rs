@@ -0,0 +1,240 @@
+//! In-process mock of `YouTube Data API` and `Google OAuth` token endpoint.
+//! Replays fixtures from `test_assets/`, so tests that make requests can run offline.
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::extract::{Form, Query};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use serde_json::{json, Value};
+
+use crate::model::net::types::{YouTubeClient, IDS_PER_REQUEST};
+
+/// The only `access token` accepted by mock `YouTube API`.
+pub(crate) const MOCK_ACCESS_TOKEN: &str = "mock-access-token";
+
+/// The only `refresh token` accepted by mock token endpoint.
+pub(crate) const MOCK_REFRESH_TOKEN: &str = "mock-refresh-token";
+
+/// The only `auth code` accepted by mock token endpoint.
+pub(crate) const MOCK_AUTH_CODE: &str = "mock-auth-code";
+
+/// Items per page, small enough to make every fixture span several pages.
+pub(crate) const MOCK_PAGE_SIZE: usize = 2;
+
+const SUBSCRIPTIONS: &str =
+    include_str!("../../../../../test_assets/subscription_list_json_response.json");
+const PLAYLISTS: &str = include_str!("../../../../../test_assets/playlist_list_json_response.json");
+const PLAYLIST_ITEMS: &str =
+    include_str!("../../../../../test_assets/playlist_item_list_json_response.json");
+const MY_CHANNEL: &str = include_str!("../../../../../test_assets/channel_list_json_response.json");
+const CHANNEL_DETAILS: &str =
+    include_str!("../../../../../test_assets/channel_details_json_response.json");
+const LIKED_VIDEOS: &str = include_str!("../../../../../test_assets/video_list_json_response.json");
+const VIDEO_DETAILS: &str =
+    include_str!("../../../../../test_assets/video_details_json_response.json");
+const ACCESS_TOKEN: &str =
+    include_str!("../../../../../test_assets/access_token_json_response.json");
+
+/// Running mock server. It's stopped together with test's runtime.
+pub(crate) struct MockYouTubeApi {
+    /// Client that sends all requests to this server.
+    pub(crate) client: YouTubeClient,
+    /// Replaces `GET_ACCESS_TOKEN_URL`.
+    pub(crate) token_url: String,
+    api_requests: Arc<AtomicUsize>,
+}
+
+impl MockYouTubeApi {
+    /// Starts server on a free local port. Must be called inside `tokio` runtime.
+    pub(crate) fn start() -> Self {
+        let api_requests = Arc::new(AtomicUsize::new(0));
+        let api = Router::new()
+            .route("/subscriptions", get(subscriptions))
+            .route("/playlists", get(playlists))
+            .route("/playlistItems", get(playlist_items))
+            .route("/videos", get(videos))
+            .route("/channels", get(channels));
+        let router = Router::new()
+            .nest("/youtube/v3", api)
+            .route("/token", post(token))
+            .layer(Extension(api_requests.clone()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(router.into_make_service());
+        tokio::spawn(server);
+
+        Self {
+            client: YouTubeClient::new(&format!("http://{addr}/youtube/v3")),
+            token_url: format!("http://{addr}/token"),
+            api_requests,
+        }
+    }
+
+    /// How many pages were requested from `YouTube API` so far.
+    pub(crate) fn api_requests(&self) -> usize {
+        self.api_requests.load(Ordering::SeqCst)
+    }
+}
+
+type Params = Query<HashMap<String, String>>;
+
+async fn subscriptions(counter: Extension<Arc<AtomicUsize>>, h: HeaderMap, q: Params) -> Response {
+    list_response(&counter, &h, &q, SUBSCRIPTIONS, |_| true)
+}
+
+async fn playlists(counter: Extension<Arc<AtomicUsize>>, h: HeaderMap, q: Params) -> Response {
+    list_response(&counter, &h, &q, PLAYLISTS, |_| true)
+}
+
+async fn playlist_items(counter: Extension<Arc<AtomicUsize>>, h: HeaderMap, q: Params) -> Response {
+    let playlist_id = q.get("playlistId").cloned().unwrap_or_default();
+    // Uploads playlist of a channel without videos doesn't exist.
+    let uploads = fixture_items(PLAYLIST_ITEMS)
+        .into_iter()
+        .map(|mut item| item["snippet"]["playlistId"].take());
+    let playlist_exists = fixture_items(PLAYLISTS)
+        .into_iter()
+        .map(|mut pl| pl["id"].take())
+        .chain(uploads)
+        .any(|id| id == playlist_id.as_str());
+    if !playlist_exists {
+        let message =
+            "The playlist identified with the request's playlistId parameter cannot be found.";
+        return error_response(StatusCode::NOT_FOUND, "playlistNotFound", message);
+    }
+    list_response(&counter, &h, &q, PLAYLIST_ITEMS, |item| {
+        item["snippet"]["playlistId"] == playlist_id.as_str()
+    })
+}
+
+async fn videos(counter: Extension<Arc<AtomicUsize>>, h: HeaderMap, q: Params) -> Response {
+    if let Some(ids) = q.get("id") {
+        let ids: Vec<_> = ids.split(',').collect();
+        if ids.len() > IDS_PER_REQUEST {
+            let message = "The request specifies too many video ids.";
+            return error_response(StatusCode::BAD_REQUEST, "tooManyIds", message);
+        }
+        // Unknown ids are silently skipped, just like deleted videos.
+        return list_response(&counter, &h, &q, VIDEO_DETAILS, |video| {
+            ids.iter().any(|id| video["id"] == *id)
+        });
+    }
+    if q.get("myRating").map(String::as_str) != Some("like") {
+        let message = "No filter selected. Expected one of: myRating, id, chart.";
+        return error_response(StatusCode::BAD_REQUEST, "missingRequiredParameter", message);
+    }
+    list_response(&counter, &h, &q, LIKED_VIDEOS, |_| true)
+}
+
+async fn channels(counter: Extension<Arc<AtomicUsize>>, h: HeaderMap, q: Params) -> Response {
+    if let Some(ids) = q.get("id") {
+        let ids: Vec<_> = ids.split(',').collect();
+        if ids.len() > IDS_PER_REQUEST {
+            let message = "The request specifies too many channel ids.";
+            return error_response(StatusCode::BAD_REQUEST, "tooManyIds", message);
+        }
+        return list_response(&counter, &h, &q, CHANNEL_DETAILS, |chan| {
+            ids.iter().any(|id| chan["id"] == *id)
+        });
+    }
+    if q.get("mine").map(String::as_str) != Some("true") {
+        let message = "No filter selected. Expected one of: mine, id, forHandle.";
+        return error_response(StatusCode::BAD_REQUEST, "missingRequiredParameter", message);
+    }
+    list_response(&counter, &h, &q, MY_CHANNEL, |_| true)
+}
+
+/// Page of `fixture` items that pass `filter`.
+/// Page tokens are `page-N`, last page has no `nextPageToken`.
+fn list_response(
+    api_requests: &AtomicUsize,
+    headers: &HeaderMap,
+    query: &HashMap<String, String>,
+    fixture: &str,
+    filter: impl Fn(&Value) -> bool,
+) -> Response {
+    api_requests.fetch_add(1, Ordering::SeqCst);
+
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if bearer != Some(MOCK_ACCESS_TOKEN) {
+        return error_response(StatusCode::UNAUTHORIZED, "authError", "Invalid Credentials");
+    }
+
+    let page_index = match query.get("pageToken") {
+        None => 0,
+        Some(token) => match token.strip_prefix("page-").map(str::parse::<usize>) {
+            Some(Ok(i)) => i,
+            _ => {
+                let message = "The request specifies an invalid page token.";
+                return error_response(StatusCode::BAD_REQUEST, "invalidPageToken", message);
+            }
+        },
+    };
+
+    let items: Vec<_> = fixture_items(fixture).into_iter().filter(filter).collect();
+    let start = page_index * MOCK_PAGE_SIZE;
+    let mut page: Value = serde_json::from_str(fixture).unwrap();
+    page["items"] = items
+        .iter()
+        .skip(start)
+        .take(MOCK_PAGE_SIZE)
+        .cloned()
+        .collect();
+    page["pageInfo"] = json!({ "totalResults": items.len(), "resultsPerPage": MOCK_PAGE_SIZE });
+    if let Some(page) = page.as_object_mut() {
+        page.remove("nextPageToken");
+        if start + MOCK_PAGE_SIZE < items.len() {
+            let next_page_token = format!("page-{}", page_index + 1);
+            page.insert("nextPageToken".to_owned(), next_page_token.into());
+        }
+    }
+    Json(page).into_response()
+}
+
+fn fixture_items(fixture: &str) -> Vec<Value> {
+    let mut page: Value = serde_json::from_str(fixture).unwrap();
+    match page["items"].take() {
+        Value::Array(items) => items,
+        _ => vec![],
+    }
+}
+
+/// Error in the same format as real `Google API` returns.
+fn error_response(status: StatusCode, reason: &str, message: &str) -> Response {
+    let body = json!({
+        "error": {
+            "code": status.as_u16(),
+            "message": message,
+            "errors": [{ "message": message, "domain": "youtube.api", "reason": reason }]
+        }
+    });
+    (status, Json(body)).into_response()
+}
+
+async fn token(Form(params): Form<HashMap<String, String>>) -> Response {
+    let param = |key: &str| params.get(key).map(String::as_str);
+    let valid_grant = match param("grant_type") {
+        Some("refresh_token") => param("refresh_token") == Some(MOCK_REFRESH_TOKEN),
+        Some("authorization_code") => {
+            param("code") == Some(MOCK_AUTH_CODE) && param("code_verifier").is_some()
+        }
+        _ => false,
+    };
+    if !valid_grant {
+        let body = json!({ "error": "invalid_grant", "error_description": "Bad Request" });
+        return (StatusCode::BAD_REQUEST, Json(body)).into_response();
+    }
+    ([(header::CONTENT_TYPE, "application/json")], ACCESS_TOKEN).into_response()
+}
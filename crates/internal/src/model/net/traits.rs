@@ -1,14 +1,15 @@
 use crate::model::net::types::{
-    PlaylistItemRequester, PlaylistRequester, SubscriptionRequester, YOUTUBE_PLAYLISTS_API,
-    YOUTUBE_PLAYLIST_ITEMS_API, YOUTUBE_SUBSCRIPTIONS_API,
+    ChannelRequester, LikedVideoRequester, MyChannelRequester, PlaylistItemRequester,
+    PlaylistRequester, SubscriptionRequester, VideoRequester, YouTubeClient, CHANNELS_ENDPOINT,
+    PLAYLISTS_ENDPOINT, PLAYLIST_ITEMS_ENDPOINT, SUBSCRIPTIONS_ENDPOINT, VIDEOS_ENDPOINT,
 };
 use crate::model::youtube::traits::{IntoSearchableItem, Searchable};
 use google_youtube3::api::{
-    Playlist, PlaylistItem, PlaylistItemListResponse, PlaylistListResponse, Subscription,
-    SubscriptionListResponse,
+    Channel, ChannelListResponse, Playlist, PlaylistItem, PlaylistItemListResponse,
+    PlaylistListResponse, Subscription, SubscriptionListResponse, Video, VideoListResponse,
 };
 use maptypings::WrapInRes;
-use reqwest::{Client, RequestBuilder};
+use reqwest::RequestBuilder;
 use std::any::type_name;
 
 // TODO : Choose a better naming.
@@ -18,7 +19,7 @@ pub(crate) trait YouTubeApiRequestBuilder {
 
     fn build_req(
         &self,
-        client: &Client,
+        client: &YouTubeClient,
         access_token: &str,
         page_token: Option<String>,
     ) -> eyre::Result<RequestBuilder>;
@@ -29,12 +30,13 @@ impl YouTubeApiRequestBuilder for SubscriptionRequester {
 
     fn build_req(
         &self,
-        client: &Client,
+        client: &YouTubeClient,
         access_token: &str,
         page_token: Option<String>,
     ) -> eyre::Result<RequestBuilder> {
         let mut req = client
-            .get(reqwest::Url::parse(YOUTUBE_SUBSCRIPTIONS_API)?)
+            .http
+            .get(client.endpoint_url(SUBSCRIPTIONS_ENDPOINT)?)
             .query(&[("part", "contentDetails,id,snippet")])
             .query(&[("maxResults", "50"), ("mine", "true")])
             .header(
@@ -54,12 +56,13 @@ impl YouTubeApiRequestBuilder for PlaylistRequester {
 
     fn build_req(
         &self,
-        client: &Client,
+        client: &YouTubeClient,
         access_token: &str,
         page_token: Option<String>,
     ) -> eyre::Result<RequestBuilder> {
         let mut req = client
-            .get(reqwest::Url::parse(YOUTUBE_PLAYLISTS_API)?)
+            .http
+            .get(client.endpoint_url(PLAYLISTS_ENDPOINT)?)
             .query(&[("part", "contentDetails,id,snippet,status")])
             .query(&[("maxResults", "50"), ("mine", "true")])
             .header(
@@ -79,7 +82,7 @@ impl<'a> YouTubeApiRequestBuilder for PlaylistItemRequester<'a> {
 
     fn build_req(
         &self,
-        client: &Client,
+        client: &YouTubeClient,
         access_token: &str,
         page_token: Option<String>,
     ) -> eyre::Result<RequestBuilder> {
@@ -90,7 +93,8 @@ impl<'a> YouTubeApiRequestBuilder for PlaylistItemRequester<'a> {
         );
 
         let mut req = client
-            .get(reqwest::Url::parse(YOUTUBE_PLAYLIST_ITEMS_API)?)
+            .http
+            .get(client.endpoint_url(PLAYLIST_ITEMS_ENDPOINT)?)
             .query(&[("part", "contentDetails,id,snippet,status")])
             .query(&[("maxResults", "50"), ("playlistId", self.playlist_id)])
             .header(
@@ -108,6 +112,110 @@ impl<'a> YouTubeApiRequestBuilder for PlaylistItemRequester<'a> {
     }
 }
 
+impl YouTubeApiRequestBuilder for LikedVideoRequester {
+    type Target = VideoListResponse;
+
+    fn build_req(
+        &self,
+        client: &YouTubeClient,
+        access_token: &str,
+        page_token: Option<String>,
+    ) -> eyre::Result<RequestBuilder> {
+        let mut req = client
+            .http
+            .get(client.endpoint_url(VIDEOS_ENDPOINT)?)
+            .query(&[("part", "contentDetails,id,snippet,statistics,status")])
+            .query(&[("maxResults", "50"), ("myRating", "like")])
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {access_token}"),
+            )
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(page) = page_token {
+            req = req.query(&[("pageToken", &page)]);
+        }
+        req.in_ok()
+    }
+}
+
+impl YouTubeApiRequestBuilder for MyChannelRequester {
+    type Target = ChannelListResponse;
+
+    fn build_req(
+        &self,
+        client: &YouTubeClient,
+        access_token: &str,
+        page_token: Option<String>,
+    ) -> eyre::Result<RequestBuilder> {
+        let mut req = client
+            .http
+            .get(client.endpoint_url(CHANNELS_ENDPOINT)?)
+            .query(&[("part", "contentDetails,id,snippet")])
+            .query(&[("mine", "true")])
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {access_token}"),
+            )
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(page) = page_token {
+            req = req.query(&[("pageToken", &page)]);
+        }
+        req.in_ok()
+    }
+}
+
+impl<'a> YouTubeApiRequestBuilder for VideoRequester<'a> {
+    type Target = VideoListResponse;
+
+    fn build_req(
+        &self,
+        client: &YouTubeClient,
+        access_token: &str,
+        page_token: Option<String>,
+    ) -> eyre::Result<RequestBuilder> {
+        let mut req = client
+            .http
+            .get(client.endpoint_url(VIDEOS_ENDPOINT)?)
+            .query(&[("part", "contentDetails,id,statistics,status")])
+            .query(&[("maxResults", "50"), ("id", &self.video_ids.join(","))])
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {access_token}"),
+            )
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(page) = page_token {
+            req = req.query(&[("pageToken", &page)]);
+        }
+        req.in_ok()
+    }
+}
+
+impl<'a> YouTubeApiRequestBuilder for ChannelRequester<'a> {
+    type Target = ChannelListResponse;
+
+    fn build_req(
+        &self,
+        client: &YouTubeClient,
+        access_token: &str,
+        page_token: Option<String>,
+    ) -> eyre::Result<RequestBuilder> {
+        let mut req = client
+            .http
+            .get(client.endpoint_url(CHANNELS_ENDPOINT)?)
+            .query(&[("part", "contentDetails,id,snippet")])
+            .query(&[("maxResults", "50"), ("id", &self.channel_ids.join(","))])
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {access_token}"),
+            )
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(page) = page_token {
+            req = req.query(&[("pageToken", &page)]);
+        }
+        req.in_ok()
+    }
+}
+
 //pub(crate) struct ItemSearchRes<S : Searchable>
 //{
 //    pub(crate) items : Option<Vec<S>>,
@@ -120,8 +228,6 @@ pub(crate) trait YouTubeApiResponsePage {
 
     fn next_page_token(&self) -> Option<String>;
 
-    fn total_results(&self) -> Option<u32>;
-
     fn items(self) -> Option<Vec<Self::Item>>;
 }
 
@@ -132,10 +238,6 @@ impl YouTubeApiResponsePage for SubscriptionListResponse {
         self.next_page_token.clone()
     }
 
-    fn total_results(&self) -> Option<u32> {
-        self.page_info.as_ref()?.total_results?.try_into().ok()
-    }
-
     fn items(self) -> Option<Vec<Self::Item>> {
         self.items
     }
@@ -148,10 +250,6 @@ impl YouTubeApiResponsePage for PlaylistListResponse {
         self.next_page_token.clone()
     }
 
-    fn total_results(&self) -> Option<u32> {
-        self.page_info.as_ref()?.total_results?.try_into().ok()
-    }
-
     fn items(self) -> Option<Vec<Self::Item>> {
         self.items
     }
@@ -164,8 +262,28 @@ impl YouTubeApiResponsePage for PlaylistItemListResponse {
         self.next_page_token.clone()
     }
 
-    fn total_results(&self) -> Option<u32> {
-        self.page_info.as_ref()?.total_results?.try_into().ok()
+    fn items(self) -> Option<Vec<Self::Item>> {
+        self.items
+    }
+}
+
+impl YouTubeApiResponsePage for ChannelListResponse {
+    type Item = Channel;
+
+    fn next_page_token(&self) -> Option<String> {
+        self.next_page_token.clone()
+    }
+
+    fn items(self) -> Option<Vec<Self::Item>> {
+        self.items
+    }
+}
+
+impl YouTubeApiResponsePage for VideoListResponse {
+    type Item = Video;
+
+    fn next_page_token(&self) -> Option<String> {
+        self.next_page_token.clone()
     }
 
     fn items(self) -> Option<Vec<Self::Item>> {
@@ -179,12 +297,11 @@ impl YouTubeApiResponsePage for PlaylistItemListResponse {
 mod tests {
     use super::*;
     use crate::model::net::types::{
-        PlaylistItemRequester, PlaylistRequester, SubscriptionRequester,
+        LikedVideoRequester, PlaylistItemRequester, PlaylistRequester, SubscriptionRequester,
     };
     use env_logger::fmt::Formatter;
     use error_traits::PassErrWith;
     use log::Record;
-    use reqwest::Client;
     use std::io::Write;
 
     fn format_logs(buf: &mut Formatter, record: &Record) -> std::io::Result<()> {
@@ -218,7 +335,7 @@ mod tests {
             .try_init()
             .ok();
 
-        let client = Client::new();
+        let client = YouTubeClient::default();
         let playlist_id = "Ljyu90PKhi75sDReqOVLaGpwTaogl68CjhmZWOKc";
 
         // Test `PlaylistItemRequester`.
@@ -251,5 +368,46 @@ mod tests {
             .build()
             .pass_err_with(|e| log::error!("error: {e:?}"))
             .unwrap();
+
+        // Test `LikedVideoRequester`.
+        let (req_builder, page_token) = (LikedVideoRequester, Some("CDIQAA".to_owned()));
+        let build = req_builder
+            .build_req(&client, access_token, page_token)
+            .pass_err_with(|e| log::error!("error: {e:?}"))
+            .unwrap()
+            .build()
+            .pass_err_with(|e| log::error!("error: {e:?}"))
+            .unwrap();
+        assert_eq!(
+            build.url().as_str(),
+            "https://www.googleapis.com/youtube/v3/videos\
+            ?part=contentDetails%2Cid%2Csnippet%2Cstatistics%2Cstatus\
+            &maxResults=50&myRating=like&pageToken=CDIQAA"
+        );
+
+        // Test `ChannelRequester`.
+        let channel_ids = [
+            "UCYmna5rFHIesFteksAvFOfg".to_owned(),
+            "UCdoadna9HFHsxXWhafhNvKw".to_owned(),
+        ];
+        let (req_builder, page_token) = (
+            ChannelRequester {
+                channel_ids: &channel_ids,
+            },
+            None,
+        );
+        let build = req_builder
+            .build_req(&client, access_token, page_token)
+            .pass_err_with(|e| log::error!("error: {e:?}"))
+            .unwrap()
+            .build()
+            .pass_err_with(|e| log::error!("error: {e:?}"))
+            .unwrap();
+        assert_eq!(
+            build.url().as_str(),
+            "https://www.googleapis.com/youtube/v3/channels\
+            ?part=contentDetails%2Cid%2Csnippet\
+            &maxResults=50&id=UCYmna5rFHIesFteksAvFOfg%2CUCdoadna9HFHsxXWhafhNvKw"
+        );
     }
 }
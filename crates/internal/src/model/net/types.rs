@@ -1,10 +1,8 @@
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-/// Custom query separator to encode multiple key-value pairs.into one url query value (key=value&key=value).
-pub const QUERY_SEPARATOR: &str = "xplusx";
+use serde::{Deserialize, Serialize};
 
-/// State code to check in incoming oauth2 response.
-pub const STATE_CODE: &str = env!("STATE_CODE");
+use crate::model::config::types::AppConfig;
 
 /// Separator placed between key and value.
 pub const KV_SEP: &str = "=";
@@ -22,12 +20,90 @@ pub const GET_ACCESS_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 /// Url for revoking `access token`.
 pub const REVOKE_ACCESS_TOKEN_URL: &str = "https://oauth2.googleapis.com/revoke";
 
-pub const YOUTUBE_SUBSCRIPTIONS_API: &str = "https://www.googleapis.com/youtube/v3/subscriptions";
+/// Base url of `YouTube Data API`, endpoints are appended to it.
+pub const YOUTUBE_API_URL: &str = "https://www.googleapis.com/youtube/v3";
+
+pub(crate) const SUBSCRIPTIONS_ENDPOINT: &str = "subscriptions";
+
+pub(crate) const PLAYLISTS_ENDPOINT: &str = "playlists";
+
+pub(crate) const PLAYLIST_ITEMS_ENDPOINT: &str = "playlistItems";
+
+pub(crate) const VIDEOS_ENDPOINT: &str = "videos";
+
+pub(crate) const CHANNELS_ENDPOINT: &str = "channels";
+
+/// Max number of ids `videos.list` and `channels.list` accept in one request.
+pub(crate) const IDS_PER_REQUEST: usize = 50;
+
+/// How many times a request is repeated after a retryable error.
+pub(crate) const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry, it's doubled for each next one.
+pub(crate) const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Body of a failed `Google API` response.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct GoogleErrorResponse {
+    pub(crate) error: GoogleErrorBody,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct GoogleErrorBody {
+    #[serde(default)]
+    pub(crate) code: u16,
+    #[serde(default)]
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) errors: Vec<GoogleErrorDetail>,
+}
+
+/// One of the reasons of the failure, e.g. `quotaExceeded` or `playlistNotFound`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct GoogleErrorDetail {
+    #[serde(default)]
+    pub(crate) reason: String,
+    #[serde(default)]
+    pub(crate) domain: String,
+    #[serde(default)]
+    pub(crate) message: String,
+}
+
+/// `HTTP` client for `YouTube Data API`.
+/// Base url is configurable, so requests can be sent to a mock server in tests.
+#[derive(Clone, Debug)]
+pub(crate) struct YouTubeClient {
+    pub(crate) http: reqwest::Client,
+    /// Base url of `YouTube Data API`, without trailing slash.
+    pub(crate) api_url: String,
+}
+
+impl YouTubeClient {
+    pub(crate) fn new(api_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_owned(),
+        }
+    }
+
+    pub(crate) fn from_config(app_config: &AppConfig) -> Self {
+        Self::new(&app_config.youtube_api_url)
+    }
 
-pub const YOUTUBE_PLAYLISTS_API: &str = "https://youtube.googleapis.com/youtube/v3/playlists";
+    /// Full url of `endpoint`, e.g. `playlists`.
+    pub(crate) fn endpoint_url(&self, endpoint: &str) -> eyre::Result<reqwest::Url> {
+        Ok(reqwest::Url::parse(&format!(
+            "{}/{endpoint}",
+            self.api_url
+        ))?)
+    }
+}
 
-pub const YOUTUBE_PLAYLIST_ITEMS_API: &str =
-    "https://youtube.googleapis.com/youtube/v3/playlistItems";
+impl Default for YouTubeClient {
+    fn default() -> Self {
+        Self::new(YOUTUBE_API_URL)
+    }
+}
 
 // TODO : Choose a better naming and description.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -42,3 +118,28 @@ pub struct PlaylistRequester;
 pub struct PlaylistItemRequester<'a> {
     pub playlist_id: &'a str,
 }
+
+/// Requests videos liked by user.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LikedVideoRequester;
+
+/// Requests user's own channel.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MyChannelRequester;
+
+/// Videos uploaded to user's own channel.
+/// Their playlist is resolved with `MyChannelRequester`, then paged with `PlaylistItemRequester`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UploadsRequester;
+
+/// Requests details of videos with given ids, at most `IDS_PER_REQUEST` of them.
+#[derive(Clone, Debug)]
+pub struct VideoRequester<'a> {
+    pub video_ids: &'a [String],
+}
+
+/// Requests channels with given ids, at most `IDS_PER_REQUEST` of them.
+#[derive(Clone, Debug)]
+pub struct ChannelRequester<'a> {
+    pub channel_ids: &'a [String],
+}
@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use teloxide::types::InlineKeyboardMarkup;
+
+use crate::model::keyboards::funcs::inline_button;
+use crate::model::keyboards::types::{Buttons, PageButtons};
+use crate::model::pagination::types::{MAX_DESCRIPTION_LEN, RESULTS_PER_PAGE};
+use crate::model::utils::HTMLise;
+use crate::model::youtube::types::{Availability, SearchableItem, VideoDetails};
+
+/// Number of pages needed to show all results (at least one).
+pub(crate) fn page_count(result_count: usize) -> usize {
+    result_count.div_ceil(RESULTS_PER_PAGE).max(1)
+}
+
+/// Cuts text to `max_chars` characters, marking the cut with `…`.
+pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut_at, _)) => format!("{}…", &text[..cut_at]),
+        None => text.to_owned(),
+    }
+}
+
+/// `HTML` of one result. Text that comes from `YouTube` is escaped.
+pub(crate) fn render_item(item: &SearchableItem) -> String {
+    let title = item.title.as_deref().unwrap_or("No title 🤷‍♂️").to_escaped();
+    let descr = item
+        .description
+        .as_deref()
+        .map(|d| truncate(d, MAX_DESCRIPTION_LEN).to_escaped())
+        .unwrap_or_else(|| "No description 🤷‍♂️".to_owned());
+    let link = item.link.as_deref().unwrap_or("No link 🤷‍♂️").to_escaped();
+    let about = item
+        .about
+        .as_deref()
+        .map(|s| format!("\n\n{}", s.to_escaped()))
+        .unwrap_or_default();
+    let video = item
+        .video
+        .as_ref()
+        .map(|v| format!("\n\n{}", render_video_details(v)))
+        .unwrap_or_default();
+    format!("{}{video} \n\n{descr} \n\n{link}{about}", title.to_bold())
+}
+
+/// One line with duration, views, likes and privacy, or a warning if video can't be watched.
+pub(crate) fn render_video_details(video: &VideoDetails) -> String {
+    if video.availability == Availability::Unavailable {
+        return format!("🚫 {} (deleted or private)", "Unavailable".to_bold());
+    }
+    let mut parts = vec![];
+    if let Some(duration) = video.duration {
+        parts.push(format!("⏱ {}", format_duration(duration)));
+    }
+    if let Some(views) = video.view_count {
+        parts.push(format!("👁 {}", group_digits(views)));
+    }
+    if let Some(likes) = video.like_count {
+        parts.push(format!("👍 {}", group_digits(likes)));
+    }
+    match video.privacy_status.as_deref() {
+        Some("private") => parts.push(format!("🔒 {}", "Private".to_bold())),
+        Some("unlisted") => parts.push("🔗 Unlisted".to_owned()),
+        _ => {}
+    }
+    parts.join("  |  ")
+}
+
+/// `1:02:03` for an hour and more, `2:03` otherwise.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// `1234567` → `1 234 567`.
+fn group_digits(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(' ');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Text of one page of results. `page` starts from `0`, and is clamped to the last page.
+pub(crate) fn render_page(results: &[SearchableItem], page: usize) -> String {
+    let page = page.min(page_count(results.len()) - 1);
+    results
+        .iter()
+        .skip(page * RESULTS_PER_PAGE)
+        .take(RESULTS_PER_PAGE)
+        .map(render_item)
+        .collect::<Vec<_>>()
+        .join("\n\n➖➖➖➖➖\n\n")
+}
+
+/// Keyboard under a page of results: `◀`, `page x/y`, `▶` and `close`.
+/// Navigation row is omitted if everything fits into one page.
+pub(crate) fn page_kb(id: &str, page: usize, result_count: usize) -> InlineKeyboardMarkup {
+    let pages = page_count(result_count);
+    let page = page.min(pages - 1);
+    let page_button = |text: String, page| {
+        let id = id.to_owned();
+        inline_button(text, Buttons::PageButtons(PageButtons::Page { id, page }))
+    };
+
+    let mut kb = InlineKeyboardMarkup::default();
+    if pages > 1 {
+        if page > 0 {
+            kb = kb.append_to_row(0, page_button("◀".to_owned(), page - 1));
+        }
+        kb = kb.append_to_row(0, page_button(format!("{}/{pages}", page + 1), 0));
+        if page + 1 < pages {
+            kb = kb.append_to_row(0, page_button("▶".to_owned(), page + 1));
+        }
+    }
+    let close = PageButtons::Close { id: id.to_owned() };
+    kb.append_to_row(1, inline_button("Close ❌", Buttons::PageButtons(close)))
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use teloxide::types::InlineKeyboardButtonKind;
+
+    use crate::model::pagination::types::RESULTS_ID_LEN;
+    use crate::model::utils::random_string;
+
+    use super::*;
+
+    fn items(count: usize) -> Vec<SearchableItem> {
+        (0..count)
+            .map(|i| SearchableItem {
+                title: format!("title {i}").into(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn callback_data(kb: &InlineKeyboardMarkup) -> Vec<Vec<String>> {
+        kb.inline_keyboard
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|b| match &b.kind {
+                        InlineKeyboardButtonKind::CallbackData(d) => d.clone(),
+                        _ => panic!("not a callback button"),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn page_count_test() {
+        assert_eq!(page_count(0), 1);
+        assert_eq!(page_count(1), 1);
+        assert_eq!(page_count(RESULTS_PER_PAGE), 1);
+        assert_eq!(page_count(RESULTS_PER_PAGE + 1), 2);
+        assert_eq!(page_count(RESULTS_PER_PAGE * 100), 100);
+    }
+
+    #[test]
+    fn render_page_test() {
+        let results = items(RESULTS_PER_PAGE + 2);
+
+        let first = render_page(&results, 0);
+        assert!(first.contains("title 0"));
+        assert!(first.contains(&format!("title {}", RESULTS_PER_PAGE - 1)));
+        assert!(!first.contains(&format!("title {RESULTS_PER_PAGE}")));
+
+        let last = render_page(&results, 1);
+        assert!(last.contains(&format!("title {}", RESULTS_PER_PAGE + 1)));
+        assert!(!last.contains("title 0"));
+
+        assert_eq!(render_page(&results, 42), last);
+    }
+
+    #[test]
+    fn render_video_details_test() {
+        let video = VideoDetails {
+            duration: Some(Duration::from_secs(4325)),
+            view_count: Some(1_523_456),
+            like_count: Some(73),
+            privacy_status: Some("unlisted".to_owned()),
+            availability: Availability::Available,
+        };
+        assert_eq!(
+            render_video_details(&video),
+            "⏱ 1:12:05  |  👁 1 523 456  |  👍 73  |  🔗 Unlisted"
+        );
+
+        let unavailable = SearchableItem {
+            title: Some("Deleted video".to_owned()),
+            video: Some(VideoDetails {
+                availability: Availability::Unavailable,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(render_item(&unavailable).starts_with(
+            "<b>Deleted video</b>\n\n🚫 <b>Unavailable</b> (deleted or private) \n\n"
+        ));
+        assert_eq!(format_duration(Duration::from_secs(213)), "3:33");
+    }
+
+    #[test]
+    fn render_item_escapes_html_test() {
+        let item = SearchableItem {
+            title: Some("<Tom & Jerry>".to_owned()),
+            description: Some("a < b".to_owned()),
+            link: Some("https://youtu.be/x?a=1&b=2".to_owned()),
+            about: Some("<i>Channel</i>".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_item(&item),
+            "<b>&lt;Tom &amp; Jerry&gt;</b> \n\na &lt; b \n\nhttps://youtu.be/x?a=1&amp;b=2\n\n\
+            &lt;i&gt;Channel&lt;/i&gt;"
+        );
+    }
+
+    #[test]
+    fn truncate_test() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("Привет, мир", 6), "Привет…");
+    }
+
+    #[test]
+    fn page_kb_test() {
+        let id = "AbCd1234";
+
+        let kb = page_kb(id, 0, RESULTS_PER_PAGE);
+        assert_eq!(kb.inline_keyboard.len(), 1);
+        assert_eq!(kb.inline_keyboard[0][0].text, "Close ❌");
+
+        let kb = page_kb(id, 0, RESULTS_PER_PAGE * 3);
+        let texts: Vec<_> = kb.inline_keyboard[0].iter().map(|b| &b.text).collect();
+        assert_eq!(texts, ["1/3", "▶"]);
+
+        let kb = page_kb(id, 1, RESULTS_PER_PAGE * 3);
+        let texts: Vec<_> = kb.inline_keyboard[0].iter().map(|b| &b.text).collect();
+        assert_eq!(texts, ["◀", "2/3", "▶"]);
+
+        let kb = page_kb(id, 2, RESULTS_PER_PAGE * 3);
+        let texts: Vec<_> = kb.inline_keyboard[0].iter().map(|b| &b.text).collect();
+        assert_eq!(texts, ["◀", "3/3"]);
+
+        let data = callback_data(&kb);
+        let prev: Buttons = serde_json::from_str(&data[0][0]).unwrap();
+        assert!(matches!(
+            prev,
+            Buttons::PageButtons(PageButtons::Page { id: ref i, page: 1 }) if i == id
+        ));
+    }
+
+    #[test]
+    fn callback_data_fits_telegram_limit_test() {
+        let id = random_string(RESULTS_ID_LEN);
+        let kb = page_kb(&id, 99_998, RESULTS_PER_PAGE * 100_000);
+        for data in callback_data(&kb).concat() {
+            assert!(data.len() <= 64, "'{data}' is {} bytes", data.len());
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use teloxide::types::UserId;
+use tokio::sync::{Mutex, OnceCell};
+
+use crate::model::utils::random_string;
+use crate::model::youtube::types::SearchableItem;
+
+/// How many results are shown on one page.
+pub(crate) const RESULTS_PER_PAGE: usize = 5;
+
+/// How long results stay available for paging.
+pub(crate) const RESULTS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Length of a random id results are saved under.
+/// Kept short, because `Telegram` limits callback data to 64 bytes.
+pub(crate) const RESULTS_ID_LEN: usize = 8;
+
+/// Longer descriptions are cut, so that a whole page fits into one message.
+pub(crate) const MAX_DESCRIPTION_LEN: usize = 300;
+
+/// How long user's subscriptions and playlists are reused by inline queries.
+/// Short, but enough to not request them again on every typed letter.
+pub(crate) const INLINE_RESULTS_TTL: Duration = Duration::from_secs(2 * 60);
+
+/// Maximum number of subscriptions or playlists, fetched for inline queries.
+pub(crate) const INLINE_FETCH_LIMIT: u32 = 1000;
+
+/// How long the full list is reused by `list` command with another sorting or limit.
+pub(crate) const LIST_RESULTS_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Maximum number of items fetched for `list` command.
+/// The whole collection is sorted, this only guards against runaway paging.
+/// When it's reached, `list` tells that only the fetched items were sorted.
+pub(crate) const LIST_FETCH_LIMIT: u32 = 5000;
+
+/// `Telegram` accepts no more than 50 results per inline query answer.
+pub(crate) const MAX_INLINE_RESULTS: usize = 50;
+
+/// How long (in seconds) `Telegram` may reuse an inline query answer.
+pub(crate) const INLINE_ANSWER_CACHE_TIME: u32 = 10;
+
+/// Shared between handlers as a `dptree` dependency.
+pub type WrappedResultCache = Arc<ResultCache>;
+
+/// Results with the user they were sent to.
+type OwnedResults = (Arc<Vec<SearchableItem>>, UserId);
+
+/// Shared results of a fetch, that may still be in progress.
+pub(crate) type SharedFetch = Arc<OnceCell<Arc<Vec<SearchableItem>>>>;
+
+/// Values with their expiration time (key -> (value, expires at)).
+/// Expired values are dropped on every insert.
+#[derive(Debug)]
+struct ExpiringMap<K, V>(Mutex<HashMap<K, (V, Instant)>>);
+
+impl<K, V> Default for ExpiringMap<K, V> {
+    fn default() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> ExpiringMap<K, V> {
+    /// Saves value under a given key, replacing previous one.
+    async fn insert(&self, key: K, value: V, ttl: Duration) {
+        let now = Instant::now();
+        let mut map = self.0.lock().await;
+        map.retain(|_, (_, expires_at)| *expires_at > now);
+        map.insert(key, (value, now + ttl));
+    }
+
+    /// Returns value only if it hasn't expired yet.
+    async fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let map = self.0.lock().await;
+        let (value, expires_at) = map.get(key)?;
+        (*expires_at > Instant::now()).then(|| value.clone())
+    }
+
+    /// Returns value that hasn't expired yet, or saves and returns a new one.
+    async fn get_or_insert_with(&self, key: K, ttl: Duration, new: impl FnOnce() -> V) -> V {
+        let now = Instant::now();
+        let mut map = self.0.lock().await;
+        map.retain(|_, (_, expires_at)| *expires_at > now);
+        let (value, _) = map.entry(key).or_insert_with(|| (new(), now + ttl));
+        value.clone()
+    }
+
+    /// Removes value only if `pred` holds for it. Returns `false` if nothing was removed.
+    async fn remove_if<Q>(&self, key: &Q, pred: impl FnOnce(&V) -> bool) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let mut map = self.0.lock().await;
+        let matches = map.get(key).is_some_and(|(value, _)| pred(value));
+        if matches {
+            map.remove(key);
+        }
+        matches
+    }
+}
+
+/// Keeps command results in memory, so that they can be browsed page by page.
+/// Only the user who got the results can browse them. Everything is lost on restart.
+#[derive(Default, Debug)]
+pub struct ResultCache {
+    /// Results that are browsed page by page, under random ids that go into callback data.
+    pages: ExpiringMap<String, OwnedResults>,
+    /// User's subscriptions and playlists for inline queries.
+    /// Kept apart from `pages`, so that no page id can reach them.
+    inline: ExpiringMap<UserId, SharedFetch>,
+    /// User's full lists for `list` command, sorted and cut only when shown.
+    /// Kept apart from `pages` for the same reason.
+    lists: ExpiringMap<String, Arc<Vec<SearchableItem>>>,
+}
+
+impl ResultCache {
+    /// Saves results of `owner` under a new random id and returns the id.
+    pub(crate) async fn save(
+        &self,
+        owner: UserId,
+        results: Vec<SearchableItem>,
+        ttl: Duration,
+    ) -> String {
+        let id = random_string(RESULTS_ID_LEN);
+        self.pages
+            .insert(id.clone(), (results.into(), owner), ttl)
+            .await;
+        id
+    }
+
+    /// Returns results only if they haven't expired yet and belong to `user_id`.
+    pub(crate) async fn get(&self, id: &str, user_id: UserId) -> Option<Arc<Vec<SearchableItem>>> {
+        let (results, owner) = self.pages.get(id).await?;
+        (owner == user_id).then_some(results)
+    }
+
+    /// Removes results only if they belong to `user_id`. Returns `false` if nothing was removed.
+    pub(crate) async fn remove(&self, id: &str, user_id: UserId) -> bool {
+        self.pages
+            .remove_if(id, |(_, owner)| *owner == user_id)
+            .await
+    }
+
+    /// Saves user's full list under a given id, replacing previous one.
+    pub(crate) async fn save_list(&self, id: &str, items: Arc<Vec<SearchableItem>>) {
+        self.lists
+            .insert(id.to_owned(), items, LIST_RESULTS_TTL)
+            .await;
+    }
+
+    /// Returns user's full list only if it hasn't expired yet.
+    pub(crate) async fn get_list(&self, id: &str) -> Option<Arc<Vec<SearchableItem>>> {
+        self.lists.get(id).await
+    }
+
+    /// User's subscriptions and playlists for inline queries, fetched or not yet.
+    /// Every query within `INLINE_RESULTS_TTL` gets the same `SharedFetch`,
+    /// so queries that come while items are being fetched wait for that fetch.
+    pub(crate) async fn inline_items(&self, user_id: UserId) -> SharedFetch {
+        self.inline
+            .get_or_insert_with(user_id, INLINE_RESULTS_TTL, SharedFetch::default)
+            .await
+    }
+}
@@ -0,0 +1,279 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use google_youtube3::api::PlaylistItem;
+
+use crate::model::export::funcs::escape_csv;
+use crate::model::export::types::ExportDocument;
+use crate::model::keyboards::types::StatsSorting;
+use crate::model::pagination::funcs::format_duration;
+use crate::model::playlist_stats::types::{DatedVideo, PlaylistStats, STATS_CSV_HEADER};
+use crate::model::youtube::funcs::video_details::details_or_unavailable;
+use crate::model::youtube::types::{Availability, SearchableItem, VideoDetails};
+
+/// Counts stats of a playlist. Video missing from `details` is counted as unavailable.
+pub(crate) fn playlist_stats(
+    playlist_id: String,
+    title: String,
+    items: &[PlaylistItem],
+    details: &HashMap<String, VideoDetails>,
+) -> PlaylistStats {
+    let mut stats = PlaylistStats {
+        playlist_id,
+        title,
+        item_count: items.len(),
+        ..Default::default()
+    };
+    for item in items {
+        let content_details = item.content_details.as_ref();
+        let video_id = content_details.and_then(|c| c.video_id.as_deref());
+        let video = video_id.map(|id| details_or_unavailable(details, id));
+        let Some(video) = video.filter(|v| v.availability != Availability::Unavailable) else {
+            stats.unavailable_count += 1;
+            continue;
+        };
+        if let Some(duration) = video.duration {
+            stats.total_runtime += duration;
+            stats.timed_count += 1;
+        }
+
+        let published_at = content_details.and_then(|c| c.video_published_at.clone());
+        let Some(published_at) = published_at.filter(|s| !s.trim().is_empty()) else {
+            continue;
+        };
+        let title = item.snippet.as_ref().and_then(|s| s.title.clone());
+        let dated = DatedVideo {
+            title: title.unwrap_or_default(),
+            published_at,
+        };
+        // `RFC 3339` dates in `UTC` compare correctly as strings.
+        if stats
+            .oldest
+            .as_ref()
+            .is_none_or(|o| dated.published_at < o.published_at)
+        {
+            stats.oldest = Some(dated.clone());
+        }
+        if stats
+            .newest
+            .as_ref()
+            .is_none_or(|n| dated.published_at > n.published_at)
+        {
+            stats.newest = Some(dated);
+        }
+    }
+    stats
+}
+
+/// Longest and biggest playlists go first, titles are in alphabetical order.
+pub(crate) fn sort_stats(stats: &mut [PlaylistStats], sorting: StatsSorting) {
+    match sorting {
+        StatsSorting::Runtime => stats.sort_by_key(|s| Reverse(s.total_runtime)),
+        StatsSorting::ItemCount => stats.sort_by_key(|s| Reverse(s.item_count)),
+        StatsSorting::Title => stats.sort_by_cached_key(|s| s.title.to_lowercase()),
+    }
+}
+
+/// One item per playlist, to be shown page by page like any other results.
+pub(crate) fn stats_to_items(stats: &[PlaylistStats]) -> Vec<SearchableItem> {
+    stats.iter().map(stats_to_item).collect()
+}
+
+fn stats_to_item(stats: &PlaylistStats) -> SearchableItem {
+    let average = stats
+        .average_length()
+        .map_or_else(|| "-".to_owned(), format_duration);
+    let description = format!(
+        "📼 {} videos  |  ⏱ {}  |  ⌀ {average}\n🚫 {} unavailable",
+        stats.item_count,
+        format_duration(stats.total_runtime),
+        stats.unavailable_count
+    );
+    let dated = |label: &str, video: &Option<DatedVideo>| {
+        video.as_ref().map(|v| {
+            let date = v.published_at.get(..10).unwrap_or(&v.published_at);
+            format!("\n\n[{label}]\n{date}  {}", v.title)
+        })
+    };
+    let about = [
+        dated("Oldest", &stats.oldest),
+        dated("Newest", &stats.newest),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<String>();
+    SearchableItem {
+        title: Some(stats.title.clone()),
+        description: Some(description),
+        link: format!("https://youtube.com/playlist?list={}", stats.playlist_id).into(),
+        about: Some(about).filter(|a| !a.is_empty()),
+        ..Default::default()
+    }
+}
+
+/// Totals of all playlists.
+pub(crate) fn stats_summary(stats: &[PlaylistStats]) -> String {
+    let (videos, unavailable) = stats.iter().fold((0, 0), |(v, u), s| {
+        (v + s.item_count, u + s.unavailable_count)
+    });
+    let runtime = stats.iter().map(|s| s.total_runtime).sum();
+    format!(
+        "{} playlists, {videos} videos, {unavailable} unavailable \n⏱ Total runtime {}",
+        stats.len(),
+        format_duration(runtime)
+    )
+}
+
+pub(crate) fn stats_to_csv(stats: &[PlaylistStats]) -> String {
+    let mut csv = STATS_CSV_HEADER.join(",") + "\r\n";
+    for s in stats {
+        let (oldest, newest) = (s.oldest.as_ref(), s.newest.as_ref());
+        let fields = [
+            s.playlist_id.clone(),
+            s.title.clone(),
+            s.item_count.to_string(),
+            s.total_runtime.as_secs().to_string(),
+            s.average_length()
+                .map(|a| a.as_secs().to_string())
+                .unwrap_or_default(),
+            oldest.map(|v| v.title.clone()).unwrap_or_default(),
+            oldest.map(|v| v.published_at.clone()).unwrap_or_default(),
+            newest.map(|v| v.title.clone()).unwrap_or_default(),
+            newest.map(|v| v.published_at.clone()).unwrap_or_default(),
+            s.unavailable_count.to_string(),
+        ];
+        let line = fields.map(|f| escape_csv(&f)).join(",");
+        csv.push_str(&line);
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub(crate) fn build_stats_document(stats: &[PlaylistStats]) -> ExportDocument {
+    ExportDocument {
+        file_name: "youtube_playlist_stats.csv".to_owned(),
+        content: stats_to_csv(stats).into_bytes(),
+        record_count: stats.len(),
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use google_youtube3::api::PlaylistItemListResponse;
+
+    use super::*;
+
+    fn items(playlist_id: &str) -> Vec<PlaylistItem> {
+        let response: PlaylistItemListResponse = serde_json::from_str(include_str!(
+            "../../../../../test_assets/playlist_item_list_json_response.json"
+        ))
+        .unwrap();
+        response
+            .items
+            .unwrap()
+            .into_iter()
+            .filter(|i| {
+                let snippet = i.snippet.as_ref().unwrap();
+                snippet.playlist_id.as_deref() == Some(playlist_id)
+            })
+            .collect()
+    }
+
+    fn details(secs: u64) -> VideoDetails {
+        VideoDetails {
+            duration: Some(Duration::from_secs(secs)),
+            ..Default::default()
+        }
+    }
+
+    fn stats_of(playlist_id: &str, title: &str) -> PlaylistStats {
+        let details = HashMap::from([
+            ("kV8dI6dl6bU".to_owned(), details(2927)),
+            ("Yy_m44vlXBk".to_owned(), details(4325)),
+            ("WtoRblP8ynk".to_owned(), details(1500)),
+        ]);
+        let items = items(playlist_id);
+        playlist_stats(playlist_id.to_owned(), title.to_owned(), &items, &details)
+    }
+
+    #[test]
+    fn playlist_stats_test() {
+        let stats = stats_of("PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a", "Интервью");
+        assert_eq!(stats.item_count, 3);
+        assert_eq!(stats.total_runtime, Duration::from_secs(11577));
+        assert_eq!(stats.average_length(), Some(Duration::from_secs(3859)));
+        assert_eq!(stats.unavailable_count, 0);
+        assert_eq!(stats.oldest.unwrap().published_at, "2020-07-30T09:55:01Z");
+        assert_eq!(
+            stats.newest.unwrap().title,
+            "Интервью с разработчиками Cyberpunk 2077"
+        );
+
+        let stats = stats_of("PL1Ng5zOyG8ExhDHpEMRYJPR-1JoIFr0i9", "Обзоры");
+        assert_eq!((stats.item_count, stats.unavailable_count), (3, 1));
+        assert_eq!(
+            stats.average_length(),
+            Some(Duration::from_millis(2_213_500))
+        );
+        assert_eq!(
+            stats.oldest.unwrap().title,
+            "Интервью с разработчиками Cyberpunk 2077"
+        );
+        assert_eq!(stats.newest.unwrap().title, "Обзор Cyberpunk 2077");
+
+        let empty = stats_of("PL1Ng5zOyG8EzV8Wi24mFUVIFKRuuzkIQ1", "Истории");
+        assert_eq!(empty.average_length(), None);
+        assert!(empty.oldest.is_none());
+    }
+
+    #[test]
+    fn sort_stats_test() {
+        let mut stats = vec![
+            stats_of("PL1Ng5zOyG8ExhDHpEMRYJPR-1JoIFr0i9", "обзоры"),
+            stats_of("PL1Ng5zOyG8EzV8Wi24mFUVIFKRuuzkIQ1", "Истории"),
+            stats_of("PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a", "Интервью"),
+        ];
+        let titles =
+            |stats: &[PlaylistStats]| stats.iter().map(|s| s.title.clone()).collect::<Vec<_>>();
+
+        sort_stats(&mut stats, StatsSorting::Runtime);
+        assert_eq!(titles(&stats), ["Интервью", "обзоры", "Истории"]);
+        sort_stats(&mut stats, StatsSorting::Title);
+        assert_eq!(titles(&stats), ["Интервью", "Истории", "обзоры"]);
+        sort_stats(&mut stats, StatsSorting::ItemCount);
+        assert_eq!(stats.last().unwrap().title, "Истории");
+    }
+
+    #[test]
+    fn stats_render_test() {
+        let stats = [stats_of("PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a", "Интервью")];
+
+        let item = stats_to_items(&stats).pop().unwrap();
+        assert_eq!(
+            item.description.unwrap(),
+            "📼 3 videos  |  ⏱ 3:12:57  |  ⌀ 1:04:19\n🚫 0 unavailable"
+        );
+        assert!(item
+            .about
+            .unwrap()
+            .starts_with("\n\n[Oldest]\n2020-07-30  "));
+
+        let csv = stats_to_csv(&stats);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next().unwrap(), STATS_CSV_HEADER.join(","));
+        assert_eq!(
+            lines.next().unwrap(),
+            "PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a,Интервью,3,11577,3859,\
+            Интервью с композитором,2020-07-30T09:55:01Z,\
+            Интервью с разработчиками Cyberpunk 2077,2021-02-11T15:00:12Z,0"
+        );
+        assert_eq!(
+            stats_summary(&stats),
+            "1 playlists, 3 videos, 0 unavailable \n⏱ Total runtime 3:12:57"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+/// Column names of `playlist_stats` `CSV` file, durations are in seconds.
+pub(crate) const STATS_CSV_HEADER: [&str; 10] = [
+    "playlist_id",
+    "title",
+    "item_count",
+    "total_runtime",
+    "average_length",
+    "oldest_video",
+    "oldest_published_at",
+    "newest_video",
+    "newest_published_at",
+    "unavailable_count",
+];
+
+/// Summary of one playlist, shown by `playlist_stats` command.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub(crate) struct PlaylistStats {
+    pub(crate) playlist_id: String,
+    pub(crate) title: String,
+    pub(crate) item_count: usize,
+    /// Sum of durations of videos that can be watched.
+    pub(crate) total_runtime: Duration,
+    /// Number of videos `total_runtime` is made of.
+    pub(crate) timed_count: usize,
+    pub(crate) oldest: Option<DatedVideo>,
+    pub(crate) newest: Option<DatedVideo>,
+    /// Deleted videos and private videos of other channels.
+    pub(crate) unavailable_count: usize,
+}
+
+impl PlaylistStats {
+    pub(crate) fn average_length(&self) -> Option<Duration> {
+        let count = u32::try_from(self.timed_count).ok().filter(|c| *c > 0)?;
+        Some(self.total_runtime / count)
+    }
+}
+
+/// Video title and the date it was published at, in `RFC 3339` format.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct DatedVideo {
+    pub(crate) title: String,
+    pub(crate) published_at: String,
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use google_youtube3::api::PlaylistItem;
+
+use crate::model::snapshot::types::{
+    PlaylistSnapshot, PlaylistSnapshots, SnapshotDiff, SnapshotItem, DELETED_VIDEO_TITLE,
+    PRIVATE_VIDEO_TITLE,
+};
+use crate::model::youtube::types::SearchableItem;
+
+/// `true` if `YouTube` replaced video's title, because it's gone or private.
+pub(crate) fn is_unavailable(title: &str) -> bool {
+    title == DELETED_VIDEO_TITLE || title == PRIVATE_VIDEO_TITLE
+}
+
+/// Builds snapshot item from playlist item. `None` if item has no video id.
+pub(crate) fn snapshot_item(item: &PlaylistItem) -> Option<SnapshotItem> {
+    let snippet = item.snippet.as_ref();
+    let video_id = snippet
+        .and_then(|s| s.resource_id.as_ref()?.video_id.clone())
+        .or_else(|| item.content_details.as_ref()?.video_id.clone())
+        .filter(|id| !id.trim().is_empty())?;
+    SnapshotItem {
+        video_id,
+        title: snippet.and_then(|s| s.title.clone()).unwrap_or_default(),
+        channel_title: snippet.and_then(|s| s.video_owner_channel_title.clone()),
+        position: snippet.and_then(|s| s.position),
+    }
+    .into()
+}
+
+/// Keeps titles from the old snapshot for videos that became unavailable since,
+/// so that original titles aren't lost when snapshot is updated.
+pub(crate) fn merge_snapshots(
+    old: Option<&PlaylistSnapshot>,
+    mut new: PlaylistSnapshot,
+) -> PlaylistSnapshot {
+    let Some(old) = old else {
+        return new;
+    };
+    let known: HashMap<&str, &SnapshotItem> = old
+        .items
+        .iter()
+        .filter(|i| !is_unavailable(&i.title))
+        .map(|i| (i.video_id.as_str(), i))
+        .collect();
+    for item in new.items.iter_mut().filter(|i| is_unavailable(&i.title)) {
+        if let Some(known_item) = known.get(item.video_id.as_str()) {
+            item.title = known_item.title.clone();
+            item.channel_title = known_item.channel_title.clone();
+        }
+    }
+    new
+}
+
+/// Replaces old snapshots with fresh ones, merged by `merge_snapshots`.
+/// Old snapshots of playlists that are gone or couldn't be read are carried over as they are,
+/// since saved titles are all that's left of their videos.
+pub(crate) fn update_snapshots(
+    old: PlaylistSnapshots,
+    fresh: PlaylistSnapshots,
+) -> PlaylistSnapshots {
+    let mut updated: PlaylistSnapshots = fresh
+        .into_iter()
+        .map(|(id, s)| {
+            let merged = merge_snapshots(old.get(&id), s);
+            (id, merged)
+        })
+        .collect();
+    for (id, s) in old {
+        updated.entry(id).or_insert(s);
+    }
+    updated
+}
+
+/// Compares the last snapshot with current playlist items.
+pub(crate) fn diff_snapshot(old: &PlaylistSnapshot, current: &[SnapshotItem]) -> SnapshotDiff {
+    let current: HashMap<&str, &SnapshotItem> =
+        current.iter().map(|i| (i.video_id.as_str(), i)).collect();
+    let mut diff = SnapshotDiff::default();
+    for old_item in &old.items {
+        match current.get(old_item.video_id.as_str()) {
+            None => diff.vanished.push(old_item.clone()),
+            Some(i) if is_unavailable(&i.title) && !is_unavailable(&old_item.title) => {
+                diff.unavailable.push(old_item.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    diff
+}
+
+/// Turns diff into printable items.
+pub(crate) fn diff_to_items(playlist_title: &str, diff: SnapshotDiff) -> Vec<SearchableItem> {
+    let to_item = |status: &str, i: SnapshotItem| {
+        let channel = i.channel_title.as_deref().unwrap_or("unknown channel");
+        let position = i
+            .position
+            .map(|p| format!(", position {}", p + 1))
+            .unwrap_or_default();
+        SearchableItem {
+            title: i.title.into(),
+            description: format!("{status} from '{playlist_title}'{position} \nby {channel}")
+                .into(),
+            link: format!("https://www.youtube.com/watch?v={}", i.video_id).into(),
+            ..Default::default()
+        }
+    };
+    let vanished = diff.vanished.into_iter().map(|i| to_item("Removed 🗑", i));
+    let unavailable = diff
+        .unavailable
+        .into_iter()
+        .map(|i| to_item("Deleted or made private 🔒", i));
+    vanished.chain(unavailable).collect()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use google_youtube3::api::{PlaylistItemSnippet, ResourceId};
+
+    use super::*;
+
+    fn item(video_id: &str, title: &str) -> SnapshotItem {
+        SnapshotItem {
+            video_id: video_id.to_owned(),
+            title: title.to_owned(),
+            channel_title: Some("channel".to_owned()),
+            position: Some(0),
+        }
+    }
+
+    fn snapshot(items: Vec<SnapshotItem>) -> PlaylistSnapshot {
+        PlaylistSnapshot {
+            playlist_id: "PL1".to_owned(),
+            title: "Music".to_owned(),
+            taken_at: time::OffsetDateTime::UNIX_EPOCH,
+            items,
+        }
+    }
+
+    #[test]
+    fn snapshot_item_test() {
+        let playlist_item = PlaylistItem {
+            snippet: PlaylistItemSnippet {
+                title: "Song".to_owned().into(),
+                position: 3.into(),
+                video_owner_channel_title: "Band".to_owned().into(),
+                resource_id: ResourceId {
+                    video_id: "v1".to_owned().into(),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        };
+        let item = snapshot_item(&playlist_item).unwrap();
+        assert_eq!(item.video_id, "v1");
+        assert_eq!(item.title, "Song");
+        assert_eq!(item.channel_title.as_deref(), Some("Band"));
+        assert_eq!(item.position, Some(3));
+
+        assert!(snapshot_item(&PlaylistItem::default()).is_none());
+    }
+
+    #[test]
+    fn diff_snapshot_test() {
+        let old = snapshot(vec![
+            item("v1", "Kept"),
+            item("v2", "Removed"),
+            item("v3", "Made private"),
+            item("v4", DELETED_VIDEO_TITLE),
+        ]);
+        let current = [
+            item("v1", "Kept"),
+            item("v3", PRIVATE_VIDEO_TITLE),
+            item("v4", DELETED_VIDEO_TITLE),
+            item("v5", "New"),
+        ];
+        let diff = diff_snapshot(&old, &current);
+        assert_eq!(diff.vanished, [item("v2", "Removed")]);
+        assert_eq!(diff.unavailable, [item("v3", "Made private")]);
+    }
+
+    #[test]
+    fn merge_snapshots_test() {
+        let old = snapshot(vec![item("v1", "Original title"), item("v2", "Other")]);
+        let new = snapshot(vec![
+            item("v1", PRIVATE_VIDEO_TITLE),
+            item("v3", DELETED_VIDEO_TITLE),
+        ]);
+
+        let merged = merge_snapshots(Some(&old), new.clone());
+        assert_eq!(merged.items[0].title, "Original title");
+        assert_eq!(merged.items[1].title, DELETED_VIDEO_TITLE);
+
+        assert_eq!(merge_snapshots(None, new.clone()), new);
+    }
+
+    #[test]
+    fn update_snapshots_test() {
+        let deleted = PlaylistSnapshot {
+            playlist_id: "PL2".to_owned(),
+            ..snapshot(vec![item("v7", "Only copy of the title")])
+        };
+        let old = PlaylistSnapshots::from([
+            (
+                "PL1".to_owned(),
+                snapshot(vec![item("v1", "Original title")]),
+            ),
+            ("PL2".to_owned(), deleted.clone()),
+        ]);
+        let fresh = PlaylistSnapshots::from([(
+            "PL1".to_owned(),
+            snapshot(vec![item("v1", PRIVATE_VIDEO_TITLE)]),
+        )]);
+
+        let updated = update_snapshots(old, fresh);
+        assert_eq!(updated.len(), 2);
+        assert_eq!(updated["PL1"].items[0].title, "Original title");
+        assert_eq!(updated["PL2"], deleted);
+    }
+
+    #[test]
+    fn diff_to_items_test() {
+        let diff = SnapshotDiff {
+            vanished: vec![item("v2", "Removed")],
+            unavailable: vec![item("v3", "Made private")],
+        };
+        let items = diff_to_items("Music", diff);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title.as_deref(), Some("Removed"));
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://www.youtube.com/watch?v=v2")
+        );
+        assert!(items[1]
+            .description
+            .as_deref()
+            .unwrap()
+            .starts_with("Deleted or made private 🔒 from 'Music', position 1"));
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+/// Title `YouTube` puts in place of a removed video.
+pub(crate) const DELETED_VIDEO_TITLE: &str = "Deleted video";
+
+/// Title `YouTube` puts in place of a video that became private.
+pub(crate) const PRIVATE_VIDEO_TITLE: &str = "Private video";
+
+/// Saved playlist item. Titles are kept even after video disappears from `YouTube`.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotItem {
+    pub(crate) video_id: String,
+    pub(crate) title: String,
+    pub(crate) channel_title: Option<String>,
+    pub(crate) position: Option<u32>,
+}
+
+/// All items of a playlist at the moment of `taken_at`.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistSnapshot {
+    pub(crate) playlist_id: String,
+    pub(crate) title: String,
+    pub(crate) taken_at: time::OffsetDateTime,
+    pub(crate) items: Vec<SnapshotItem>,
+}
+
+/// User's playlist snapshots (playlist id -> snapshot).
+pub type PlaylistSnapshots = HashMap<String, PlaylistSnapshot>;
+
+/// Fresh snapshots of user's playlists.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub(crate) struct TakenSnapshots {
+    pub(crate) snapshots: PlaylistSnapshots,
+    /// Ids of playlists that exist, but couldn't be read.
+    pub(crate) unreadable: HashSet<String>,
+}
+
+/// What happened to playlist since the last snapshot.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub(crate) struct SnapshotDiff {
+    /// Items that are no longer in the playlist.
+    pub(crate) vanished: Vec<SnapshotItem>,
+    /// Items that are still in the playlist, but were deleted or became private.
+    pub(crate) unavailable: Vec<SnapshotItem>,
+}
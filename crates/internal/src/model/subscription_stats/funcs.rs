@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use crate::model::export::funcs::escape_csv;
+use crate::model::export::types::ExportDocument;
+use crate::model::subscription_stats::types::{
+    DatedSubscription, SubscriptionStats, STOP_WORDS, SUBSCRIPTION_STATS_CSV_HEADER, TOP_WORDS,
+};
+use crate::model::utils::HTMLise;
+use crate::model::youtube::traits::Searchable;
+
+/// Counts stats of subscriptions, `date` of a subscription is the date it was made.
+pub(crate) fn subscription_stats<T: Searchable>(subscriptions: &[T]) -> SubscriptionStats {
+    let mut stats = SubscriptionStats {
+        total: subscriptions.len(),
+        title_words: top_words(subscriptions.iter().filter_map(Searchable::title)),
+        description_words: top_words(subscriptions.iter().filter_map(Searchable::description)),
+        ..Default::default()
+    };
+    for sub in subscriptions {
+        let Some(date) = sub.date() else { continue };
+        if let Some(year) = date.get(..4) {
+            *stats.per_year.entry(year.to_owned()).or_default() += 1;
+        }
+        let dated = DatedSubscription {
+            title: sub.title().unwrap_or_default().to_owned(),
+            link: sub.link(),
+            subscribed_at: date.to_owned(),
+        };
+        // `RFC 3339` dates in `UTC` compare correctly as strings.
+        if stats
+            .oldest
+            .as_ref()
+            .is_none_or(|o| dated.subscribed_at < o.subscribed_at)
+        {
+            stats.oldest = Some(dated.clone());
+        }
+        if stats
+            .newest
+            .as_ref()
+            .is_none_or(|n| dated.subscribed_at > n.subscribed_at)
+        {
+            stats.newest = Some(dated);
+        }
+    }
+    stats
+}
+
+/// `TOP_WORDS` most frequent words of `texts`, ties are in alphabetical order.
+fn top_words<'a>(texts: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in texts.flat_map(words) {
+        *counts.entry(word).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|(a_word, a), (b_word, b)| b.cmp(a).then_with(|| a_word.cmp(b_word)));
+    counts.truncate(TOP_WORDS);
+    counts
+}
+
+/// Lowercase words of `text` that are at least 3 letters long and aren't stop words.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3 && !w.chars().all(|c| c.is_numeric()))
+        .map(str::to_lowercase)
+        .filter(|w| !STOP_WORDS.contains(&w.as_str()))
+}
+
+/// Report to be sent as a single `HTML` message.
+pub(crate) fn stats_report(stats: &SubscriptionStats) -> String {
+    let mut report = format!(
+        "📈 {} \n\nTotal: {}",
+        "Subscriptions".to_bold(),
+        stats.total
+    );
+
+    if !stats.per_year.is_empty() {
+        report.push_str(&format!("\n\n{}", "Per year".to_bold()));
+        for (year, count) in &stats.per_year {
+            report.push_str(&format!("\n{year}  {count}"));
+        }
+    }
+
+    let dated = |label: &str, sub: &Option<DatedSubscription>| {
+        sub.as_ref().map(|s| {
+            let date = s.subscribed_at.get(..10).unwrap_or(&s.subscribed_at);
+            let title = s.title.to_escaped();
+            let title = match &s.link {
+                Some(link) => link.to_link(title),
+                None => title,
+            };
+            format!("\n\n{}\n{date}  {title}", label.to_bold())
+        })
+    };
+    report.extend(dated("Oldest", &stats.oldest));
+    report.extend(dated("Newest", &stats.newest));
+
+    let words = |label: &str, words: &[(String, usize)]| {
+        (!words.is_empty()).then(|| {
+            let words = words
+                .iter()
+                .map(|(word, count)| format!("{}  {count}", word.to_escaped()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("\n\n{}\n{words}", label.to_bold())
+        })
+    };
+    report.extend(words("Frequent words in titles", &stats.title_words));
+    report.extend(words(
+        "Frequent words in descriptions",
+        &stats.description_words,
+    ));
+    report
+}
+
+pub(crate) fn subscription_stats_to_csv(stats: &SubscriptionStats) -> String {
+    let mut rows = vec![["total".to_owned(), String::new(), stats.total.to_string()]];
+    rows.extend(
+        stats
+            .per_year
+            .iter()
+            .map(|(year, count)| ["year".to_owned(), year.clone(), count.to_string()]),
+    );
+    for (section, sub) in [("oldest", &stats.oldest), ("newest", &stats.newest)] {
+        if let Some(s) = sub {
+            rows.push([section.to_owned(), s.title.clone(), s.subscribed_at.clone()]);
+        }
+    }
+    for (section, words) in [
+        ("title_word", &stats.title_words),
+        ("description_word", &stats.description_words),
+    ] {
+        rows.extend(
+            words
+                .iter()
+                .map(|(word, count)| [section.to_owned(), word.clone(), count.to_string()]),
+        );
+    }
+
+    let mut csv = SUBSCRIPTION_STATS_CSV_HEADER.join(",") + "\r\n";
+    for row in rows {
+        csv.push_str(&row.map(|f| escape_csv(&f)).join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub(crate) fn build_subscription_stats_document(stats: &SubscriptionStats) -> ExportDocument {
+    ExportDocument {
+        file_name: "youtube_subscription_stats.csv".to_owned(),
+        content: subscription_stats_to_csv(stats).into_bytes(),
+        record_count: stats.total,
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use google_youtube3::api::{Subscription, SubscriptionListResponse};
+
+    use super::*;
+
+    fn subscriptions() -> Vec<Subscription> {
+        let response: SubscriptionListResponse = serde_json::from_str(include_str!(
+            "../../../../../test_assets/subscription_list_json_response.json"
+        ))
+        .unwrap();
+        response.items.unwrap()
+    }
+
+    #[test]
+    fn words_test() {
+        let found: Vec<_> = words("Обзоры игр. The AWS re:Invent 2023, Всё & ))").collect();
+        assert_eq!(found, ["обзоры", "игр", "aws", "invent"]);
+    }
+
+    #[test]
+    fn subscription_stats_test() {
+        let stats = subscription_stats(&subscriptions());
+        assert_eq!(stats.total, 3);
+        assert_eq!(
+            stats.per_year.into_iter().collect::<Vec<_>>(),
+            [("2019".to_owned(), 1), ("2022".to_owned(), 2)]
+        );
+        assert_eq!(stats.oldest.unwrap().title, "iXBT games");
+        let newest = stats.newest.unwrap();
+        assert_eq!(newest.title, "Dr. Plants");
+        assert_eq!(newest.subscribed_at, "2022-12-25T14:55:17.743678Z");
+        assert_eq!(stats.title_words[0], ("aws".to_owned(), 1));
+        assert_eq!(stats.description_words[0], ("aws".to_owned(), 9));
+        assert!(stats.description_words.len() <= TOP_WORDS);
+    }
+
+    #[test]
+    fn stats_report_test() {
+        let mut stats = subscription_stats(&subscriptions());
+        stats.oldest.as_mut().unwrap().title = "Tom & Jerry <live>".to_owned();
+
+        let report = stats_report(&stats);
+        assert!(report.starts_with("📈 <b>Subscriptions</b> \n\nTotal: 3"));
+        assert!(report.contains("\n\n<b>Per year</b>\n2019  1\n2022  2"));
+        assert!(report.contains(
+            "\n\n<b>Oldest</b>\n2019-09-13  \
+            <a href=\"https://youtube.com/channel/UC4e_XPBiiIO4fo4_CucxQeg\">\
+            Tom &amp; Jerry &lt;live&gt;</a>"
+        ));
+        assert!(report.contains("\n\n<b>Frequent words in descriptions</b>\naws  9\n"));
+
+        let csv = subscription_stats_to_csv(&stats);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(
+            lines[..4],
+            [
+                "section,key,value",
+                "total,,3",
+                "year,2019,1",
+                "year,2022,2"
+            ]
+        );
+        assert!(lines.contains(&"oldest,Tom & Jerry <live>,2019-09-13T21:39:24.861714Z"));
+        assert!(lines.contains(&"description_word,aws,9"));
+    }
+}
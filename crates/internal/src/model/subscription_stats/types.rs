@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+
+/// Columns of `stats` CSV file, one row per number in the report.
+pub(crate) const SUBSCRIPTION_STATS_CSV_HEADER: [&str; 3] = ["section", "key", "value"];
+
+/// How many of the most frequent words are shown.
+pub(crate) const TOP_WORDS: usize = 10;
+
+/// Words that say nothing about a channel.
+pub(crate) const STOP_WORDS: &[&str] = &[
+    "and", "are", "for", "from", "our", "the", "this", "that", "with", "you", "your", "все", "всё",
+    "для", "его", "как", "они", "про", "что", "это",
+];
+
+/// Totals of user's subscriptions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SubscriptionStats {
+    pub(crate) total: usize,
+    /// Subscriptions made in each year, by `snippet.published_at`.
+    pub(crate) per_year: BTreeMap<String, usize>,
+    pub(crate) oldest: Option<DatedSubscription>,
+    pub(crate) newest: Option<DatedSubscription>,
+    /// Most frequent words with their counts, most frequent first.
+    pub(crate) title_words: Vec<(String, usize)>,
+    pub(crate) description_words: Vec<(String, usize)>,
+}
+
+/// Subscription and the date it was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DatedSubscription {
+    pub(crate) title: String,
+    pub(crate) link: Option<String>,
+    pub(crate) subscribed_at: String,
+}
@@ -1,4 +1,6 @@
 use log::Level;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
 use std::fmt::{Debug, Display};
 
 pub fn log(lvl: Level, op: &str, msg: &str, p: impl Debug) {
@@ -16,6 +18,15 @@ impl<T> PassWith<T> for T {
     }
 }
 
+/// Random alphanumeric string, used for nonces.
+pub(crate) fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
 pub(crate) fn maybe_print<T, P, D>(prefix: P, printable: &Option<T>, default: D) -> String
 where
     T: Display + Debug,
@@ -45,6 +56,14 @@ where
     fn to_link<L: Display>(&self, link_text: L) -> String {
         format!("<a href=\"{self}\">{link_text}</a>")
     }
+
+    /// Escapes text that comes from `YouTube`, so it can't break `HTML` of the message.
+    fn to_escaped(&self) -> String {
+        self.to_string()
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
 }
 
 impl<T> HTMLise for T where T: Display {}
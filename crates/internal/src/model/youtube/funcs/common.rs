@@ -1,15 +1,41 @@
-use std::fmt::Debug;
+use std::future::Future;
+use std::time::Duration;
 
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::future;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use maptypings::WrapInRes;
+use sha2::{Digest, Sha256};
 
+use crate::model::errors::YouTubeApiError;
 use crate::model::net::funcs::join;
 use crate::model::net::traits::{YouTubeApiRequestBuilder, YouTubeApiResponsePage};
-use crate::model::youtube::types::{RequiredAuthURLParams, AUTH_URL_BASE};
+use crate::model::net::types::{
+    PlaylistItemRequester, PlaylistRequester, YouTubeClient, INITIAL_BACKOFF, MAX_RETRIES,
+};
+use crate::model::youtube::traits::Searchable;
+use crate::model::youtube::types::{PlaylistContents, RequiredAuthURLParams, AUTH_URL_BASE};
 use crate::StdRes;
 
-/// Makes a single call to `YouTube API` go get one page of items.
+/// Makes a call to `YouTube API` go get one page of items.
+/// Retryable errors are retried with exponential backoff.
 pub(crate) async fn items_request<T>(
-    client: &reqwest::Client,
+    client: &YouTubeClient,
+    access_token: &str,
+    req_builder: &T,
+    page_token: Option<String>,
+) -> eyre::Result<T::Target>
+where
+    T: YouTubeApiRequestBuilder,
+{
+    let request = || items_request_once(client, access_token, req_builder, page_token.clone());
+    retry_with_backoff(MAX_RETRIES, INITIAL_BACKOFF, request).await
+}
+
+/// Makes a single call to `YouTube API` go get one page of items.
+async fn items_request_once<T>(
+    client: &YouTubeClient,
     access_token: &str,
     req_builder: &T,
     page_token: Option<String>,
@@ -25,19 +51,69 @@ where
     let f = |s| format!(" [:: LOG ::]    ( @:[fn::items_request] 'resp' is [| '{s:#?}' |] )");
     log::info!("{}", f((&resp.headers(), &resp.status())));
 
-    let status_is_failure = !resp.status().is_success();
-    let body = resp.text().await;
+    let status = resp.status();
+    let body = resp.text().await?;
 
     log::info!("@:[fn::items_request] <body> is: {body:#?}");
 
-    if status_is_failure {
-        return eyre::eyre!("status code is not a success").in_err();
+    if !status.is_success() {
+        return Err(YouTubeApiError::from_response(status.as_u16(), &body).into());
     }
 
-    let body = body?;
     serde_json::from_str::<T::Target>(&body)?.in_ok()
 }
 
+/// Calls `request` until it succeeds, fails with an error that isn't retryable,
+/// or is retried `max_retries` times. Delay is doubled after each retry.
+pub(crate) async fn retry_with_backoff<T, F, Fut>(
+    max_retries: u32,
+    initial_backoff: Duration,
+    mut request: F,
+) -> eyre::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = eyre::Result<T>>,
+{
+    let (mut attempt, mut backoff) = (0, initial_backoff);
+    loop {
+        match request().await {
+            Err(e) if attempt < max_retries && is_retryable(&e) => {
+                attempt += 1;
+                log::warn!("@:[fn::retry_with_backoff] retry {attempt} in {backoff:?}: {e:?}");
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            res => return res,
+        }
+    }
+}
+
+fn is_retryable(e: &eyre::Report) -> bool {
+    if let Some(e) = e.downcast_ref::<YouTubeApiError>() {
+        return e.is_retryable();
+    }
+    e.downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_timeout() || e.is_connect())
+}
+
+/// `true` if `e` concerns only one item (playlist, channel), so that others can still be processed.
+pub(crate) fn is_item_error(e: &eyre::Report) -> bool {
+    e.downcast_ref::<YouTubeApiError>()
+        .is_some_and(|e| !e.is_fatal())
+}
+
+/// Turns an error that concerns only the requested item into `None` (see `is_item_error`).
+pub(crate) fn skip_item_error<T>(res: eyre::Result<T>) -> eyre::Result<Option<T>> {
+    match res {
+        Ok(val) => Ok(Some(val)),
+        Err(e) if is_item_error(&e) => {
+            log::warn!("@:[fn::skip_item_error] item is skipped: {e:?}");
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
 /// Authorization url constructor.
 pub(crate) fn make_auth_url<V>(
     client_id: V,
@@ -67,41 +143,99 @@ where
     url.in_ok()
 }
 
-/// Gives full access all pages of request, applying 'f' to each page.
-/// Stop condition can be set using `stop_if`.
-pub(crate) async fn pagination<I, F, S>(req_builder: I, access_token: &str, stop_if: S, f: F)
-where
-    I: YouTubeApiRequestBuilder,
-    I::Target: Default + Debug + YouTubeApiResponsePage,
-    F: FnMut(I::Target),
-    S: Fn(&I::Target) -> bool,
-{
-    log::info!(" [:: LOG ::]    ( @:[fn::pagination] started )");
-
-    let mut f = f;
-    let client = reqwest::Client::new();
-
-    let mut next_page_token = None;
-    loop {
-        let resp = items_request(&client, access_token, &req_builder, next_page_token).await;
-
-        log::info!("@:[fn::pagination] <resp> is: {resp:?}");
-
-        let search_res = resp.unwrap_or_default();
-        next_page_token = search_res.next_page_token();
+/// Derives `PKCE` code challenge from code verifier (`S256` method).
+pub(crate) fn make_code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
 
-        if stop_if(&search_res) {
-            break;
-        }
+/// Item of a page returned by request built with `I`.
+pub(crate) type PageItem<I> =
+    <<I as YouTubeApiRequestBuilder>::Target as YouTubeApiResponsePage>::Item;
 
-        f(search_res);
+/// Stream of items from all pages of request.
+/// Pages are requested lazily, one at a time, so dropping the stream early saves requests.
+/// Failed request ends the stream with an error.
+pub(crate) fn item_stream<'a, I>(
+    client: &'a YouTubeClient,
+    req_builder: I,
+    access_token: &'a str,
+) -> impl Stream<Item = eyre::Result<PageItem<I>>> + 'a
+where
+    I: YouTubeApiRequestBuilder + 'a,
+    I::Target: YouTubeApiResponsePage,
+{
+    // `None` means there are no more pages, `Some(None)` is the first page.
+    let first_page: Option<Option<String>> = Some(None);
+    let state = (req_builder, first_page);
+    stream::try_unfold(state, move |(req_builder, page)| async move {
+        let Some(page_token) = page else {
+            return eyre::Ok(None);
+        };
+        let resp = items_request(client, access_token, &req_builder, page_token).await?;
+        let next_page = resp.next_page_token().map(Some);
+        let items = resp.items().unwrap_or_default();
+        Ok(Some((items, (req_builder, next_page))))
+    })
+    .map_ok(|items| stream::iter(items).map(Ok))
+    .try_flatten()
+}
 
-        if next_page_token.is_none() {
-            break;
-        }
-    }
+/// `(id, title)` of all user's playlists.
+pub(crate) async fn playlist_ids_and_titles(
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<Vec<(String, String)>> {
+    item_stream(client, PlaylistRequester, access_token)
+        .try_filter_map(|pl| {
+            let title = pl.title().unwrap_or_default().to_owned();
+            let id = pl.id.filter(|id| !id.trim().is_empty());
+            future::ready(Ok(id.map(|id| (id, title))))
+        })
+        .try_collect()
+        .await
+}
 
-    log::info!(" [:: LOG ::]    ( @:[fn::pagination] ended )");
+/// Pages items of given playlists, at most `concurrency` of them at once.
+/// Playlists that can't be read are skipped, the rest keep order of `playlists`.
+pub(crate) async fn playlist_contents(
+    client: &YouTubeClient,
+    access_token: &str,
+    playlists: Vec<(String, String)>,
+    concurrency: usize,
+) -> eyre::Result<Vec<PlaylistContents>> {
+    let mut contents: Vec<(usize, PlaylistContents)> =
+        stream::iter(playlists.into_iter().enumerate())
+            .map(|(i, (playlist_id, title))| async move {
+                let req_builder = PlaylistItemRequester {
+                    playlist_id: &playlist_id,
+                };
+                let res: eyre::Result<Vec<_>> = item_stream(client, req_builder, access_token)
+                    .try_collect()
+                    .await;
+                match res {
+                    Ok(items) => Ok(Some((
+                        i,
+                        PlaylistContents {
+                            playlist_id,
+                            title,
+                            items,
+                        },
+                    ))),
+                    Err(e) if is_item_error(&e) => {
+                        log::warn!(
+                            "@:[fn::playlist_contents] playlist '{title}' is skipped: {e:?}"
+                        );
+                        Ok(None)
+                    }
+                    Err(e) => Err(e),
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .try_filter_map(|found| async move { Ok(found) })
+            .try_collect()
+            .await?;
+    contents.sort_by_key(|(i, _)| *i);
+    Ok(contents.into_iter().map(|(_, c)| c).collect())
 }
 
 #[allow(clippy::unwrap_used)]
@@ -109,11 +243,12 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::net::types::YOUTUBE_PLAYLIST_ITEMS_API;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::PLAYLIST_ITEMS_ENDPOINT;
     use env_logger::fmt::Formatter;
     use error_traits::PassErrWith;
     use log::Record;
-    use reqwest::{Client, RequestBuilder};
+    use reqwest::RequestBuilder;
     use serde::Deserialize;
     use std::any::type_name;
     use std::io::Write;
@@ -160,13 +295,14 @@ mod tests {
 
             fn build_req(
                 &self,
-                client: &Client,
+                client: &YouTubeClient,
                 access_token: &str,
                 page_token: Option<String>,
             ) -> eyre::Result<RequestBuilder> {
                 log::info!("@:[fn::build_req {}]", type_name::<Self::Target>());
                 let mut req = client
-                    .get(reqwest::Url::parse(YOUTUBE_PLAYLIST_ITEMS_API)?)
+                    .http
+                    .get(client.endpoint_url(PLAYLIST_ITEMS_ENDPOINT)?)
                     .query(&[
                         ("part", "contentDetails,id,snippet,status"),
                         ("maxResults", "50"),
@@ -192,7 +328,7 @@ mod tests {
             .try_init()
             .ok();
 
-        let client = Client::new();
+        let client = YouTubeClient::default();
         let playlist_id = "PLtPKOVLaGpwTaogl68CjhmZmCSBOMWOKc";
         let (access_token, req_builder, page_token) = (
             "gfTHy^75$367Frt%4dHHJytE$#A",
@@ -207,4 +343,87 @@ mod tests {
             .pass_err_with(|e| log::error!("error: {e:?}"))
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn playlist_contents_skips_missing_playlist_test() {
+        let mock_api = MockYouTubeApi::start();
+        let playlists = vec![
+            ("PLdeleted".to_owned(), "Deleted".to_owned()),
+            (
+                "PL1Ng5zOyG8EwtAQWC2ayghILV9saLC65a".to_owned(),
+                "Интервью".to_owned(),
+            ),
+        ];
+        let contents = playlist_contents(&mock_api.client, "expired_token", playlists.clone(), 2);
+        assert!(contents.await.is_err());
+
+        let contents = playlist_contents(&mock_api.client, MOCK_ACCESS_TOKEN, playlists, 2)
+            .await
+            .unwrap();
+        assert_eq!(contents.len(), 1);
+        assert_eq!(contents[0].title, "Интервью");
+        assert_eq!(contents[0].items.len(), 3);
+    }
+
+    #[test]
+    fn skip_item_error_test() {
+        let forbidden = YouTubeApiError::Other {
+            status: 403,
+            reason: "playlistItemsNotAccessible".to_owned(),
+            message: "The request is not properly authorized.".to_owned(),
+        };
+        let quota = YouTubeApiError::QuotaExceeded {
+            message: "The request cannot be completed because you have exceeded your quota."
+                .to_owned(),
+        };
+
+        assert_eq!(skip_item_error(Ok(1)).unwrap(), Some(1));
+        assert_eq!(skip_item_error::<()>(Err(forbidden.into())).unwrap(), None);
+        assert!(skip_item_error::<()>(Err(quota.into())).is_err());
+        assert!(skip_item_error::<()>(Err(eyre::eyre!("Connection reset"))).is_err());
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_test() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let server_error = || YouTubeApiError::ServerError {
+            status: 503,
+            message: String::new(),
+        };
+        let calls = AtomicU32::new(0);
+        let flaky = || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(server_error().into()),
+                n => Ok(n),
+            }
+        };
+        let res = retry_with_backoff(3, Duration::from_millis(1), flaky).await;
+        assert_eq!(res.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let always_failing = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(server_error().into())
+        };
+        let err = retry_with_backoff(2, Duration::from_millis(1), always_failing)
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<YouTubeApiError>(), Some(&server_error()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let quota_exceeded = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let message = String::new();
+            Err::<(), _>(YouTubeApiError::QuotaExceeded { message }.into())
+        };
+        assert!(
+            retry_with_backoff(3, Duration::from_millis(1), quota_exceeded)
+                .await
+                .is_err()
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
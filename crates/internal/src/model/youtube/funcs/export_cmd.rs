@@ -0,0 +1,30 @@
+use crate::model::export::types::{ExportRecord, EXPORT_LIMIT};
+use crate::model::net::traits::{YouTubeApiRequestBuilder, YouTubeApiResponsePage};
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::common::item_stream;
+use crate::model::youtube::traits::Exportable;
+use futures::{future, StreamExt, TryStreamExt};
+
+/// Returns all items on user's channel (up to `EXPORT_LIMIT`), ready to be exported.
+pub(crate) async fn export_items<T>(
+    client: &YouTubeClient,
+    req_builder: T,
+    access_token: &str,
+) -> eyre::Result<Vec<ExportRecord>>
+where
+    T: YouTubeApiRequestBuilder,
+    T::Target: YouTubeApiResponsePage,
+    <T::Target as YouTubeApiResponsePage>::Item: Exportable,
+{
+    log::info!(" [:: LOG ::]    ( @:[fn::export_items] started )");
+
+    let records = item_stream(client, req_builder, access_token)
+        .try_filter_map(|item| future::ready(Ok(item.export_record())))
+        .take(EXPORT_LIMIT as usize)
+        .try_collect()
+        .await?;
+
+    log::info!(" [:: LOG ::]    ( @:[fn::export_items] ended )");
+
+    Ok(records)
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use futures::stream::{self, StreamExt};
+use futures::TryStreamExt;
+
+use crate::model::inactive::types::ChannelActivity;
+use crate::model::net::types::{
+    ChannelRequester, PlaylistItemRequester, SubscriptionRequester, YouTubeClient, IDS_PER_REQUEST,
+};
+use crate::model::youtube::funcs::common::{item_stream, skip_item_error};
+
+/// Pages user's subscriptions, looks up uploads playlists of their channels
+/// in batches, then takes the latest upload of each, at most `concurrency` at once.
+pub(crate) async fn subscriptions_activity(
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<Vec<ChannelActivity>> {
+    log::info!(" [:: LOG ::]    ( @:[fn::subscriptions_activity] started )");
+
+    let channels: Vec<ChannelActivity> = item_stream(client, SubscriptionRequester, access_token)
+        .try_filter_map(|sub| async move {
+            let snippet = sub.snippet.unwrap_or_default();
+            let channel_id = snippet.resource_id.and_then(|r| r.channel_id);
+            let channel = channel_id.map(|channel_id| ChannelActivity {
+                channel_id,
+                title: snippet.title.unwrap_or_default(),
+                last_upload: None,
+            });
+            Ok(channel)
+        })
+        .try_collect()
+        .await?;
+
+    let channel_ids: Vec<String> = channels.iter().map(|c| c.channel_id.clone()).collect();
+    let mut last_uploads = latest_uploads(client, access_token, &channel_ids, concurrency).await?;
+    let activity = channels
+        .into_iter()
+        .map(|channel| ChannelActivity {
+            last_upload: last_uploads.remove(&channel.channel_id),
+            ..channel
+        })
+        .collect();
+
+    log::info!(" [:: LOG ::]    ( @:[fn::subscriptions_activity] ended )");
+    Ok(activity)
+}
+
+/// Dates of the latest uploads by channel id, at most `concurrency` are looked up at once.
+/// Channels without uploads are missing from the map.
+pub(crate) async fn latest_uploads(
+    client: &YouTubeClient,
+    access_token: &str,
+    channel_ids: &[String],
+    concurrency: usize,
+) -> eyre::Result<HashMap<String, String>> {
+    let uploads = uploads_playlist_ids(client, access_token, channel_ids).await?;
+    stream::iter(uploads)
+        .map(|(channel_id, playlist_id)| async move {
+            let date = last_upload(client, access_token, &playlist_id).await?;
+            eyre::Ok(date.map(|d| (channel_id, d)))
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_filter_map(|found| async move { Ok(found) })
+        .try_collect()
+        .await
+}
+
+/// Ids of uploads playlists by channel id.
+/// Deleted channels aren't returned by `YouTube`, so they are missing from the map.
+async fn uploads_playlist_ids(
+    client: &YouTubeClient,
+    access_token: &str,
+    channel_ids: &[String],
+) -> eyre::Result<HashMap<String, String>> {
+    let mut uploads = HashMap::new();
+    for chunk in channel_ids.chunks(IDS_PER_REQUEST) {
+        let req_builder = ChannelRequester { channel_ids: chunk };
+        let channels: Vec<_> = item_stream(client, req_builder, access_token)
+            .try_collect()
+            .await?;
+        uploads.extend(channels.into_iter().filter_map(|chan| {
+            let playlist_id = chan.content_details?.related_playlists?.uploads?;
+            Some((chan.id?, playlist_id))
+        }));
+    }
+    Ok(uploads)
+}
+
+/// Publication date of the first item of uploads playlist, which is the latest upload.
+/// Uploads playlist of a channel without videos doesn't exist, that is `None` as well,
+/// as is any other playlist that can't be read.
+async fn last_upload(
+    client: &YouTubeClient,
+    access_token: &str,
+    playlist_id: &str,
+) -> eyre::Result<Option<String>> {
+    let req_builder = PlaylistItemRequester { playlist_id };
+    let res: eyre::Result<Vec<_>> = item_stream(client, req_builder, access_token)
+        .take(1)
+        .try_collect()
+        .await;
+    let Some(items) = skip_item_error(res)? else {
+        return Ok(None);
+    };
+    let published_at = items
+        .into_iter()
+        .find_map(|i| i.content_details?.video_published_at)
+        .filter(|d| !d.trim().is_empty());
+    Ok(published_at)
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn subscriptions_activity_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let mut activity = subscriptions_activity(client, MOCK_ACCESS_TOKEN, 2)
+            .await
+            .unwrap();
+        activity.sort_by(|a, b| a.title.cmp(&b.title));
+        let found: Vec<_> = activity
+            .iter()
+            .map(|c| (c.title.as_str(), c.last_upload.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("AWS Events", Some("2023-05-10T17:00:00Z")),
+                ("Dr. Plants", None),
+                ("iXBT games", Some("2014-05-20T11:00:00Z")),
+            ]
+        );
+    }
+}
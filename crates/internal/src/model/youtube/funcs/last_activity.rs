@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use futures::stream::{self, StreamExt};
+use futures::TryStreamExt;
+
+use crate::model::net::types::{PlaylistItemRequester, YouTubeClient};
+use crate::model::youtube::funcs::common::{item_stream, skip_item_error};
+use crate::model::youtube::funcs::inactive_cmd::latest_uploads;
+use crate::model::youtube::types::SearchableItem;
+
+/// Sets `last_activity` of items, at most `concurrency` lookups run at once.
+/// Subscriptions get the latest upload of their channel, playlists get the latest
+/// video added to them, and videos are active as of their own date.
+pub(crate) async fn add_last_activity(
+    client: &YouTubeClient,
+    access_token: &str,
+    items: &mut [SearchableItem],
+    concurrency: usize,
+) -> eyre::Result<()> {
+    log::info!(" [:: LOG ::]    ( @:[fn::add_last_activity] started )");
+
+    let channel_ids: Vec<String> = items.iter().filter_map(|i| i.channel_id.clone()).collect();
+    let uploads = latest_uploads(client, access_token, &channel_ids, concurrency).await?;
+
+    let playlist_ids: Vec<String> = items.iter().filter_map(|i| i.playlist_id.clone()).collect();
+    let added: HashMap<String, String> = stream::iter(playlist_ids)
+        .map(|playlist_id| async move {
+            let date = latest_added(client, access_token, &playlist_id).await?;
+            eyre::Ok(date.map(|d| (playlist_id, d)))
+        })
+        .buffer_unordered(concurrency.max(1))
+        .try_filter_map(|found| async move { Ok(found) })
+        .try_collect()
+        .await?;
+
+    for item in items {
+        item.last_activity = match (&item.channel_id, &item.playlist_id, &item.video_id) {
+            (Some(channel_id), _, _) => uploads.get(channel_id).cloned(),
+            (_, Some(playlist_id), _) => added.get(playlist_id).cloned(),
+            (_, _, Some(_)) => item.date.clone(),
+            _ => None,
+        };
+    }
+    Ok(())
+}
+
+/// Date the latest video was added to a playlist, `None` if it's empty or can't be read.
+/// Playlists can be sorted in any order, so all items are checked.
+async fn latest_added(
+    client: &YouTubeClient,
+    access_token: &str,
+    playlist_id: &str,
+) -> eyre::Result<Option<String>> {
+    let req_builder = PlaylistItemRequester { playlist_id };
+    let res: eyre::Result<Vec<_>> = item_stream(client, req_builder, access_token)
+        .try_collect()
+        .await;
+    let Some(items) = skip_item_error(res)? else {
+        return Ok(None);
+    };
+    // `RFC 3339` dates in `UTC` compare correctly as strings.
+    let latest = items
+        .into_iter()
+        .filter_map(|i| i.snippet?.published_at)
+        .filter(|d| !d.trim().is_empty())
+        .max();
+    Ok(latest)
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::{PlaylistRequester, SubscriptionRequester};
+    use crate::model::youtube::traits::IntoSearchableItem;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn add_last_activity_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let subs = item_stream(client, SubscriptionRequester, MOCK_ACCESS_TOKEN);
+        let playlists = item_stream(client, PlaylistRequester, MOCK_ACCESS_TOKEN);
+        let mut items: Vec<SearchableItem> = subs
+            .map_ok(IntoSearchableItem::into_item)
+            .try_collect()
+            .await
+            .unwrap();
+        let playlists: Vec<SearchableItem> = playlists
+            .map_ok(IntoSearchableItem::into_item)
+            .try_collect()
+            .await
+            .unwrap();
+        items.extend(playlists);
+
+        add_last_activity(client, MOCK_ACCESS_TOKEN, &mut items, 2)
+            .await
+            .unwrap();
+        let activity: HashMap<_, _> = items
+            .iter()
+            .map(|i| (i.title.as_deref().unwrap(), i.last_activity.as_deref()))
+            .collect();
+        assert_eq!(activity["AWS Events"], Some("2023-05-10T17:00:00Z"));
+        assert_eq!(activity["Dr. Plants"], None);
+        assert_eq!(activity["Истории"], None);
+        assert!(activity["Интервью"].is_some());
+    }
+}
@@ -1,51 +1,206 @@
-use crate::model::keyboards::types::Sorting;
+use std::cmp::Ordering;
+
+use futures::{StreamExt, TryStreamExt};
+
+use crate::model::keyboards::types::{SortOrder, Sorting};
+use crate::model::matching::funcs::fold_case;
 use crate::model::net::traits::{YouTubeApiRequestBuilder, YouTubeApiResponsePage};
-use crate::model::youtube::funcs::common::{items_request, pagination};
-use crate::model::youtube::traits::{IntoSearchableItem, Searchable};
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::common::item_stream;
+use crate::model::youtube::traits::IntoSearchableItem;
 use crate::model::youtube::types::SearchableItem;
-use std::fmt::Debug;
 
-#[allow(clippy::unwrap_used)]
-/// Returns all items on user's channel.
+/// Returns at most `res_limit` items on user's channel, in order `YouTube` returns them.
 pub(crate) async fn list_items<T>(
+    client: &YouTubeClient,
     req_builder: T,
     access_token: &str,
-    sorting: &Sorting,
     res_limit: u32,
-) -> Vec<SearchableItem>
+) -> eyre::Result<Vec<SearchableItem>>
 where
     T: YouTubeApiRequestBuilder,
-    T::Target: Default + Debug + YouTubeApiResponsePage,
+    T::Target: YouTubeApiResponsePage,
 {
     log::info!(" [:: LOG ::]    ( @:[fn::list_items] started )");
 
-    let client = reqwest::Client::new();
-    let resp = items_request(&client, access_token, &req_builder, None).await;
-    let search_res = resp.unwrap_or_default();
+    let results = item_stream(client, req_builder, access_token)
+        .take(res_limit as usize)
+        .map_ok(IntoSearchableItem::into_item)
+        .try_collect()
+        .await?;
 
-    let cap = (search_res.total_results().unwrap()).min(res_limit) as usize;
-    let mut store_in = Vec::with_capacity(cap);
-    let current_cap = std::sync::Arc::new(std::sync::Mutex::new(store_in.len()));
+    log::info!(" [:: LOG ::]    ( @:[fn::list_items] ended )");
+    Ok(results)
+}
 
-    let stop_if = |_: &T::Target| *current_cap.lock().unwrap() > res_limit as usize;
-    let f = |item: T::Target| {
-        if let Some(mut i) = item.items() {
-            store_in.append(&mut i);
+/// Sorts items, those without a value to sort by go last in both directions.
+/// `Sorting::LastActivity` needs `add_last_activity` to be called first.
+pub(crate) fn sort_items(items: &mut [SearchableItem], sorting: &Sorting) {
+    let order = sorting.order();
+    match *sorting {
+        Sorting::Alphabetical(_) => {
+            sort_by_opt_key(items, order, |i| i.title.as_deref().map(title_sort_key));
         }
-        *current_cap.lock().unwrap() = store_in.len();
-    };
+        Sorting::Date(_) => sort_by_opt_key(items, order, |i| i.date.clone()),
+        Sorting::ItemCount(_) => sort_by_opt_key(items, order, |i| i.item_count),
+        Sorting::LastActivity(_) => sort_by_opt_key(items, order, |i| i.last_activity.clone()),
+    }
+}
 
-    pagination(req_builder, access_token, stop_if, f).await;
+fn sort_by_opt_key<K: Ord>(
+    items: &mut [SearchableItem],
+    order: SortOrder,
+    key: impl Fn(&SearchableItem) -> Option<K>,
+) {
+    items.sort_by(|a, b| match (key(a), key(b)) {
+        (Some(a), Some(b)) if order == SortOrder::Ascending => a.cmp(&b),
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
 
-    log::info!(" [:: LOG ::]    ( @:[fn::list_items] ended )");
+/// Case-insensitive key for sorting titles.
+/// It isn't locale-aware: lowercased titles are compared by code points, so letters
+/// with diacritics (`é`, `ä`) and `ß` go after `z`. Only `ё` goes together with `е`,
+/// as in Russian dictionaries. Titles that differ only in case are ordered by the original text.
+pub(crate) fn title_sort_key(title: &str) -> (String, String) {
+    (fold_case(title.trim()), title.to_owned())
+}
 
-    match *sorting {
-        Sorting::Alphabetical => store_in.sort_by(|a, b| Ord::cmp(&a.title(), &b.title())),
-        Sorting::Date => store_in.sort_by(|a, b| Ord::cmp(&a.date(), &b.date())),
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::{LikedVideoRequester, PlaylistRequester, SubscriptionRequester};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn list_items_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let mut subs = list_items(client, SubscriptionRequester, MOCK_ACCESS_TOKEN, 50)
+            .await
+            .unwrap();
+        sort_items(&mut subs, &Sorting::Alphabetical(SortOrder::Ascending));
+        let titles: Vec<_> = subs.iter().map(|s| s.title.as_deref().unwrap()).collect();
+        assert_eq!(titles, ["AWS Events", "Dr. Plants", "iXBT games"]);
+        assert_eq!(mock_api.api_requests(), 2);
+
+        let mut playlists = list_items(client, PlaylistRequester, MOCK_ACCESS_TOKEN, 3)
+            .await
+            .unwrap();
+        sort_items(&mut playlists, &Sorting::Date(SortOrder::Ascending));
+        let titles: Vec<_> = playlists
+            .iter()
+            .map(|p| p.title.as_deref().unwrap())
+            .collect();
+        assert_eq!(titles, ["GAMETECH", "Интервью", "Истории"]);
+        // Pages after the limit is reached aren't requested.
+        assert_eq!(mock_api.api_requests(), 4);
+    }
+
+    fn titles(items: &[SearchableItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_deref().unwrap()).collect()
+    }
+
+    #[test]
+    fn sort_items_test() {
+        let item = |title: &str, item_count: Option<u32>| SearchableItem {
+            title: Some(title.to_owned()),
+            item_count,
+            ..Default::default()
+        };
+        let mut items = vec![
+            item("ёлки", Some(10)),
+            item("Ель", None),
+            item("apple", Some(410)),
+            item("Banana", Some(1)),
+            item("Жук", Some(105)),
+        ];
+
+        sort_items(&mut items, &Sorting::Alphabetical(SortOrder::Ascending));
+        assert_eq!(titles(&items), ["apple", "Banana", "ёлки", "Ель", "Жук"]);
+        sort_items(&mut items, &Sorting::Alphabetical(SortOrder::Descending));
+        assert_eq!(titles(&items), ["Жук", "Ель", "ёлки", "Banana", "apple"]);
+
+        sort_items(&mut items, &Sorting::ItemCount(SortOrder::Descending));
+        assert_eq!(titles(&items), ["apple", "Жук", "ёлки", "Banana", "Ель"]);
+        // Item without a count stays last in both directions.
+        sort_items(&mut items, &Sorting::ItemCount(SortOrder::Ascending));
+        assert_eq!(titles(&items), ["Banana", "ёлки", "Жук", "apple", "Ель"]);
+    }
+
+    #[test]
+    fn title_sort_key_test() {
+        let mut titles = [
+            "Éclair", "Straße", "zoo", "Ärzte", "Strauss", "apple", "Apple", "ёж", "Еда",
+        ];
+        titles.sort_by_key(|t| title_sort_key(t));
+        assert_eq!(
+            titles,
+            ["Apple", "apple", "Strauss", "Straße", "zoo", "Ärzte", "Éclair", "Еда", "ёж"]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_item_count_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let mut playlists = list_items(client, PlaylistRequester, MOCK_ACCESS_TOKEN, 50)
+            .await
+            .unwrap();
+        sort_items(&mut playlists, &Sorting::ItemCount(SortOrder::Descending));
+        assert_eq!(
+            titles(&playlists),
+            [
+                "Трансляции",
+                "Обзоры",
+                "GAMETECH",
+                "Истории",
+                "Интервью",
+                "Favorites"
+            ]
+        );
+
+        let mut subs = list_items(client, SubscriptionRequester, MOCK_ACCESS_TOKEN, 50)
+            .await
+            .unwrap();
+        sort_items(&mut subs, &Sorting::ItemCount(SortOrder::Ascending));
+        assert_eq!(titles(&subs), ["Dr. Plants", "iXBT games", "AWS Events"]);
+    }
+
+    #[tokio::test]
+    async fn list_liked_videos_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let mut videos = list_items(client, LikedVideoRequester, MOCK_ACCESS_TOKEN, 50)
+            .await
+            .unwrap();
+        sort_items(&mut videos, &Sorting::Date(SortOrder::Ascending));
+        let titles: Vec<_> = videos.iter().map(|v| v.title.as_deref().unwrap()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Rick Astley - Never Gonna Give You Up (Official Music Video)",
+                "PSY - GANGNAM STYLE(강남스타일) M/V",
+                "Cyberpunk 2077 — Official Gameplay Trailer"
+            ]
+        );
+
+        let rick_roll = &videos[0];
+        assert_eq!(
+            rick_roll.link.as_deref(),
+            Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+        );
+        let about = rick_roll.about.as_deref().unwrap();
+        assert!(about.contains("[Published by]\n\nRick Astley"));
+        assert!(about.contains("[Published at]\n \n2009-10-25T06:57:33Z"));
     }
-    store_in
-        .into_iter()
-        .take(res_limit as usize)
-        .map(IntoSearchableItem::into_item)
-        .collect()
 }
@@ -0,0 +1,69 @@
+use crate::model::net::types::YouTubeClient;
+use crate::model::playlist_stats::funcs::playlist_stats;
+use crate::model::playlist_stats::types::PlaylistStats;
+use crate::model::youtube::funcs::common::{playlist_contents, playlist_ids_and_titles};
+use crate::model::youtube::funcs::video_details::fetch_video_details;
+
+/// Pages items of all user's playlists, then looks up their videos.
+/// Videos of all playlists are looked up together, so each one is requested once.
+/// At most `concurrency` playlists are paged at once, ones that can't be read are skipped.
+pub(crate) async fn collect_playlist_stats(
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<Vec<PlaylistStats>> {
+    log::info!(" [:: LOG ::]    ( @:[fn::collect_playlist_stats] started )");
+
+    let playlists = playlist_ids_and_titles(client, access_token).await?;
+    let playlists = playlist_contents(client, access_token, playlists, concurrency).await?;
+
+    let video_ids = playlists
+        .iter()
+        .flat_map(|pl| &pl.items)
+        .filter_map(|i| i.content_details.as_ref()?.video_id.clone())
+        .collect();
+    let details = fetch_video_details(client, access_token, video_ids).await?;
+
+    log::info!(" [:: LOG ::]    ( @:[fn::collect_playlist_stats] ended )");
+
+    let stats = playlists
+        .into_iter()
+        .map(|pl| playlist_stats(pl.playlist_id, pl.title, &pl.items, &details))
+        .collect();
+    Ok(stats)
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn collect_playlist_stats_test() {
+        let mock_api = MockYouTubeApi::start();
+
+        let stats = collect_playlist_stats(&mock_api.client, MOCK_ACCESS_TOKEN, 2)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 6);
+
+        let reviews = stats.iter().find(|s| s.title == "Обзоры").unwrap();
+        assert_eq!((reviews.item_count, reviews.unavailable_count), (3, 1));
+        assert_eq!(reviews.total_runtime, Duration::from_secs(4427));
+
+        let favorites = stats.iter().find(|s| s.title == "Favorites").unwrap();
+        assert_eq!(favorites.total_runtime, Duration::from_secs(598));
+        assert_eq!(
+            favorites.newest.as_ref().unwrap().title,
+            "Best moments of E3"
+        );
+
+        let total: usize = stats.iter().map(|s| s.item_count).sum();
+        assert_eq!(total, 7);
+    }
+}
@@ -1,95 +1,201 @@
+use futures::{future, TryStreamExt};
+
 use crate::model::keyboards::types::SearchIn;
+use crate::model::matching::types::Matcher;
 use crate::model::net::traits::{YouTubeApiRequestBuilder, YouTubeApiResponsePage};
-use crate::model::youtube::funcs::common::pagination;
+use crate::model::net::types::YouTubeClient;
+use crate::model::youtube::funcs::common::item_stream;
 use crate::model::youtube::traits::{IntoSearchableItem, Searchable};
 use crate::model::youtube::types::SearchableItem;
-use std::fmt::Debug;
 
-#[allow(clippy::unwrap_used)]
 /// Search and filter items (subscriptions, playlists, etc).
+/// All items are checked, so that `res_limit` best matches are returned, best ones first.
 pub(crate) async fn search_items<T>(
+    client: &YouTubeClient,
     search_in: &SearchIn,
     req_builder: T,
-    search_for: &str,
+    matcher: &Matcher,
     access_token: &str,
     res_limit: u32,
-) -> Vec<SearchableItem>
+) -> eyre::Result<Vec<SearchableItem>>
 where
     T: YouTubeApiRequestBuilder,
-    T::Target: Default + Debug + YouTubeApiResponsePage,
+    T::Target: YouTubeApiResponsePage,
 {
     log::info!(" [:: LOG ::]    ( @:[fn::search_items] started )");
     log::info!(
         " [:: LOG ::]    ( @:[fn::search_items] INPUT is [ '{:?}' ] )",
-        (&search_in, &search_for, &res_limit)
+        (&search_in, &matcher, &res_limit)
     );
 
-    let mut store_in = vec![];
-    let current_cap = std::sync::Arc::new(std::sync::Mutex::new(store_in.len()));
-
-    let stop_if = |_: &T::Target| *current_cap.lock().unwrap() > res_limit as usize;
-    let f = |search_target: T::Target| {
-        if let Some(items) = search_target.items() {
-            find_matches(search_for, search_in, res_limit, items, &mut store_in);
-        }
-        *current_cap.lock().unwrap() = store_in.len();
-    };
-    pagination(req_builder, access_token, stop_if, f).await;
+    let scored: Vec<_> = item_stream(client, req_builder, access_token)
+        .try_filter_map(|item| {
+            let score = match_score(&item, matcher, search_in);
+            future::ok(score.map(|s| (s, item.into_item())))
+        })
+        .try_collect()
+        .await?;
+    let mut results = rank(scored);
+    results.truncate(res_limit as usize);
 
     log::info!(
-        " [:: LOG ::]    ( @:[fn::search_items] 'current_cap' is [ '{:?}' ] )",
-        current_cap
+        " [:: LOG ::]    ( @:[fn::search_items] 'results.len()' is [ '{:?}' ] )",
+        results.len()
     );
     log::info!(" [:: LOG ::]    ( @:[fn::search_items] ended )");
 
-    store_in
-        .into_iter()
-        .map(IntoSearchableItem::into_item)
-        .collect()
+    Ok(results)
 }
 
-/// Find matches in a list of subscriptions.
-fn find_matches<S>(
-    search_for: &str,
-    search_in: &SearchIn,
-    res_limit: u32,
-    items: Vec<S>,
-    store_in: &mut Vec<S>,
-) where
+/// Score of title or description (depending on `search_in`), `None` if it doesn't match.
+pub(crate) fn match_score<S>(item: &S, matcher: &Matcher, search_in: &SearchIn) -> Option<f64>
+where
     S: Searchable,
 {
-    log::info!(" [:: LOG ::]    ( @:[fn::find_matches] started )");
+    let compare_by = match *search_in {
+        SearchIn::Title => item.title(),
+        SearchIn::Description => item.description(),
+    };
+    compare_by.and_then(|t| matcher.score(t))
+}
 
-    let text_to_search = search_for.to_lowercase();
+/// Items with higher score go first, items with equal score keep their order.
+pub(crate) fn rank(mut scored: Vec<(f64, SearchableItem)>) -> Vec<SearchableItem> {
+    sort_by_score(&mut scored);
+    scored.into_iter().map(|(_, item)| item).collect()
+}
 
-    log::info!(
-        " [:: LOG ::]    ( @:[fn::find_matches] 'text_to_search' is [| '{:#?}' |] )",
-        (&text_to_search)
-    );
+/// Same order as `rank`, but scores are kept.
+pub(crate) fn sort_by_score(scored: &mut [(f64, SearchableItem)]) {
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+}
+
+/// Adds scored items to already ranked ones, so that the whole list stays ranked.
+/// Items with equal score go after the ones that are already there.
+pub(crate) fn merge_ranked(
+    ranked: &mut Vec<(f64, SearchableItem)>,
+    scored: Vec<(f64, SearchableItem)>,
+) {
+    for (score, item) in scored {
+        let at = ranked.partition_point(|(s, _)| *s >= score);
+        ranked.insert(at, (score, item));
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use google_youtube3::api::SubscriptionListResponse;
+
+    use crate::model::errors::YouTubeApiError;
+    use crate::model::keyboards::types::MatchMode;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::SubscriptionRequester;
+
+    use super::*;
+
+    fn matcher(match_mode: MatchMode, text_to_search: &str) -> Matcher {
+        Matcher::new(match_mode, text_to_search).unwrap()
+    }
 
-    for item in items {
-        let compare_by = match *search_in {
-            SearchIn::Title => item.title(),
-            SearchIn::Description => item.description(),
+    #[test]
+    fn match_score_test() {
+        let subs =
+            std::fs::read_to_string("../../test_assets/subscription_list_json_response.json")
+                .unwrap();
+        let subs = serde_json::from_str::<SubscriptionListResponse>(&subs)
+            .unwrap()
+            .items
+            .unwrap();
+        let sub = subs
+            .iter()
+            .find(|s| s.title() == Some("Dr. Plants"))
+            .unwrap();
+
+        let score =
+            |match_mode, text, search_in| match_score(sub, &matcher(match_mode, text), &search_in);
+        assert!(score(MatchMode::Exact, "plants", SearchIn::Title).is_some());
+        assert!(score(MatchMode::Exact, "Dr. P", SearchIn::Title).is_some());
+        assert!(score(MatchMode::Exact, "plants dr", SearchIn::Title).is_none());
+        assert!(score(MatchMode::AllWords, "plants dr", SearchIn::Title).is_some());
+        assert!(score(MatchMode::Fuzzy, "dr plans", SearchIn::Title).is_some());
+        assert!(score(MatchMode::Exact, "zzz", SearchIn::Description).is_none());
+    }
+
+    #[test]
+    fn rank_test() {
+        let item = |title: &str| SearchableItem {
+            title: Some(title.to_owned()),
+            ..Default::default()
         };
+        let ranked = rank(vec![(0.2, item("a")), (0.9, item("b")), (0.2, item("c"))]);
+        let titles: Vec<_> = ranked.iter().map(|i| i.title.as_deref().unwrap()).collect();
+        assert_eq!(titles, ["b", "a", "c"]);
+    }
 
-        log::info!(
-            " [:: LOG ::]    ( @:[fn::find_matches] 'compare_by' is [| '{:#?}' |] )",
-            (&compare_by)
+    #[test]
+    fn merge_ranked_test() {
+        let item = |title: &str| SearchableItem {
+            title: Some(title.to_owned()),
+            ..Default::default()
+        };
+        let mut ranked = vec![(0.9, item("a")), (0.5, item("b"))];
+        merge_ranked(
+            &mut ranked,
+            vec![(1.0, item("c")), (0.5, item("d")), (0.1, item("e"))],
         );
-
-        if let Some(title_or_descr) = compare_by {
-            if store_in.len() < res_limit as usize
-                && title_or_descr.to_lowercase().contains(&text_to_search)
-            {
-                store_in.push(item);
-            }
-        }
+        let titles: Vec<_> = ranked
+            .iter()
+            .map(|(_, i)| i.title.as_deref().unwrap())
+            .collect();
+        assert_eq!(titles, ["c", "a", "b", "d", "e"]);
     }
 
-    log::info!(
-        " [:: LOG ::]    ( @:[fn::find_matches] 'store_in.len()' is [| '{:#?}' |] )",
-        (&store_in.len())
-    );
-    log::info!(" [:: LOG ::]    ( @:[fn::find_matches] ended )");
+    #[tokio::test]
+    async fn search_items_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let found = search_items(
+            client,
+            &SearchIn::Title,
+            SubscriptionRequester,
+            &matcher(MatchMode::Exact, "EVENTS"),
+            MOCK_ACCESS_TOKEN,
+            10,
+        )
+        .await
+        .unwrap();
+        let titles: Vec<_> = found.iter().map(|i| i.title.as_deref().unwrap()).collect();
+        assert_eq!(titles, ["AWS Events"]);
+
+        let found = search_items(
+            client,
+            &SearchIn::Description,
+            SubscriptionRequester,
+            &matcher(MatchMode::Exact, "обзоры"),
+            MOCK_ACCESS_TOKEN,
+            10,
+        )
+        .await
+        .unwrap();
+        let titles: Vec<_> = found.iter().map(|i| i.title.as_deref().unwrap()).collect();
+        assert_eq!(titles, ["iXBT games"]);
+
+        let err = search_items(
+            client,
+            &SearchIn::Title,
+            SubscriptionRequester,
+            &matcher(MatchMode::Exact, "events"),
+            "expired_token",
+            10,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<YouTubeApiError>(),
+            Some(YouTubeApiError::AuthError { .. })
+        ));
+    }
 }
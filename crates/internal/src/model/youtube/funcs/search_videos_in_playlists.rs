@@ -1,142 +1,202 @@
 use error_traits::PassErrWith;
-use google_youtube3::api::PlaylistItemListResponse;
+use futures::stream::{self, Stream, StreamExt};
+use futures::{future, TryStreamExt};
 use maptypings::WrapInRes;
 use std::fmt::Display;
-use tokio::task::JoinHandle;
 
 use crate::model::keyboards::types::SearchIn;
-use crate::model::net::traits::YouTubeApiResponsePage;
-use crate::model::net::types::{PlaylistItemRequester, PlaylistRequester};
-use crate::model::youtube::funcs::common::{items_request, pagination};
-use crate::model::youtube::traits::{IntoSearchableItem, Searchable};
-use crate::model::youtube::types::SearchableItem;
-
-#[allow(clippy::unwrap_used)]
-/// Returns all items on user's channel.
-pub(crate) async fn search_videos_in_playlists(
-    search_in: &SearchIn,
-    search_for: &str,
-    access_token: &str,
-    res_limit: u32,
-) -> Vec<SearchableItem> {
+use crate::model::matching::types::Matcher;
+use crate::model::net::types::{PlaylistItemRequester, PlaylistRequester, YouTubeClient};
+use crate::model::youtube::funcs::common::{is_item_error, item_stream};
+use crate::model::youtube::funcs::search_cmd::{match_score, sort_by_score};
+use crate::model::youtube::funcs::video_details::add_video_details;
+use crate::model::youtube::traits::{add_playlist_title, IntoSearchableItem, Searchable};
+use crate::model::youtube::types::{PlaylistScan, SearchableItem};
+
+/// Scans user's playlists, at most `concurrency` of them at once.
+/// Matches of each playlist are yielded as soon as it's scanned, best ones first.
+/// Playlist that can't be read is counted as scanned without matches,
+/// only fatal errors (like expired token or exhausted quota) end the stream.
+/// Dropping the stream cancels playlists that are still being scanned.
+pub(crate) async fn search_videos_in_playlists<'a>(
+    client: &'a YouTubeClient,
+    search_in: &'a SearchIn,
+    matcher: &'a Matcher,
+    access_token: &'a str,
+    concurrency: usize,
+) -> eyre::Result<impl Stream<Item = eyre::Result<PlaylistScan>> + 'a> {
     log::info!(" [:: LOG ::]    ( @:[fn::search_videos_in_playlists] started )");
 
-    let mut store_in = vec![];
-    let client = reqwest::Client::new();
-
-    let mut next_page_token = None;
-    loop {
-        let resp = items_request(&client, access_token, &PlaylistRequester, next_page_token).await;
-        let search_res = resp.unwrap_or_default();
-        next_page_token = search_res.next_page_token();
-
-        let results: Vec<JoinHandle<Vec<SearchableItem>>> = search_res
-            .items
-            .into_iter()
-            .flatten()
-            .map(|playlist| {
-                let pl_title = playlist.title().unwrap_or_default().to_owned();
-                let pl_id = playlist.id.clone().unwrap_or_default();
-                let search_in = search_in.clone();
-                let (access_token, search_for) = (access_token.to_owned(), search_for.to_owned());
-                tokio::spawn(find_videos_in_playlist(
-                    pl_title,
-                    pl_id,
-                    search_in,
-                    search_for,
-                    access_token,
-                ))
-            })
-            .collect();
-        for i in results {
-            let log_prefix = "@:[fn::items_request] ";
-            let log_err = |e: &_| log::error!("{log_prefix}{e:?}");
-            let mut res = i.await.pass_err_with(log_err).unwrap_or_default();
-            store_in.append(&mut res);
-        }
-
-        if next_page_token.is_none() {
-            break;
-        }
-    }
-
-    log::info!(" [:: LOG ::]    ( @:[fn::search_videos_in_playlists] ended )");
+    let playlists: Vec<_> = item_stream(client, PlaylistRequester, access_token)
+        .try_collect()
+        .await?;
+
+    let total = playlists.len();
+    let scans = stream::iter(playlists)
+        .map(move |playlist| {
+            let pl_title = playlist.title().unwrap_or_default().to_owned();
+            let pl_id = playlist.id.unwrap_or_default();
+            let search_in = search_in.clone();
+            find_videos_in_playlist(client, pl_title, pl_id, search_in, matcher, access_token)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .enumerate()
+        .map(move |(i, matches)| to_scan(i + 1, total, matches));
+    Ok(scans)
+}
 
-    store_in.into_iter().take(res_limit as usize).collect()
+/// Result of one playlist, failed playlist has no matches unless its error is fatal.
+fn to_scan(
+    scanned: usize,
+    total: usize,
+    matches: eyre::Result<Vec<(f64, SearchableItem)>>,
+) -> eyre::Result<PlaylistScan> {
+    let matches = match matches {
+        Ok(matches) => matches,
+        Err(e) if is_item_error(&e) => vec![],
+        Err(e) => return Err(e),
+    };
+    Ok(PlaylistScan {
+        scanned,
+        total,
+        matches,
+    })
 }
 
 async fn find_videos_in_playlist(
+    client: &YouTubeClient,
     pl_title: impl Display,
     pl_id: impl Display,
     search_in: SearchIn,
-    search_for: impl Display,
+    matcher: &Matcher,
     access_token: impl Display,
-) -> Vec<SearchableItem> {
+) -> eyre::Result<Vec<(f64, SearchableItem)>> {
     log::info!(" [:: LOG ::]    ( @:[fn::find_videos_in_playlist] started )");
 
-    find_videos_in_playlist_helper(pl_title, pl_id, search_in, search_for, access_token)
+    let pl_title = pl_title.to_string();
+    find_videos_in_playlist_helper(client, &pl_title, pl_id, search_in, matcher, access_token)
         .await
-        .pass_err_with(|e| log::error!("{e:?}"))
-        .unwrap_or_default()
+        .pass_err_with(|e| log::error!("playlist '{pl_title}' failed: {e:?}"))
 }
 
 async fn find_videos_in_playlist_helper(
+    client: &YouTubeClient,
     pl_title: impl Display,
     pl_id: impl Display,
     search_in: SearchIn,
-    search_for: impl Display,
+    matcher: &Matcher,
     access_token: impl Display,
-) -> eyre::Result<Vec<SearchableItem>> {
+) -> eyre::Result<Vec<(f64, SearchableItem)>> {
     log::info!(" [:: LOG ::]    ( @:[fn::find_videos_in_playlist_helper] started )");
 
-    let mut store_in: Vec<SearchableItem> = vec![];
-    let text_to_search = search_for.to_string().to_lowercase();
-
-    let stop_if = |_: &_| false;
-    let f = |search_target: PlaylistItemListResponse| {
-        for i in search_target.items.into_iter().flatten() {
-            log::info!("@:[fn::find_videos_in_playlist_helper] <playlistItem> is: {i:#?}");
-
-            let compare_by = match search_in {
-                SearchIn::Title => i.title(),
-                SearchIn::Description => i.description(),
-            };
-            log::info!("@:[fn::find_videos_in_playlist_helper] <compare_by> is: {compare_by:?}");
-            log::info!(
-                "@:[fn::find_videos_in_playlist_helper] <text_to_search> is: {text_to_search:?}"
-            );
-            if let Some(compare_by) = compare_by {
-                if compare_by.to_lowercase().contains(&text_to_search) {
-                    store_in.push(i.into_item());
-                }
-            }
-        }
+    let (playlist_id, access_token) = (pl_id.to_string(), access_token.to_string());
+    let req_builder = PlaylistItemRequester {
+        playlist_id: &playlist_id,
     };
-    let playlist_id = pl_id.to_string();
-    let access_token = access_token.to_string();
-    pagination(
-        PlaylistItemRequester {
-            playlist_id: &playlist_id,
-        },
-        &access_token,
-        stop_if,
-        f,
-    )
-    .await;
+    let mut scored: Vec<_> = item_stream(client, req_builder, &access_token)
+        .try_filter_map(|i| {
+            let score = match_score(&i, matcher, &search_in);
+            future::ok(score.map(|s| (s, i.into_item())))
+        })
+        .try_collect()
+        .await?;
+    sort_by_score(&mut scored);
+    let (scores, mut store_in): (Vec<_>, Vec<_>) = scored.into_iter().unzip();
 
     log::info!(
         "@:[fn::find_videos_in_playlist_helper] <store_in.len()> is: {:?}",
         store_in.len()
     );
 
+    add_video_details(client, &access_token, &mut store_in).await?;
+
+    let pl_title = pl_title.to_string();
     for i in &mut store_in {
         log::info!("@:[fn::find_videos_in_playlist_helper] <searchableItem> is: {i:#?}");
 
-        let opt_about = i.about.as_mut();
-        if let Some(about) = opt_about {
-            *about = about.replace("[Playlist]\n", &format!("[Playlist]\n \n{pl_title}"));
-        }
+        add_playlist_title(i, &pl_title);
     }
 
-    store_in.in_ok()
+    scores.into_iter().zip(store_in).collect::<Vec<_>>().in_ok()
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::errors::YouTubeApiError;
+    use crate::model::keyboards::types::MatchMode;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn search_videos_in_playlists_test() {
+        let mock_api = MockYouTubeApi::start();
+        let (client, search_in) = (&mock_api.client, SearchIn::Title);
+
+        let matcher = Matcher::new(MatchMode::Exact, "CYBERPUNK").unwrap();
+        let scans: Vec<_> =
+            search_videos_in_playlists(client, &search_in, &matcher, MOCK_ACCESS_TOKEN, 2)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+        assert_eq!(scans.len(), 6);
+        assert!(scans.iter().all(|s| s.total == 6));
+        assert_eq!(scans.last().unwrap().scanned, 6);
+
+        let mut titles: Vec<_> = scans
+            .into_iter()
+            .flat_map(|s| s.matches)
+            .map(|(_, i)| i.title.unwrap())
+            .collect();
+        titles.sort();
+        assert_eq!(
+            titles,
+            [
+                "Интервью с разработчиками Cyberpunk 2077",
+                "Интервью с разработчиками Cyberpunk 2077",
+                "Обзор Cyberpunk 2077"
+            ]
+        );
+    }
+
+    #[test]
+    fn to_scan_test() {
+        let not_found = YouTubeApiError::NotFound {
+            reason: "playlistNotFound".to_owned(),
+            message: String::new(),
+        };
+        let scan = to_scan(2, 3, Err(not_found.into())).unwrap();
+        assert_eq!((scan.scanned, scan.total, scan.matches.len()), (2, 3, 0));
+
+        let quota = YouTubeApiError::QuotaExceeded {
+            message: String::new(),
+        };
+        assert!(to_scan(2, 3, Err(quota.into())).is_err());
+        assert!(to_scan(2, 3, Err(eyre::eyre!("connection reset"))).is_err());
+    }
+
+    #[tokio::test]
+    async fn find_videos_in_missing_playlist_test() {
+        let mock_api = MockYouTubeApi::start();
+        let (client, search_in) = (&mock_api.client, SearchIn::Title);
+
+        let err = find_videos_in_playlist(
+            client,
+            "Deleted",
+            "PLdeleted",
+            search_in,
+            &Matcher::new(MatchMode::Exact, "cyberpunk").unwrap(),
+            MOCK_ACCESS_TOKEN,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<YouTubeApiError>(),
+            Some(YouTubeApiError::NotFound { reason, .. }) if reason == "playlistNotFound"
+        ));
+    }
 }
@@ -0,0 +1,66 @@
+use futures::future;
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use crate::model::net::types::{PlaylistItemRequester, YouTubeClient};
+use crate::model::snapshot::funcs::snapshot_item;
+use crate::model::snapshot::types::{PlaylistSnapshot, SnapshotItem, TakenSnapshots};
+use crate::model::youtube::funcs::common::{is_item_error, item_stream, playlist_ids_and_titles};
+
+/// Takes fresh snapshots of all user's playlists, at most `concurrency` of them at once.
+/// Playlists that can't be read are listed as `unreadable`, only fatal errors are returned.
+pub(crate) async fn take_playlist_snapshots(
+    client: &YouTubeClient,
+    access_token: &str,
+    concurrency: usize,
+) -> eyre::Result<TakenSnapshots> {
+    log::info!(" [:: LOG ::]    ( @:[fn::take_playlist_snapshots] started )");
+
+    let playlists = playlist_ids_and_titles(client, access_token).await?;
+
+    let taken_at = time::OffsetDateTime::now_utc();
+    let taken = stream::iter(playlists)
+        .map(|(playlist_id, title)| async move {
+            let items = playlist_snapshot_items(client, &playlist_id, access_token).await;
+            (playlist_id, title, items)
+        })
+        .buffer_unordered(concurrency.max(1))
+        .map(Ok)
+        .try_fold(
+            TakenSnapshots::default(),
+            |mut taken, (playlist_id, title, items)| async move {
+                match items {
+                    Ok(items) => {
+                        let snapshot = PlaylistSnapshot {
+                            playlist_id: playlist_id.clone(),
+                            title,
+                            taken_at,
+                            items,
+                        };
+                        taken.snapshots.insert(playlist_id, snapshot);
+                    }
+                    Err(e) if is_item_error(&e) => {
+                        log::warn!("@:[fn::take_playlist_snapshots] '{title}' is skipped: {e:?}");
+                        taken.unreadable.insert(playlist_id);
+                    }
+                    Err(e) => return Err(e),
+                }
+                Ok(taken)
+            },
+        )
+        .await?;
+
+    log::info!(" [:: LOG ::]    ( @:[fn::take_playlist_snapshots] ended )");
+
+    Ok(taken)
+}
+
+async fn playlist_snapshot_items(
+    client: &YouTubeClient,
+    playlist_id: &str,
+    access_token: &str,
+) -> eyre::Result<Vec<SnapshotItem>> {
+    item_stream(client, PlaylistItemRequester { playlist_id }, access_token)
+        .try_filter_map(|i| future::ready(Ok(snapshot_item(&i))))
+        .try_collect()
+        .await
+}
@@ -0,0 +1,68 @@
+use futures::{StreamExt, TryStreamExt};
+
+use crate::model::net::types::{MyChannelRequester, YouTubeClient};
+use crate::model::youtube::funcs::common::item_stream;
+
+/// Id of the playlist with videos uploaded to user's own channel.
+/// `None` if user has no channel.
+pub(crate) async fn uploads_playlist_id(
+    client: &YouTubeClient,
+    access_token: &str,
+) -> eyre::Result<Option<String>> {
+    log::info!(" [:: LOG ::]    ( @:[fn::uploads_playlist_id] started )");
+
+    let channels: Vec<_> = item_stream(client, MyChannelRequester, access_token)
+        .take(1)
+        .try_collect()
+        .await?;
+    let playlist_id = channels
+        .into_iter()
+        .find_map(|chan| chan.content_details?.related_playlists?.uploads)
+        .filter(|id| !id.trim().is_empty());
+    Ok(playlist_id)
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::keyboards::types::{MatchMode, SearchIn};
+    use crate::model::matching::types::Matcher;
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+    use crate::model::net::types::PlaylistItemRequester;
+    use crate::model::youtube::funcs::search_cmd::search_items;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn search_in_uploads_test() {
+        let mock_api = MockYouTubeApi::start();
+        let client = &mock_api.client;
+
+        let playlist_id = uploads_playlist_id(client, MOCK_ACCESS_TOKEN)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(playlist_id, "UU4e_XPBiiIO4fo4_CucxQeg");
+
+        let req_builder = PlaylistItemRequester {
+            playlist_id: &playlist_id,
+        };
+        let (search_in, matcher) = (
+            SearchIn::Description,
+            Matcher::new(MatchMode::Exact, "стрим").unwrap(),
+        );
+        let found = search_items(
+            client,
+            &search_in,
+            req_builder,
+            &matcher,
+            MOCK_ACCESS_TOKEN,
+            10,
+        )
+        .await
+        .unwrap();
+        let titles: Vec<_> = found.iter().map(|i| i.title.as_deref().unwrap()).collect();
+        assert_eq!(titles, ["Как мы делаем обзоры"]);
+    }
+}
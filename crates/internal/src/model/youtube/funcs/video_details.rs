@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use google_youtube3::api::Video;
+
+use crate::model::net::types::{VideoRequester, YouTubeClient, IDS_PER_REQUEST};
+use crate::model::youtube::funcs::common::item_stream;
+use crate::model::youtube::types::{Availability, SearchableItem, VideoDetails};
+
+/// Fills `video` of every item that has a `video_id` and no details yet.
+/// Ids are looked up with `videos.list`, `IDS_PER_REQUEST` per call.
+/// Videos that `videos.list` doesn't return are marked as unavailable.
+pub(crate) async fn add_video_details(
+    client: &YouTubeClient,
+    access_token: &str,
+    items: &mut [SearchableItem],
+) -> eyre::Result<()> {
+    log::info!(" [:: LOG ::]    ( @:[fn::add_video_details] started )");
+
+    let video_ids: Vec<String> = items
+        .iter()
+        .filter(|item| item.video.is_none())
+        .filter_map(|item| item.video_id.clone())
+        .collect();
+    let details = fetch_video_details(client, access_token, video_ids).await?;
+
+    for item in items.iter_mut().filter(|item| item.video.is_none()) {
+        if let Some(id) = &item.video_id {
+            item.video = details_or_unavailable(&details, id).into();
+        }
+    }
+    Ok(())
+}
+
+/// Details of videos, that `videos.list` returned, by video id.
+/// Ids may repeat, each one is requested once.
+pub(crate) async fn fetch_video_details(
+    client: &YouTubeClient,
+    access_token: &str,
+    mut video_ids: Vec<String>,
+) -> eyre::Result<HashMap<String, VideoDetails>> {
+    video_ids.sort_unstable();
+    video_ids.dedup();
+
+    let mut details = HashMap::with_capacity(video_ids.len());
+    for chunk in video_ids.chunks(IDS_PER_REQUEST) {
+        let req_builder = VideoRequester { video_ids: chunk };
+        let videos: Vec<Video> = item_stream(client, req_builder, access_token)
+            .try_collect()
+            .await?;
+        for video in videos {
+            if let Some(id) = video.id.clone() {
+                details.insert(id, video_details(&video));
+            }
+        }
+    }
+    Ok(details)
+}
+
+/// Video missing from `videos.list` response is deleted or private.
+pub(crate) fn details_or_unavailable(
+    details: &HashMap<String, VideoDetails>,
+    video_id: &str,
+) -> VideoDetails {
+    details
+        .get(video_id)
+        .cloned()
+        .unwrap_or_else(|| VideoDetails {
+            availability: Availability::Unavailable,
+            ..Default::default()
+        })
+}
+
+/// Details of the video returned by `videos.list`.
+pub(crate) fn video_details(video: &Video) -> VideoDetails {
+    let count = |c: &Option<String>| c.as_deref().and_then(|c| c.parse().ok());
+    let privacy_status = video
+        .status
+        .as_ref()
+        .and_then(|s| s.privacy_status.clone())
+        .filter(|s| !s.trim().is_empty());
+    let availability = match privacy_status.as_deref() {
+        Some("private") => Availability::Private,
+        _ => Availability::Available,
+    };
+    let statistics = video.statistics.as_ref();
+    VideoDetails {
+        duration: video
+            .content_details
+            .as_ref()
+            .and_then(|c| c.duration.as_deref())
+            .and_then(parse_iso8601_duration),
+        view_count: statistics.and_then(|s| count(&s.view_count)),
+        like_count: statistics.and_then(|s| count(&s.like_count)),
+        privacy_status,
+        availability,
+    }
+}
+
+/// Parses durations in the form `YouTube` uses, e.g. `PT1H2M3S` or `P1DT2H`.
+/// Years and months are never used for videos, so they aren't supported.
+pub(crate) fn parse_iso8601_duration(text: &str) -> Option<Duration> {
+    let rest = text.strip_prefix('P')?;
+    let (date, time) = rest.split_once('T').unwrap_or((rest, ""));
+    if date.is_empty() && time.is_empty() {
+        return None;
+    }
+    let date_units: &[(char, u64)] = &[('W', 7 * 24 * 3600), ('D', 24 * 3600)];
+    let time_units: &[(char, u64)] = &[('H', 3600), ('M', 60), ('S', 1)];
+
+    let mut secs = 0;
+    for (mut part, units) in [(date, date_units), (time, time_units)] {
+        for &(unit, unit_secs) in units {
+            if let Some((num, after)) = part.split_once(unit) {
+                secs += num.parse::<u64>().ok()? * unit_secs;
+                part = after;
+            }
+        }
+        if !part.is_empty() {
+            return None;
+        }
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used)]
+#[cfg(test)]
+mod tests {
+    use crate::model::net::mock_server::{MockYouTubeApi, MOCK_ACCESS_TOKEN};
+
+    use super::*;
+
+    #[test]
+    fn parse_iso8601_duration_test() {
+        let secs = |s| parse_iso8601_duration(s).map(|d| d.as_secs());
+        assert_eq!(secs("PT3M33S"), Some(213));
+        assert_eq!(secs("PT1H12M5S"), Some(4325));
+        assert_eq!(secs("PT25M"), Some(1500));
+        assert_eq!(secs("P1DT2H3M4S"), Some(93784));
+        assert_eq!(secs("P0D"), Some(0));
+        assert_eq!(secs("P1W"), Some(604_800));
+        assert_eq!(secs("PT"), None);
+        assert_eq!(secs("P1Y2M"), None);
+        assert_eq!(secs("PT5S3M"), None);
+        assert_eq!(secs("3M"), None);
+    }
+
+    #[tokio::test]
+    async fn add_video_details_test() {
+        let mock_api = MockYouTubeApi::start();
+        let item = |id: &str| SearchableItem {
+            video_id: Some(id.to_owned()),
+            ..Default::default()
+        };
+        let mut items = vec![
+            item("Yy_m44vlXBk"),
+            item("dQ8mZ3kY1vE"),
+            item("kX0wQfRrnM4"),
+            SearchableItem::default(),
+            item("Yy_m44vlXBk"),
+        ];
+
+        add_video_details(&mock_api.client, MOCK_ACCESS_TOKEN, &mut items)
+            .await
+            .unwrap();
+        assert_eq!(mock_api.api_requests(), 1);
+
+        let public = items[0].video.clone().unwrap();
+        assert_eq!(public.duration, Some(Duration::from_secs(4325)));
+        assert_eq!(public.view_count, Some(84512));
+        assert_eq!(public.like_count, Some(3120));
+        assert_eq!(public.privacy_status.as_deref(), Some("public"));
+        assert_eq!(public.availability, Availability::Available);
+
+        let deleted = items[1].video.clone().unwrap();
+        assert_eq!(deleted.availability, Availability::Unavailable);
+        assert_eq!(deleted.duration, None);
+
+        let private = items[2].video.clone().unwrap();
+        assert_eq!(private.availability, Availability::Private);
+        assert_eq!(private.like_count, None);
+
+        assert!(items[3].video.is_none());
+        assert_eq!(items[4].video, items[0].video);
+    }
+}
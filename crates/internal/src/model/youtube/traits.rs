@@ -1,6 +1,8 @@
+use crate::model::export::types::{ExportRecord, CHANNEL_FEED_URL, PLAYLIST_FEED_URL};
 use crate::model::utils::HTMLise;
+use crate::model::youtube::funcs::video_details::video_details;
 use crate::model::youtube::types::SearchableItem;
-use google_youtube3::api::{Playlist, PlaylistItem, Subscription};
+use google_youtube3::api::{Channel, Playlist, PlaylistItem, Subscription, Video};
 
 /// Anything that can be searched on user's `YouTube` channel.
 pub(crate) trait Searchable {
@@ -131,6 +133,125 @@ impl Searchable for PlaylistItem {
     }
 }
 
+impl Searchable for Video {
+    fn title(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .title
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .description
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn date(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .published_at
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn link(&self) -> Option<String> {
+        let video_id = self.id.as_ref();
+        video_id
+            .filter(|s| !s.trim().is_empty())
+            .map(|video_id| format!("https://www.youtube.com/watch?v={video_id}"))
+    }
+
+    fn about(&self) -> Option<String> {
+        let snippet = self.snippet.clone()?;
+        construct_about_for_video(
+            snippet.channel_title,
+            snippet.channel_id,
+            snippet.published_at,
+        )
+        .into()
+    }
+}
+
+impl Searchable for Channel {
+    fn title(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .title
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .description
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn date(&self) -> Option<&str> {
+        self.snippet
+            .as_ref()?
+            .published_at
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+    }
+
+    fn link(&self) -> Option<String> {
+        let chan_id = self.id.as_ref();
+        chan_id
+            .filter(|s| !s.trim().is_empty())
+            .map(|chan_id| format!("https://youtube.com/channel/{chan_id}"))
+    }
+
+    fn about(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Anything that can be exported with `export` command.
+pub(crate) trait Exportable: Searchable {
+    /// `None` if item has no id.
+    fn export_record(&self) -> Option<ExportRecord>;
+}
+
+/// Builds record from item's `id` and its `RSS` feed url prefix.
+fn export_record_with_id<E: Searchable>(item: &E, id: &str, feed_url: &str) -> ExportRecord {
+    ExportRecord {
+        id: id.to_owned(),
+        title: item.title().unwrap_or_default().to_owned(),
+        description: item.description().unwrap_or_default().to_owned(),
+        published_at: item.date().unwrap_or_default().to_owned(),
+        url: item.link().unwrap_or_default(),
+        feed_url: format!("{feed_url}{id}"),
+    }
+}
+
+impl Exportable for Subscription {
+    fn export_record(&self) -> Option<ExportRecord> {
+        let chan_id = self
+            .snippet
+            .as_ref()?
+            .resource_id
+            .as_ref()?
+            .channel_id
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())?;
+        export_record_with_id(self, chan_id, CHANNEL_FEED_URL).into()
+    }
+}
+
+impl Exportable for Playlist {
+    fn export_record(&self) -> Option<ExportRecord> {
+        let plist_id = self.id.as_deref().filter(|s| !s.trim().is_empty())?;
+        export_record_with_id(self, plist_id, PLAYLIST_FEED_URL).into()
+    }
+}
+
 pub(crate) trait IntoSearchableItem {
     fn into_item(self) -> SearchableItem;
 }
@@ -142,12 +263,16 @@ impl IntoSearchableItem for Subscription {
             item.title = snippet.title.filter(|s| !s.trim().is_empty());
             item.description = snippet.description.filter(|s| !s.trim().is_empty());
             item.date = snippet.published_at.filter(|s| !s.trim().is_empty());
-            item.link = snippet
+            item.channel_id = snippet
                 .resource_id
                 .and_then(|r_id| r_id.channel_id)
-                .filter(|s| !s.trim().is_empty())
+                .filter(|s| !s.trim().is_empty());
+            item.link = item
+                .channel_id
+                .as_ref()
                 .map(|chan_id| format!("https://youtube.com/channel/{chan_id}"));
         }
+        item.item_count = self.content_details.and_then(|c| c.total_item_count);
         item
     }
 }
@@ -163,8 +288,10 @@ impl IntoSearchableItem for Playlist {
         if let Some(plist_id) = self.id {
             if !plist_id.trim().is_empty() {
                 item.link = format!("https://youtube.com/playlist?list={plist_id}").into();
+                item.playlist_id = Some(plist_id);
             }
         }
+        item.item_count = self.content_details.and_then(|c| c.item_count);
         item
     }
 }
@@ -177,6 +304,7 @@ impl IntoSearchableItem for PlaylistItem {
             item.description = snippet.description.filter(|s| !s.trim().is_empty());
             item.date = snippet.published_at.filter(|s| !s.trim().is_empty());
             let video_id = snippet.resource_id.and_then(|r_id| r_id.video_id);
+            item.video_id = video_id.clone().filter(|s| !s.trim().is_empty());
             let index_in_pl = snippet.position;
             if let (Some(pl_item_id), Some(v), Some(idx)) = (self.id, video_id, index_in_pl) {
                 let id = pl_item_id;
@@ -199,6 +327,52 @@ impl IntoSearchableItem for PlaylistItem {
     }
 }
 
+impl IntoSearchableItem for Video {
+    fn into_item(self) -> SearchableItem {
+        let mut item = SearchableItem {
+            link: self.link(),
+            video_id: self.id.clone().filter(|s| !s.trim().is_empty()),
+            video: Some(video_details(&self)),
+            ..Default::default()
+        };
+        if let Some(snippet) = self.snippet {
+            item.title = snippet.title.filter(|s| !s.trim().is_empty());
+            item.description = snippet.description.filter(|s| !s.trim().is_empty());
+            item.date = snippet.published_at.filter(|s| !s.trim().is_empty());
+            item.about = construct_about_for_video(
+                snippet.channel_title,
+                snippet.channel_id,
+                item.date.clone(),
+            )
+            .into();
+        }
+        item
+    }
+}
+
+impl IntoSearchableItem for Channel {
+    fn into_item(self) -> SearchableItem {
+        let mut item = SearchableItem {
+            link: self.link(),
+            ..Default::default()
+        };
+        if let Some(snippet) = self.snippet {
+            item.title = snippet.title.filter(|s| !s.trim().is_empty());
+            item.description = snippet.description.filter(|s| !s.trim().is_empty());
+            item.date = snippet.published_at.filter(|s| !s.trim().is_empty());
+        }
+        item
+    }
+}
+
+/// Puts playlist title into `about` made by `construct_about_for_pl_item`,
+/// since playlist item knows only the id of its playlist.
+pub(crate) fn add_playlist_title(item: &mut SearchableItem, pl_title: &str) {
+    if let Some(about) = item.about.as_mut() {
+        *about = about.replace("[Playlist]\n", &format!("[Playlist]\n \n{pl_title}"));
+    }
+}
+
 fn construct_about_for_pl_item(
     playlist_id: Option<String>,
     video_owner_channel_title: Option<String>,
@@ -233,6 +407,36 @@ fn construct_about_for_pl_item(
     about
 }
 
+fn construct_about_for_video(
+    channel_title: Option<String>,
+    channel_id: Option<String>,
+    published_at: Option<String>,
+) -> String {
+    let form = |chan_id| format!("https://youtube.com/channel/{chan_id}");
+    let chan_link = channel_id.filter(|s| !s.trim().is_empty()).map(form);
+    let channel_title = channel_title.filter(|s| !s.trim().is_empty());
+
+    let mut about = format!("{about}\n\n--------------------", about = "About".to_bold());
+
+    if channel_title.is_some() || chan_link.is_some() {
+        about += "\n\n[Published by]\n";
+    }
+
+    if let Some(channel_title) = channel_title {
+        about += &*format!("\n{channel_title}");
+    }
+
+    if let Some(chan_link) = chan_link {
+        about += &*format!("\n{chan_link}");
+    }
+
+    if let Some(published_at) = published_at.filter(|s| !s.trim().is_empty()) {
+        about += &*format!("\n\n[Published at]\n \n{published_at}");
+    }
+
+    about
+}
+
 #[allow(clippy::unwrap_used)]
 #[allow(clippy::expect_used)]
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use crate::model::youtube::traits::Searchable;
+use google_youtube3::api::PlaylistItem;
 use parse_display::Display;
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -7,7 +8,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 /// It is produced by all authentication flows.
 /// It authenticates certain operations, and must be refreshed once it reached it's expiry date.
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
-pub(crate) struct YouTubeAccessToken {
+pub struct YouTubeAccessToken {
     /// The token that your application sends to authorize a Google API request.
     pub(crate) access_token: String,
     /// Date and time when access_token expires (it does so after 1 hour).
@@ -84,6 +85,12 @@ pub(crate) const SCOPE_YOUTUBE_READONLY: &str = "https://www.googleapis.com/auth
 /// Required in token request to get optional `refresh token` in addition to `access token`.
 pub(crate) const ACCESS_TYPE: &str = "offline";
 
+/// `PKCE` code challenge is a `base64url` encoded `SHA256` hash of code verifier.
+pub(crate) const CODE_CHALLENGE_METHOD: &str = "S256";
+
+/// Length of `PKCE` code verifier (must be from 43 to 128 characters).
+pub(crate) const CODE_VERIFIER_LEN: usize = 64;
+
 #[derive(Debug, Display)]
 #[display(style = "snake_case")]
 pub(crate) enum RequiredAuthURLParams {
@@ -100,6 +107,59 @@ pub(crate) struct SearchableItem {
     pub(crate) date: Option<String>,
     pub(crate) link: Option<String>,
     pub(crate) about: Option<String>,
+    /// Set if item is a video or a playlist item.
+    pub(crate) video_id: Option<String>,
+    /// Set after `videos.list` lookup.
+    pub(crate) video: Option<VideoDetails>,
+    /// Set if item is a subscription.
+    pub(crate) channel_id: Option<String>,
+    /// Set if item is a playlist.
+    pub(crate) playlist_id: Option<String>,
+    /// Videos in a playlist, or uploads of a subscribed channel.
+    pub(crate) item_count: Option<u32>,
+    /// `RFC 3339` date, set after `add_last_activity` lookup.
+    pub(crate) last_activity: Option<String>,
+}
+
+/// Whether a video can be watched.
+#[derive(Default, PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) enum Availability {
+    #[default]
+    Available,
+    /// User's own private video.
+    Private,
+    /// Deleted, or made private by another channel, so `videos.list` doesn't return it.
+    Unavailable,
+}
+
+/// Video info taken from `videos.list`.
+#[derive(Default, PartialEq, Eq, Debug, Clone)]
+pub(crate) struct VideoDetails {
+    pub(crate) duration: Option<std::time::Duration>,
+    pub(crate) view_count: Option<u64>,
+    pub(crate) like_count: Option<u64>,
+    /// `public`, `unlisted` or `private`.
+    pub(crate) privacy_status: Option<String>,
+    pub(crate) availability: Availability,
+}
+
+/// Matches found in one playlist, with overall progress of searching.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct PlaylistScan {
+    /// How many playlists are scanned so far, including this one.
+    pub(crate) scanned: usize,
+    pub(crate) total: usize,
+    /// Matches with their scores, best ones first.
+    /// Scores let matches of different playlists be ranked together.
+    pub(crate) matches: Vec<(f64, SearchableItem)>,
+}
+
+/// Playlist with all of its items.
+#[derive(Default, Debug, Clone)]
+pub(crate) struct PlaylistContents {
+    pub(crate) playlist_id: String,
+    pub(crate) title: String,
+    pub(crate) items: Vec<PlaylistItem>,
 }
 
 impl Searchable for SearchableItem {
@@ -1,27 +1,55 @@
 use error_traits::LogErr;
 use maptypings::ForgetValue;
-use teloxide::payloads::{EditMessageTextSetters, SendMessageSetters};
+use std::time::Instant;
+use teloxide::payloads::{
+    AnswerInlineQuerySetters, EditMessageTextSetters, SendDocumentSetters, SendMessageSetters,
+};
 use teloxide::requests::Requester;
 use teloxide::types::ParseMode::Html;
-use teloxide::types::{CallbackQuery, ChatId, InlineKeyboardMarkup, Message};
+use teloxide::types::{
+    ChatId, InlineKeyboardMarkup, InlineQueryResult, InputFile, Message, UserId,
+};
 use teloxide::Bot;
+use tokio::sync::mpsc;
 
+use crate::model::config::types::AppConfig;
+use crate::model::db::traits::{SnapshotStore, TokenStore};
 use crate::model::dialogue::types::{CommandConfig, DialogueData, MessageWithKB, TheDialogue};
+use crate::model::export::types::ExportDocument;
+use crate::model::handlers::callback::duplicates_cmd::execute_find_duplicates_command;
+use crate::model::handlers::callback::export_cmd::execute_export_command;
+use crate::model::handlers::callback::inactive_cmd::execute_inactive_subscriptions_command;
 use crate::model::handlers::callback::list_cmd::execute_list_command;
+use crate::model::handlers::callback::playlist_stats_cmd::{
+    execute_playlist_stats_command, StatsReport,
+};
 use crate::model::handlers::callback::search_cmd::execute_search_command;
 use crate::model::handlers::callback::search_videos_in_playlits::execute_search_videos_in_playlists_command;
-use crate::model::utils::HTMLise;
-use crate::model::youtube::types::SearchableItem;
+use crate::model::handlers::callback::snapshot_cmd::{
+    execute_playlist_changes_command, execute_snapshot_playlists_command,
+};
+use crate::model::handlers::callback::subscription_stats_cmd::execute_subscription_stats_command;
+use crate::model::pagination::funcs::{page_kb, render_page};
+use crate::model::pagination::types::{
+    ResultCache, INLINE_ANSWER_CACHE_TIME, RESULTS_PER_PAGE, RESULTS_TTL,
+};
+use crate::model::youtube::funcs::search_cmd::merge_ranked;
+use crate::model::youtube::types::{PlaylistScan, SearchableItem};
 use crate::view::funcs::shorthands::{send_message, update_dialogue};
 use crate::view::types::Sendable;
+use crate::view::types::PROGRESS_EDIT_INTERVAL;
 
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn update_view<S>(
     bot: &Bot,
     send_to: ChatId,
     sendable: Sendable<S>,
     dialogue: TheDialogue,
-    opt_callback: Option<CallbackQuery>,
+    opt_user_id: Option<UserId>,
+    token_store: &dyn TokenStore,
+    snapshot_store: &dyn SnapshotStore,
+    app_config: &AppConfig,
+    result_cache: &ResultCache,
 ) where
     S: Into<String>,
 {
@@ -57,7 +85,20 @@ pub(crate) async fn update_view<S>(
             .await;
         }
         Sendable::ExecuteCommand(command_config) => {
-            execute_command(bot, send_to, opt_callback.unwrap(), command_config).await;
+            let Some(user_id) = opt_user_id else {
+                return send_user_error(bot, send_to, "⚠ Internal error ⚠").await;
+            };
+            execute_command(
+                bot,
+                send_to,
+                user_id,
+                command_config,
+                token_store,
+                snapshot_store,
+                app_config,
+                result_cache,
+            )
+            .await;
         }
     }
 }
@@ -152,39 +193,32 @@ async fn edit_message_and_keyboard(
     }
 }
 
+/// Sends all results as a single message, with a keyboard for browsing pages.
+/// Results are kept in `result_cache`, so that only a short id goes into callback data.
 async fn send_results(
     bot: &Bot,
     prefix: Option<impl Into<String>>,
     postfix: Option<impl Into<String>>,
     send_to: ChatId,
+    owner: UserId,
     values: Vec<SearchableItem>,
+    result_cache: &ResultCache,
 ) {
     let log_prefix = " [:: LOG ::]    | @:[fn::send_results] error: ";
-    let formatting = |i: SearchableItem| {
-        let title = i.title.as_deref().unwrap_or("No title 🤷‍♂️");
-        let descr = i.description.as_deref().unwrap_or("No description 🤷‍♂️");
-        let link = i.link.as_deref().unwrap_or("No link 🤷‍♂️");
-        let about = i
-            .about
-            .as_deref()
-            .map(|s| format!("\n\n{s}"))
-            .unwrap_or_default();
-        format!(
-            "{}{}{}{}",
-            title.to_bold() + " \n\n",
-            descr.to_owned() + " \n\n",
-            link,
-            about
-        )
-    };
 
     if let Some(p) = prefix {
         send_message(bot, send_to, p, log_prefix).await;
     }
 
-    for v in values {
-        let text = formatting(v);
-        send_message(bot, send_to, text, log_prefix).await;
+    if !values.is_empty() {
+        let (text, result_count) = (render_page(&values, 0), values.len());
+        let id = result_cache.save(owner, values, RESULTS_TTL).await;
+        bot.send_message(send_to, text)
+            .reply_markup(page_kb(&id, 0, result_count))
+            .parse_mode(Html)
+            .await
+            .log_err(log_prefix)
+            .forget_val();
     }
 
     if let Some(p) = postfix {
@@ -192,30 +226,249 @@ async fn send_results(
     }
 }
 
-async fn execute_command(
+/// Collects matches as they arrive into a single page of results, kept up to date
+/// along with a progress message. Both are edited at most once per `PROGRESS_EDIT_INTERVAL`.
+/// When search is finished, results are saved to `result_cache` and page keyboard is attached.
+async fn send_search_progress(
+    bot: &Bot,
+    send_to: ChatId,
+    owner: UserId,
+    mut scans: mpsc::UnboundedReceiver<PlaylistScan>,
+    result_cache: &ResultCache,
+) {
+    let log_prefix = " [:: LOG ::]    | @:[fn::send_search_progress] error: ";
+
+    let (mut progress_msg, mut page_msg) = (LiveMessage::default(), LiveMessage::default());
+    // Matches of all playlists scanned so far, ranked together by score.
+    let (mut ranked, mut progress_text) = (vec![], String::new());
+    let mut opt_last_edit: Option<Instant> = None;
+    while let Some(scan) = scans.recv().await {
+        merge_ranked(&mut ranked, scan.matches);
+        progress_text = format!(
+            "Scanned {}/{} playlists 🔎 \nFound {} results",
+            scan.scanned,
+            scan.total,
+            ranked.len()
+        );
+        if opt_last_edit.is_some_and(|t| t.elapsed() < PROGRESS_EDIT_INTERVAL) {
+            continue;
+        }
+        progress_msg
+            .show(bot, send_to, &progress_text, None, log_prefix)
+            .await;
+        if !ranked.is_empty() {
+            let first_page: Vec<_> = ranked
+                .iter()
+                .take(RESULTS_PER_PAGE)
+                .map(|(_, item)| item.clone())
+                .collect();
+            let page = render_page(&first_page, 0);
+            page_msg.show(bot, send_to, &page, None, log_prefix).await;
+        }
+        opt_last_edit = Some(Instant::now());
+    }
+
+    progress_msg
+        .show(bot, send_to, &progress_text, None, log_prefix)
+        .await;
+    let results: Vec<_> = ranked.into_iter().map(|(_, item)| item).collect();
+    if !results.is_empty() {
+        let (page, result_count) = (render_page(&results, 0), results.len());
+        let id = result_cache.save(owner, results, RESULTS_TTL).await;
+        let kb = page_kb(&id, 0, result_count);
+        page_msg
+            .show(bot, send_to, &page, Some(kb), log_prefix)
+            .await;
+    }
+}
+
+/// Message that is sent once and then edited, but only when there is something new to show.
+#[derive(Default)]
+struct LiveMessage {
+    opt_message: Option<Message>,
+    shown_text: String,
+}
+
+impl LiveMessage {
+    async fn show(
+        &mut self,
+        bot: &Bot,
+        send_to: ChatId,
+        text: &str,
+        opt_kb: Option<InlineKeyboardMarkup>,
+        log_prefix: &str,
+    ) {
+        if text.is_empty() || (self.shown_text == text && opt_kb.is_none()) {
+            return;
+        }
+        match self.opt_message.as_ref() {
+            None => {
+                let mut req = bot.send_message(send_to, text).parse_mode(Html);
+                if let Some(kb) = opt_kb {
+                    req = req.reply_markup(kb);
+                }
+                self.opt_message = req.await.log_err(log_prefix).ok();
+            }
+            Some(m) => {
+                let mut req = bot
+                    .edit_message_text(m.chat.id, m.id, text)
+                    .parse_mode(Html);
+                if let Some(kb) = opt_kb {
+                    req = req.reply_markup(kb);
+                }
+                req.await.log_err(log_prefix).forget_val();
+            }
+        }
+        text.clone_into(&mut self.shown_text);
+    }
+}
+
+async fn send_document(bot: &Bot, send_to: ChatId, document: ExportDocument) {
+    let log_prefix = " [:: LOG ::]    | @:[fn::send_document] error: ";
+    let ExportDocument {
+        file_name,
+        content,
+        record_count,
+    } = document;
+    bot.send_document(send_to, InputFile::memory(content).file_name(file_name))
+        .caption(format!("Finished! ✔ \nExported {record_count} items"))
+        .await
+        .log_err(log_prefix)
+        .forget_val();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn execute_command(
     bot: &Bot,
     send_to: ChatId,
-    callback: CallbackQuery,
+    user_id: UserId,
     config: CommandConfig,
+    token_store: &dyn TokenStore,
+    snapshot_store: &dyn SnapshotStore,
+    app_config: &AppConfig,
+    result_cache: &ResultCache,
 ) {
     let log_prefix = " [:: LOG ::]   @:[fn::execute_command] error: ";
 
     send_message(bot, send_to, "Searching, please wait 🕵️‍♂️", log_prefix).await;
     let results = match config {
-        CommandConfig::SearchConfig(s) => execute_search_command(s, callback).await,
-        CommandConfig::ListConfig(l) => execute_list_command(l, callback).await,
+        CommandConfig::SearchConfig(s) => {
+            execute_search_command(s, user_id, send_to, token_store, app_config).await
+        }
+        CommandConfig::ListConfig(l) => {
+            execute_list_command(l, user_id, send_to, token_store, app_config, result_cache).await
+        }
         CommandConfig::SearchVideosInPlaylistsConfig(sv) => {
-            execute_search_videos_in_playlists_command(sv, callback).await
+            let (progress, scans) = mpsc::unbounded_channel();
+            let search = execute_search_videos_in_playlists_command(
+                sv,
+                user_id,
+                send_to,
+                token_store,
+                app_config,
+                progress,
+            );
+            let progress = send_search_progress(bot, send_to, user_id, scans, result_cache);
+            tokio::join!(search, progress).0
+        }
+        CommandConfig::SnapshotPlaylists => {
+            execute_snapshot_playlists_command(
+                user_id,
+                send_to,
+                token_store,
+                snapshot_store,
+                app_config,
+            )
+            .await
+        }
+        CommandConfig::PlaylistChanges => {
+            execute_playlist_changes_command(
+                user_id,
+                send_to,
+                token_store,
+                snapshot_store,
+                app_config,
+            )
+            .await
+        }
+        CommandConfig::FindDuplicatesConfig(d) => {
+            execute_find_duplicates_command(d, user_id, send_to, token_store, app_config).await
+        }
+        CommandConfig::InactiveSubscriptionsConfig(i) => {
+            execute_inactive_subscriptions_command(i, user_id, send_to, token_store, app_config)
+                .await
+        }
+        CommandConfig::PlaylistStatsConfig(s) => {
+            match execute_playlist_stats_command(s, user_id, send_to, token_store, app_config).await
+            {
+                Ok(StatsReport::Table(triplet)) => Ok(triplet),
+                Ok(StatsReport::Document(document)) => {
+                    send_document(bot, send_to, document).await;
+                    return;
+                }
+                Err(e) => Err(e),
+            }
+        }
+        CommandConfig::SubscriptionStatsConfig(s) => {
+            match execute_subscription_stats_command(s, user_id, send_to, token_store, app_config)
+                .await
+            {
+                Ok(StatsReport::Table(triplet)) => Ok(triplet),
+                Ok(StatsReport::Document(document)) => {
+                    send_document(bot, send_to, document).await;
+                    return;
+                }
+                Err(e) => Err(e),
+            }
+        }
+        CommandConfig::ExportConfig(e) => {
+            match execute_export_command(e, user_id, send_to, token_store, app_config).await {
+                Ok(document) => send_document(bot, send_to, document).await,
+                Err(e) => send_message(bot, send_to, e, log_prefix).await,
+            };
+            return;
         }
     };
     match results {
         Err(e) => send_message(bot, send_to, e, log_prefix).await,
         Ok((prefix, results, postfix)) => {
-            send_results(bot, prefix, postfix, send_to, results).await;
+            send_results(
+                bot,
+                prefix,
+                postfix,
+                send_to,
+                user_id,
+                results,
+                result_cache,
+            )
+            .await;
         }
     };
 }
 
+/// Answers `inline query`.
+/// If user hasn't logged in yet, answer has a button, that opens chat with the bot.
+pub(crate) async fn answer_inline_query(
+    bot: &Bot,
+    query_id: String,
+    opt_results: Option<Vec<InlineQueryResult>>,
+) {
+    let log_prefix = " [:: LOG ::]    | @:[fn::answer_inline_query] error: ";
+    let answer = match opt_results {
+        Some(results) => bot.answer_inline_query(query_id, results),
+        None => bot
+            .answer_inline_query(query_id, vec![])
+            .switch_pm_text("Log in to search 🔑")
+            .switch_pm_parameter("login"),
+    };
+    answer
+        .is_personal(true)
+        .cache_time(INLINE_ANSWER_CACHE_TIME)
+        .await
+        .log_err(log_prefix)
+        .forget_val();
+}
+
 mod shorthands {
     use super::{
         Bot, ChatId, DialogueData, ForgetValue, Html, LogErr, Requester, SendMessageSetters,
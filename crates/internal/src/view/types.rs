@@ -1,6 +1,10 @@
 use crate::model::dialogue::types::{CommandConfig, DialogueData};
+use std::time::Duration;
 use teloxide::types::{InlineKeyboardMarkup, Message};
 
+/// Minimal delay between edits of a progress message, to stay within `Telegram` rate limits.
+pub(crate) const PROGRESS_EDIT_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone)]
 pub(crate) enum Sendable<S>
 where